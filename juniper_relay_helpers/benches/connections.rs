@@ -0,0 +1,124 @@
+use criterion::{BatchSize, Criterion, black_box, criterion_group, criterion_main};
+use juniper::GraphQLObject;
+use juniper_relay_helpers::{
+    Cursor, CursorByKey, KeyedCursorProvider, OffsetCursor, OffsetCursorProvider, PageRequest,
+    RelayConnection, StringCursor,
+};
+
+const PAGE_SIZE: i32 = 1000;
+
+#[derive(Clone, GraphQLObject, RelayConnection)]
+#[relay(cursor = OffsetCursor)]
+pub struct Track {
+    pub id: i32,
+    pub title: String,
+}
+
+#[derive(Clone, GraphQLObject, RelayConnection)]
+#[relay(cursor = StringCursor)]
+pub struct Album {
+    pub id: i32,
+    pub title: String,
+}
+
+impl CursorByKey for Album {
+    fn cursor_key(&self) -> String {
+        format!("album-{}", self.id)
+    }
+}
+
+fn tracks() -> Vec<Option<Track>> {
+    (0..PAGE_SIZE)
+        .map(|id| {
+            Some(Track {
+                id,
+                title: format!("Track {id}"),
+            })
+        })
+        .collect()
+}
+
+fn albums() -> Vec<Option<Album>> {
+    (0..PAGE_SIZE)
+        .map(|id| {
+            Some(Album {
+                id,
+                title: format!("Album {id}"),
+            })
+        })
+        .collect()
+}
+
+fn offset_connections(c: &mut Criterion) {
+    let nodes = tracks();
+    let page_request = PageRequest::new(Some(PAGE_SIZE), Some(OffsetCursor::new(99)), None);
+
+    c.bench_function("offset new 1000", |b| {
+        b.iter(|| {
+            TrackRelayConnection::new(
+                black_box(Some(&nodes)),
+                Some(10_000),
+                OffsetCursorProvider::new(),
+                Some(page_request.clone()),
+            )
+        })
+    });
+
+    c.bench_function("offset from_iter 1000", |b| {
+        b.iter_batched(
+            || nodes.iter().flatten().cloned().collect::<Vec<_>>(),
+            |owned| {
+                TrackRelayConnection::from_iter(
+                    owned,
+                    Some(10_000),
+                    OffsetCursorProvider::new(),
+                    Some(page_request.clone()),
+                )
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn keyed_connections(c: &mut Criterion) {
+    let nodes = albums();
+    let page_request = PageRequest::new(
+        Some(PAGE_SIZE),
+        Some(StringCursor::new("album-99".to_string())),
+        None,
+    );
+
+    c.bench_function("keyed new 1000", |b| {
+        b.iter(|| {
+            AlbumRelayConnection::new(
+                black_box(Some(&nodes)),
+                None,
+                KeyedCursorProvider,
+                Some(page_request.clone()),
+            )
+        })
+    });
+}
+
+fn cursor_encoding(c: &mut Criterion) {
+    let cursors: Vec<OffsetCursor> = (0..PAGE_SIZE)
+        .map(|offset| OffsetCursor::new(offset).with_total(Some(10_000)))
+        .collect();
+
+    c.bench_function("offset encode 1000", |b| {
+        b.iter(|| {
+            black_box(&cursors)
+                .iter()
+                .map(Cursor::to_encoded_string)
+                .collect::<Vec<_>>()
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    offset_connections,
+    keyed_connections,
+    cursor_encoding
+);
+criterion_main!(benches);
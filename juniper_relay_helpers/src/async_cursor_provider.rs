@@ -0,0 +1,183 @@
+use crate::{Cursor, PageInfoFactory, PaginationMetadata};
+
+/// As `CursorProvider`, for cursors that need I/O to build, such as swapping a key for a short
+/// opaque token stored in Redis. Used with `RelayConnection::new_async`.
+///
+/// The methods return `Send` futures so connections can be built inside Juniper's async resolvers.
+/// Implement them with `async fn`:
+///
+/// ```
+/// use juniper::GraphQLObject;
+/// use juniper_relay_helpers::{
+///     AsyncCursorProvider, PageInfoFactory, PaginationMetadata, RelayConnection, StringCursor,
+/// };
+///
+/// #[derive(Clone, GraphQLObject, RelayConnection)]
+/// pub struct Expedition {
+///     pub id: String,
+/// }
+///
+/// struct TokenProvider;
+///
+/// impl TokenProvider {
+///     async fn token_for(&self, expedition: &Expedition) -> StringCursor {
+///         // Look the token up in a cache here.
+///         StringCursor::new(format!("token-{}", expedition.id))
+///     }
+/// }
+///
+/// impl AsyncCursorProvider<Expedition> for TokenProvider {
+///     type CursorType = StringCursor;
+///
+///     async fn get_cursor_for_item(
+///         &self,
+///         _metadata: &PaginationMetadata<StringCursor>,
+///         _item_idx: i32,
+///         item: Option<&Expedition>,
+///     ) -> StringCursor {
+///         match item {
+///             Some(expedition) => self.token_for(expedition).await,
+///             None => StringCursor::default(),
+///         }
+///     }
+///
+///     async fn get_page_info<PageInfoType>(
+///         &self,
+///         metadata: &PaginationMetadata<StringCursor>,
+///         items: Option<&[Option<Expedition>]>,
+///     ) -> PageInfoType
+///     where
+///         PageInfoType: PageInfoFactory<StringCursor>,
+///     {
+///         let items = items.unwrap_or_default();
+///         let last = items.last().and_then(|item| item.as_ref());
+///         PageInfoType::new(
+///             false,
+///             metadata.more_available.unwrap_or(false),
+///             None,
+///             match last {
+///                 Some(expedition) => Some(self.token_for(expedition).await),
+///                 None => None,
+///             },
+///         )
+///     }
+/// }
+///
+/// # async fn expeditions() -> ExpeditionRelayConnection {
+/// let items = vec![Some(Expedition { id: "33".to_string() })];
+/// ExpeditionRelayConnection::new_async(Some(&items), None, TokenProvider, None).await
+/// # }
+/// ```
+pub trait AsyncCursorProvider<ItemT> {
+    type CursorType: Cursor;
+
+    /// As `CursorProvider::get_cursor_for_item`.
+    fn get_cursor_for_item(
+        &self,
+        metadata: &PaginationMetadata<Self::CursorType>,
+        item_idx: i32,
+        item: Option<&ItemT>,
+    ) -> impl Future<Output = Self::CursorType> + Send;
+
+    /// As `CursorProvider::get_page_info`.
+    fn get_page_info<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<Self::CursorType>,
+        items: Option<&[Option<ItemT>]>,
+    ) -> impl Future<Output = PageInfoType> + Send
+    where
+        PageInfoType: PageInfoFactory<Self::CursorType>;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        AsyncCursorProvider, CursorProvider, OffsetCursor, OffsetCursorProvider, PageInfoFactory,
+        PageRequest, PaginationMetadata, RelayConnection,
+    };
+    use juniper::GraphQLObject;
+
+    #[derive(Debug, Clone, GraphQLObject, RelayConnection)]
+    #[relay(cursor = OffsetCursor, expose_page_size)]
+    pub struct Nevron {
+        name: String,
+    }
+
+    /// Hands out offset cursors, yielding to the runtime before each one as a lookup would.
+    struct YieldingOffsetProvider;
+
+    impl AsyncCursorProvider<Nevron> for YieldingOffsetProvider {
+        type CursorType = OffsetCursor;
+
+        async fn get_cursor_for_item(
+            &self,
+            metadata: &PaginationMetadata<OffsetCursor>,
+            item_idx: i32,
+            item: Option<&Nevron>,
+        ) -> OffsetCursor {
+            tokio::task::yield_now().await;
+            OffsetCursorProvider::new().get_cursor_for_item(metadata, item_idx, item)
+        }
+
+        async fn get_page_info<PageInfoType>(
+            &self,
+            metadata: &PaginationMetadata<OffsetCursor>,
+            items: Option<&[Option<Nevron>]>,
+        ) -> PageInfoType
+        where
+            PageInfoType: PageInfoFactory<OffsetCursor>,
+        {
+            tokio::task::yield_now().await;
+            OffsetCursorProvider::new().get_page_info(metadata, items)
+        }
+    }
+
+    fn nevrons() -> Vec<Option<Nevron>> {
+        ["Chromatic Troubadour", "Bourgeon", "Lampmaster"]
+            .into_iter()
+            .map(|name| {
+                Some(Nevron {
+                    name: name.to_string(),
+                })
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_new_async_matches_new() {
+        let request = || Some(PageRequest::new(Some(3), Some(OffsetCursor::new(4)), None));
+
+        let conn = NevronRelayConnection::new_async(
+            Some(&nevrons()),
+            Some(20),
+            YieldingOffsetProvider,
+            request(),
+        )
+        .await;
+        let sync_conn = NevronRelayConnection::new(
+            Some(&nevrons()),
+            Some(20),
+            OffsetCursorProvider::new(),
+            request(),
+        );
+
+        let cursors = |conn: &NevronRelayConnection| -> Vec<Option<OffsetCursor>> {
+            conn.edges
+                .iter()
+                .flatten()
+                .flatten()
+                .map(|edge| edge.cursor.clone())
+                .collect()
+        };
+        assert_eq!(cursors(&conn), cursors(&sync_conn));
+        assert_eq!(cursors(&conn)[0], Some(OffsetCursor::new(5)));
+        assert_eq!(conn.count, Some(20));
+        assert_eq!(conn.page_size, Some(3));
+        assert_eq!(conn.page_info.end_cursor, sync_conn.page_info.end_cursor);
+        assert!(conn.page_info.has_previous_page);
+        assert!(conn.page_info.has_next_page);
+
+        let conn = NevronRelayConnection::new_async(None, None, YieldingOffsetProvider, None).await;
+        assert!(conn.edges.is_none());
+    }
+}
@@ -0,0 +1,303 @@
+use crate::{Cursor, PageRequest, PageRequestError};
+use juniper::macros::reflect;
+use juniper::marker::IsInputType;
+use juniper::meta::MetaType;
+use juniper::{
+    ArcStr, FieldError, FromInputValue, GraphQLType, GraphQLValue, InputValue, IntoFieldError,
+    Registry, ScalarValue, ToInputValue,
+};
+
+/// The four pagination arguments as one input object, for resolvers that would otherwise declare
+/// `first`, `after`, `last` and `before` one by one. Unlike `#[relay(page_args)]` it isn't tied to a
+/// node, so every connection paginated with the same cursor type can share it.
+///
+/// It's named after the cursor in the schema, so `ConnectionArgs<OffsetCursor>` is an
+/// `OffsetCursorConnectionArgs` input, and connections with different cursors get different inputs.
+///
+/// ```
+/// use juniper::{graphql_object, EmptyMutation, EmptySubscription, FieldResult, GraphQLObject};
+/// use juniper_relay_helpers::{
+///     ConnectionArgs, OffsetCursor, OffsetCursorProvider, RelayConnection,
+/// };
+///
+/// #[derive(Clone, GraphQLObject, RelayConnection)]
+/// #[relay(cursor = OffsetCursor)]
+/// pub struct Lantern {
+///     pub colour: String,
+/// }
+///
+/// pub struct Query;
+///
+/// #[graphql_object]
+/// impl Query {
+///     fn lanterns(args: ConnectionArgs<OffsetCursor>) -> FieldResult<LanternRelayConnection> {
+///         let page_request = args.into_page_request()?;
+///         let lanterns = vec![Some(Lantern { colour: "amber".to_string() })];
+///         Ok(LanternRelayConnection::new(
+///             Some(&lanterns),
+///             Some(1),
+///             OffsetCursorProvider::new(),
+///             Some(page_request),
+///         ))
+///     }
+/// }
+///
+/// let schema = juniper::RootNode::new(
+///     Query,
+///     EmptyMutation::<()>::new(),
+///     EmptySubscription::<()>::new(),
+/// );
+/// let (result, errors) = juniper::execute_sync(
+///     "{ lanterns(args: { first: 1 }) { edges { node { colour } } } }",
+///     None,
+///     &schema,
+///     &juniper::Variables::new(),
+///     &(),
+/// )
+/// .unwrap();
+/// assert!(errors.is_empty());
+/// assert!(result.to_string().contains("amber"));
+/// ```
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct ConnectionArgs<CursorT>
+where
+    CursorT: Cursor,
+{
+    /// The number of items to return, paging forwards.
+    pub first: Option<i32>,
+
+    /// Return items after this cursor, paging forwards.
+    pub after: Option<CursorT>,
+
+    /// The number of items to return, paging backwards.
+    pub last: Option<i32>,
+
+    /// Return items before this cursor, paging backwards.
+    pub before: Option<CursorT>,
+}
+
+impl<CursorT> ConnectionArgs<CursorT>
+where
+    CursorT: Cursor,
+{
+    /// The arguments as a `PageRequest`, as given. Use `into_page_request` to have them validated.
+    pub fn to_page_request(&self) -> PageRequest<CursorT> {
+        PageRequest {
+            first: self.first,
+            after: self.after.clone(),
+            last: self.last,
+            before: self.before.clone(),
+        }
+    }
+
+    /// Builds the `PageRequest` as `PageRequest::from_args` does, rejecting arguments that mix
+    /// forward and backward paging or are otherwise invalid.
+    pub fn into_page_request(self) -> Result<PageRequest<CursorT>, PageRequestError> {
+        PageRequest::from_args(self.first, self.after, self.last, self.before)
+    }
+}
+
+impl<CursorT> From<ConnectionArgs<CursorT>> for PageRequest<CursorT>
+where
+    CursorT: Cursor,
+{
+    fn from(args: ConnectionArgs<CursorT>) -> Self {
+        args.to_page_request()
+    }
+}
+
+// The input object is implemented by hand rather than derived, as the derive can't name the type
+// after its cursor, or bound a generic cursor for every `ScalarValue`.
+
+impl<CursorT, S> GraphQLValue<S> for ConnectionArgs<CursorT>
+where
+    CursorT: Cursor + GraphQLType<S, TypeInfo = ()> + FromInputValue<S>,
+    <CursorT as FromInputValue<S>>::Error: IntoFieldError<S>,
+    S: ScalarValue,
+{
+    type Context = ();
+    type TypeInfo = ();
+
+    fn type_name(&self, info: &()) -> Option<ArcStr> {
+        <Self as GraphQLType<S>>::name(info)
+    }
+}
+
+impl<CursorT, S> GraphQLType<S> for ConnectionArgs<CursorT>
+where
+    CursorT: Cursor + GraphQLType<S, TypeInfo = ()> + FromInputValue<S>,
+    <CursorT as FromInputValue<S>>::Error: IntoFieldError<S>,
+    S: ScalarValue,
+{
+    fn name(info: &()) -> Option<ArcStr> {
+        CursorT::name(info).map(|cursor| format!("{cursor}ConnectionArgs").into())
+    }
+
+    fn meta(info: &(), registry: &mut Registry<S>) -> MetaType<S> {
+        let args = [
+            registry
+                .arg::<Option<i32>>("first", &())
+                .description("The number of items to return, paging forwards"),
+            registry
+                .arg::<Option<CursorT>>("after", &())
+                .description("Return items after this cursor, paging forwards"),
+            registry
+                .arg::<Option<i32>>("last", &())
+                .description("The number of items to return, paging backwards"),
+            registry
+                .arg::<Option<CursorT>>("before", &())
+                .description("Return items before this cursor, paging backwards"),
+        ];
+
+        registry
+            .build_input_object_type::<Self>(info, &args)
+            .description(
+                "Pagination arguments. Page forwards with first and after, or backwards with last and before, but not both.",
+            )
+            .into_meta()
+    }
+}
+
+impl<CursorT, S> FromInputValue<S> for ConnectionArgs<CursorT>
+where
+    CursorT: Cursor + FromInputValue<S>,
+    <CursorT as FromInputValue<S>>::Error: IntoFieldError<S>,
+    S: ScalarValue,
+{
+    type Error = FieldError<S>;
+
+    fn from_input_value(value: &InputValue<S>) -> Result<Self, FieldError<S>> {
+        let fields = value.to_object_value().ok_or_else(|| {
+            FieldError::<S>::from(format!(
+                "Expected the connection arguments as an object, found: {value}"
+            ))
+        })?;
+
+        // Fields left out are the same as ones given as `null`.
+        let null = InputValue::Null;
+        let field = |name: &str| fields.get(name).copied().unwrap_or(&null);
+
+        Ok(ConnectionArgs {
+            first: Option::<i32>::from_input_value(field("first"))
+                .map_err(IntoFieldError::into_field_error)?,
+            after: Option::<CursorT>::from_input_value(field("after"))
+                .map_err(IntoFieldError::into_field_error)?,
+            last: Option::<i32>::from_input_value(field("last"))
+                .map_err(IntoFieldError::into_field_error)?,
+            before: Option::<CursorT>::from_input_value(field("before"))
+                .map_err(IntoFieldError::into_field_error)?,
+        })
+    }
+}
+
+impl<CursorT, S> ToInputValue<S> for ConnectionArgs<CursorT>
+where
+    CursorT: Cursor + ToInputValue<S>,
+    S: ScalarValue,
+{
+    fn to_input_value(&self) -> InputValue<S> {
+        InputValue::object(
+            [
+                ("first", self.first.to_input_value()),
+                ("after", self.after.to_input_value()),
+                ("last", self.last.to_input_value()),
+                ("before", self.before.to_input_value()),
+            ]
+            .into_iter()
+            .collect(),
+        )
+    }
+}
+
+impl<CursorT, S> IsInputType<S> for ConnectionArgs<CursorT>
+where
+    CursorT: Cursor + IsInputType<S> + GraphQLType<S, TypeInfo = ()> + FromInputValue<S>,
+    <CursorT as FromInputValue<S>>::Error: IntoFieldError<S>,
+    S: ScalarValue,
+{
+}
+
+// Used by Juniper's compile time checks of interface field arguments. The name has to be a constant,
+// so it can't include the cursor's, which makes arguments using different cursors look alike there.
+impl<CursorT, S> reflect::BaseType<S> for ConnectionArgs<CursorT>
+where
+    CursorT: Cursor,
+{
+    const NAME: reflect::Type = "ConnectionArgs";
+}
+
+impl<CursorT, S> reflect::BaseSubTypes<S> for ConnectionArgs<CursorT>
+where
+    CursorT: Cursor,
+{
+    const NAMES: reflect::Types = &[<Self as reflect::BaseType<S>>::NAME];
+}
+
+impl<CursorT, S> reflect::WrappedType<S> for ConnectionArgs<CursorT>
+where
+    CursorT: Cursor,
+{
+    const VALUE: reflect::WrappedValue = 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ConnectionArgs, OffsetCursor, PageRequest, PageRequestError};
+    use juniper::{DefaultScalarValue, FromInputValue, InputValue, ToInputValue};
+
+    #[test]
+    fn test_to_page_request() {
+        let args = ConnectionArgs {
+            first: Some(10),
+            after: Some(OffsetCursor::new(4)),
+            ..Default::default()
+        };
+        assert_eq!(
+            args.to_page_request(),
+            PageRequest::new(Some(10), Some(OffsetCursor::new(4)), None)
+        );
+        assert_eq!(
+            args.into_page_request(),
+            Ok(PageRequest::new(Some(10), Some(OffsetCursor::new(4)), None))
+        );
+
+        let mixed = ConnectionArgs {
+            first: Some(10),
+            before: Some(OffsetCursor::new(4)),
+            ..Default::default()
+        };
+        assert_eq!(
+            mixed.into_page_request(),
+            Err(PageRequestError::MixedDirections)
+        );
+    }
+
+    #[test]
+    fn test_input_value_round_trip() {
+        let args = ConnectionArgs {
+            last: Some(5),
+            before: Some(OffsetCursor::new(20)),
+            ..Default::default()
+        };
+        let input: InputValue<DefaultScalarValue> = args.to_input_value();
+        assert_eq!(ConnectionArgs::from_input_value(&input), Ok(args));
+
+        // Missing fields are read as null.
+        let partial = InputValue::<DefaultScalarValue>::object(
+            [("first", InputValue::scalar(3))].into_iter().collect(),
+        );
+        assert_eq!(
+            ConnectionArgs::<OffsetCursor>::from_input_value(&partial),
+            Ok(ConnectionArgs {
+                first: Some(3),
+                ..Default::default()
+            })
+        );
+        assert!(
+            ConnectionArgs::<OffsetCursor>::from_input_value(
+                &InputValue::<DefaultScalarValue>::scalar(3)
+            )
+            .is_err()
+        );
+    }
+}
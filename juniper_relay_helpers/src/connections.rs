@@ -1,5 +1,12 @@
 use crate::cursor_provider::CursorProvider;
-use crate::{Cursor, RelayEdge};
+use crate::{
+    AsyncCursorProvider, Cursor, PageInfoFactory, RelayEdge, RelayPageInfo, try_map_edges,
+};
+use std::borrow::Cow;
+use std::convert::Infallible;
+
+/// A connection taken apart by `RelayConnection::into_parts`: its edges, count and PageInfo.
+pub type ConnectionParts<EdgeT, PageInfoT> = (Option<Vec<Option<EdgeT>>>, Option<i32>, PageInfoT);
 
 /// Common trait for Relay connections. Will be implemented by the codegen.
 pub trait RelayConnection {
@@ -12,6 +19,9 @@ pub trait RelayConnection {
     /// The type of Cursor that this connection uses.
     type CursorType: Cursor;
 
+    /// The generated PageInfo type for this connection.
+    type PageInfoType: RelayPageInfo<CursorType = Self::CursorType>;
+
     /// Builds a connection and associated edges from a Vec of the Nodes themselves. Pagination cursors
     /// can also be generated for you by providing the page info and CursorProvider trait instance.
     fn new<ProviderT>(
@@ -22,13 +32,364 @@ pub trait RelayConnection {
     ) -> Self
     where
         ProviderT: CursorProvider<Self::NodeType, CursorType = Self::CursorType>;
+
+    /// As `new`, but returns any error the cursor provider hits building the cursors (such as a
+    /// `KeyedCursorProvider` item without a key) rather than building a bad cursor. The error converts
+    /// into a `FieldError`, so resolvers can `?` it.
+    fn try_new<ProviderT>(
+        nodes: Option<&[Option<Self::NodeType>]>,
+        total_items: Option<i32>,
+        cursor_provider: ProviderT,
+        page_request: Option<crate::PageRequest<Self::CursorType>>,
+    ) -> Result<Self, crate::CursorError>
+    where
+        Self: Sized,
+        ProviderT: CursorProvider<Self::NodeType, CursorType = Self::CursorType>;
+
+    /// As `new`, but takes the nodes by value so none of them need cloning into the edges. Useful when
+    /// the nodes are large, or streamed straight out of a database.
+    fn from_iter<IterT, ProviderT>(
+        nodes: IterT,
+        total_items: Option<i32>,
+        cursor_provider: ProviderT,
+        page_request: Option<crate::PageRequest<Self::CursorType>>,
+    ) -> Self
+    where
+        IterT: IntoIterator<Item = Self::NodeType>,
+        ProviderT: CursorProvider<Self::NodeType, CursorType = Self::CursorType>;
+
+    /// As `new`, but takes the nodes as anything that converts into a `Cow`, so an owned `Vec` is
+    /// moved into the edges (as with `from_iter`) and a borrowed slice is cloned (as with `new`).
+    /// Useful when the same code path handles both.
+    fn from_cow<'a, NodesT, ProviderT>(
+        nodes: NodesT,
+        total_items: Option<i32>,
+        cursor_provider: ProviderT,
+        page_request: Option<crate::PageRequest<Self::CursorType>>,
+    ) -> Self
+    where
+        Self: Sized,
+        Self::NodeType: Clone + 'a,
+        NodesT: Into<Cow<'a, [Option<Self::NodeType>]>>,
+        ProviderT: CursorProvider<Self::NodeType, CursorType = Self::CursorType>;
+
+    /// Builds a connection from pre-built `PaginationMetadata` rather than the total and page request.
+    /// Useful when the resolver knows more about the result set than the count, such as
+    /// `more_available` from fetching an extra item.
+    fn from_metadata<ProviderT>(
+        nodes: Option<&[Option<Self::NodeType>]>,
+        metadata: crate::PaginationMetadata<Self::CursorType>,
+        cursor_provider: ProviderT,
+    ) -> Self
+    where
+        ProviderT: CursorProvider<Self::NodeType, CursorType = Self::CursorType>;
+
+    /// As `new`, but with the cursors and page info coming from an `AsyncCursorProvider`, awaiting
+    /// each cursor in turn.
+    fn new_async<ProviderT>(
+        nodes: Option<&[Option<Self::NodeType>]>,
+        total_items: Option<i32>,
+        cursor_provider: ProviderT,
+        page_request: Option<crate::PageRequest<Self::CursorType>>,
+    ) -> impl Future<Output = Self> + Send
+    where
+        Self: Sized,
+        Self::NodeType: Send + Sync,
+        Self::CursorType: Send + Sync,
+        ProviderT: AsyncCursorProvider<Self::NodeType, CursorType = Self::CursorType> + Send + Sync;
+
+    /// Assembles a connection from edges and page info that have already been built, for when the
+    /// resolver needs cursor logic no provider can express. Nothing is derived: the edges and page
+    /// info are used as they are, and the count is `total_items`.
+    fn from_edges(
+        edges: Vec<Self::EdgeType>,
+        total_items: Option<i32>,
+        page_info: Self::PageInfoType,
+    ) -> Self
+    where
+        Self: Sized;
+
+    /// A connection with no edges, a `count` of 0 and a `PageInfo` with no pages either side and no
+    /// cursors, for resolvers that know there's nothing to return.
+    ///
+    /// ```
+    /// use juniper::GraphQLObject;
+    /// use juniper_relay_helpers::{OffsetCursor, RelayConnection};
+    ///
+    /// #[derive(Clone, GraphQLObject, RelayConnection)]
+    /// #[relay(cursor = OffsetCursor)]
+    /// pub struct Weapon {
+    ///     pub name: String,
+    /// }
+    ///
+    /// let conn = WeaponRelayConnection::empty();
+    /// assert_eq!(conn.count, Some(0));
+    /// assert!(conn.is_empty());
+    /// assert!(!conn.page_info.has_next_page);
+    /// assert_eq!(conn.page_info.end_cursor, None);
+    /// ```
+    fn empty() -> Self
+    where
+        Self: Sized,
+        Self::PageInfoType: PageInfoFactory<Self::CursorType>,
+    {
+        Self::from_edges(
+            vec![],
+            Some(0),
+            Self::PageInfoType::new(false, false, None, None),
+        )
+    }
+
+    /// A connection holding just `node`, with a `count` of 1. The cursor is used for the edge and as
+    /// both the start and end cursor of the `PageInfo`, which has no pages either side.
+    fn single(node: Self::NodeType, cursor: Self::CursorType) -> Self
+    where
+        Self: Sized,
+        Self::EdgeType: RelayEdge<NodeType = Self::NodeType, CursorType = Self::CursorType>,
+        Self::PageInfoType: PageInfoFactory<Self::CursorType>,
+    {
+        let page_info =
+            Self::PageInfoType::new(false, false, Some(cursor.clone()), Some(cursor.clone()));
+        Self::from_edges(
+            vec![Self::EdgeType::new(Some(node), cursor)],
+            Some(1),
+            page_info,
+        )
+    }
+
+    /// Takes the connection apart into its edges, count and PageInfo. The count is `None` for
+    /// connections derived with `no_count`.
+    fn into_parts(self) -> ConnectionParts<Self::EdgeType, Self::PageInfoType>
+    where
+        Self: Sized;
+
+    /// Converts the connection into another with a different node type, mapping each node with `f`
+    /// and keeping the cursors, count and `PageInfo` as they are. Both connections need the same
+    /// cursor type.
+    ///
+    /// ```
+    /// use juniper::GraphQLObject;
+    /// use juniper_relay_helpers::{OffsetCursor, OffsetCursorProvider, RelayConnection};
+    ///
+    /// #[derive(Clone, GraphQLObject, RelayConnection)]
+    /// #[relay(cursor = OffsetCursor)]
+    /// pub struct CharacterRow {
+    ///     pub name: String,
+    /// }
+    ///
+    /// #[derive(Clone, GraphQLObject, RelayConnection)]
+    /// #[relay(cursor = OffsetCursor)]
+    /// pub struct Character {
+    ///     pub display_name: String,
+    /// }
+    ///
+    /// let rows = vec![Some(CharacterRow { name: "sciel".to_string() })];
+    /// let rows = CharacterRowRelayConnection::new(Some(&rows), Some(1), OffsetCursorProvider::new(), None);
+    ///
+    /// let characters: CharacterRelayConnection = rows.map_nodes(|row| Character {
+    ///     display_name: row.name.to_uppercase(),
+    /// });
+    /// assert_eq!(characters.count, Some(1));
+    /// ```
+    ///
+    /// Only the edges, count and `PageInfo` carry over: fields added by options such as
+    /// `extra_field` start from their defaults, as with `from_edges`. Missing edges are dropped.
+    fn map_nodes<TargetT, F>(self, mut f: F) -> TargetT
+    where
+        Self: Sized,
+        Self::EdgeType: RelayEdge<NodeType = Self::NodeType, CursorType = Self::CursorType>,
+        TargetT: RelayConnection<CursorType = Self::CursorType>,
+        TargetT::EdgeType: RelayEdge<NodeType = TargetT::NodeType, CursorType = Self::CursorType>,
+        TargetT::PageInfoType: PageInfoFactory<Self::CursorType>,
+        F: FnMut(Self::NodeType) -> TargetT::NodeType,
+    {
+        match self.try_map_nodes(|node| Ok::<_, Infallible>(f(node))) {
+            Ok(connection) => connection,
+            Err(never) => match never {},
+        }
+    }
+
+    /// As `map_nodes`, for conversions that can fail. Returns the first error `f` returns.
+    fn try_map_nodes<TargetT, F, ErrorT>(self, f: F) -> Result<TargetT, ErrorT>
+    where
+        Self: Sized,
+        Self::EdgeType: RelayEdge<NodeType = Self::NodeType, CursorType = Self::CursorType>,
+        TargetT: RelayConnection<CursorType = Self::CursorType>,
+        TargetT::EdgeType: RelayEdge<NodeType = TargetT::NodeType, CursorType = Self::CursorType>,
+        TargetT::PageInfoType: PageInfoFactory<Self::CursorType>,
+        F: FnMut(Self::NodeType) -> Result<TargetT::NodeType, ErrorT>,
+    {
+        let (edges, count, page_info) = self.into_parts();
+        let edges: Vec<Option<TargetT::EdgeType>> = try_map_edges(edges.unwrap_or_default(), f)?;
+        let page_info = TargetT::PageInfoType::new(
+            page_info.has_previous_page(),
+            page_info.has_next_page(),
+            page_info.start_cursor().cloned(),
+            page_info.end_cursor().cloned(),
+        );
+
+        Ok(TargetT::from_edges(
+            edges.into_iter().flatten().collect(),
+            count,
+            page_info,
+        ))
+    }
+
+    /// Combines two connections of the same type into one, such as the results of a read fanned out
+    /// to two shards. The edges of `other` follow those of `self`, the counts are summed, and the
+    /// `PageInfo` runs from the first edge's cursor to the last's, with a page either side if either
+    /// connection had one.
+    ///
+    /// ```
+    /// use juniper::GraphQLObject;
+    /// use juniper_relay_helpers::{OffsetCursor, OffsetCursorProvider, RelayConnection};
+    ///
+    /// #[derive(Clone, GraphQLObject, RelayConnection)]
+    /// #[relay(cursor = OffsetCursor)]
+    /// pub struct Shard {
+    ///     pub region: String,
+    /// }
+    ///
+    /// let shard = |region: &str| vec![Some(Shard { region: region.to_string() })];
+    /// let eu = ShardRelayConnection::new(Some(&shard("eu")), Some(1), OffsetCursorProvider::new(), None);
+    /// let us = ShardRelayConnection::new(Some(&shard("us")), Some(1), OffsetCursorProvider::new(), None);
+    ///
+    /// let merged = eu.merge(us);
+    /// assert_eq!(merged.count, Some(2));
+    /// assert_eq!(merged.len(), 2);
+    /// ```
+    ///
+    /// The edges are concatenated as they are, so ordering them, and making sure their cursors still
+    /// mean something to the resolver that receives them back, is up to the caller. The count is only
+    /// kept if both connections have one.
+    ///
+    /// For derived connections, `errors` (with `partial_errors`) and `nodes` (with `with_nodes`) are
+    /// concatenated the same way, and `page_size` and `extra_field` fields are kept from `self`. This
+    /// default, for connections implemented by hand, rebuilds the connection with `from_edges`
+    /// instead, so as with `map_nodes` any other fields start from their defaults and missing edges
+    /// are dropped.
+    fn merge(self, other: Self) -> Self
+    where
+        Self: Sized,
+        Self::EdgeType: RelayEdge<CursorType = Self::CursorType>,
+        Self::PageInfoType: PageInfoFactory<Self::CursorType>,
+    {
+        let (edges, count, page_info) = self.into_parts();
+        let (other_edges, other_count, other_page_info) = other.into_parts();
+
+        let edges: Vec<Self::EdgeType> = edges
+            .into_iter()
+            .chain(other_edges)
+            .flatten()
+            .flatten()
+            .collect();
+        let boundary_cursor =
+            |edge: Option<&Self::EdgeType>| edge.and_then(|edge| edge.cursor()).cloned();
+        let page_info = Self::PageInfoType::new(
+            page_info.has_previous_page() || other_page_info.has_previous_page(),
+            page_info.has_next_page() || other_page_info.has_next_page(),
+            boundary_cursor(edges.first()),
+            boundary_cursor(edges.last()),
+        );
+        let count = count
+            .zip(other_count)
+            .map(|(count, other_count)| count.saturating_add(other_count));
+
+        Self::from_edges(edges, count, page_info)
+    }
+
+    /// Reverses the connection, for backends that can only fetch forwards: fetch the tail of the set
+    /// in reverse order to answer a `last`/`before` request, then reverse it back for the client.
+    /// The edges are put in the opposite order, and the `PageInfo`'s start and end cursors swap
+    /// places, as do `has_next_page` and `has_previous_page`.
+    ///
+    /// ```
+    /// use juniper::GraphQLObject;
+    /// use juniper_relay_helpers::{OffsetCursor, RelayConnection};
+    ///
+    /// #[derive(Clone, GraphQLObject, RelayConnection)]
+    /// #[relay(cursor = OffsetCursor)]
+    /// pub struct Journal {
+    ///     pub entry: i32,
+    /// }
+    ///
+    /// let conn = JournalRelayConnection::single(Journal { entry: 61 }, OffsetCursor::new(61));
+    /// let reversed = conn.reverse();
+    /// assert_eq!(reversed.page_info.start_cursor, Some(OffsetCursor::new(61)));
+    /// ```
+    ///
+    /// Derived connections are reversed in place, so nothing else changes: the count, `errors`,
+    /// `page_size` and `extra_field` fields are kept, and `nodes` (with `with_nodes`) is reversed
+    /// along with the edges. This default, for connections implemented by hand, rebuilds the
+    /// connection with `from_edges` instead, so as with `map_nodes` any other fields start from
+    /// their defaults and missing edges are dropped.
+    fn reverse(self) -> Self
+    where
+        Self: Sized,
+        Self::PageInfoType: PageInfoFactory<Self::CursorType>,
+    {
+        let (edges, count, page_info) = self.into_parts();
+        let page_info = Self::PageInfoType::new(
+            page_info.has_next_page(),
+            page_info.has_previous_page(),
+            page_info.end_cursor().cloned(),
+            page_info.start_cursor().cloned(),
+        );
+
+        Self::from_edges(
+            edges.into_iter().flatten().flatten().rev().collect(),
+            count,
+            page_info,
+        )
+    }
+
+    /// The edges in the connection, if any.
+    fn edges(&self) -> Option<&[Option<Self::EdgeType>]>;
+
+    /// The PageInfo for the connection.
+    fn page_info(&self) -> &Self::PageInfoType;
+
+    /// The number of edges in this page of the connection. Not to be confused with the total `count`.
+    fn len(&self) -> usize {
+        self.edges().map_or(0, |edges| edges.len())
+    }
+
+    /// Whether this page of the connection has no edges.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Finds the cursor of the node whose key matches `id`, for "jump to this item" style UIs.
+    /// `key_fn` pulls the key out of a node. Returns `None` if no node in this page matches.
+    ///
+    /// Call `to_encoded_string` on the result to get the cursor as the client sees it.
+    fn cursor_for_id<'a, KeyT, KeyFn>(
+        &'a self,
+        id: KeyT,
+        key_fn: KeyFn,
+    ) -> Option<&'a Self::CursorType>
+    where
+        Self::EdgeType: RelayEdge<NodeType = Self::NodeType, CursorType = Self::CursorType>,
+        KeyT: PartialEq,
+        KeyFn: Fn(&'a Self::NodeType) -> KeyT,
+    {
+        self.edges()?
+            .iter()
+            .flatten()
+            .find(|edge| edge.node().is_some_and(|node| key_fn(node) == id))?
+            .cursor()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::OffsetCursor;
+    use crate::{
+        CursorProvider, OffsetCursor, OffsetCursorProvider, PageInfoFactory, PageRequest,
+        PaginationMetadata, RelayConnection, RelayEdge, map_edges,
+    };
     use juniper::GraphQLObject;
-    use juniper_relay_helpers_codegen::RelayConnection;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
 
     #[derive(Debug, GraphQLObject, RelayConnection, Clone, Eq, PartialEq)]
     #[relay(cursor = OffsetCursor)]
@@ -36,6 +397,12 @@ mod tests {
         name: String,
     }
 
+    #[derive(Debug, GraphQLObject, RelayConnection, Clone, Eq, PartialEq)]
+    #[relay(cursor = OffsetCursor, expose_page_size)]
+    pub struct Weapon {
+        name: String,
+    }
+
     #[test]
     fn connection_types_are_generated() {
         let conn = UserRelayConnection {
@@ -53,6 +420,58 @@ mod tests {
         assert_eq!(conn.edges.unwrap().len(), 0);
     }
 
+    #[test]
+    fn test_from_edges() {
+        let edges = vec![
+            UserRelayEdge {
+                node: Some(User {
+                    name: "Esquie".to_string(),
+                }),
+                cursor: Some(OffsetCursor::new(40)),
+            },
+            UserRelayEdge {
+                node: Some(User {
+                    name: "Monoco".to_string(),
+                }),
+                cursor: Some(OffsetCursor::new(7)),
+            },
+        ];
+        let page_info = UserRelayConnectionPageInfo::new(
+            true,
+            false,
+            Some(OffsetCursor::new(40)),
+            Some(OffsetCursor::new(7)),
+        );
+
+        let conn = UserRelayConnection::from_edges(edges.clone(), Some(99), page_info);
+        assert_eq!(conn.count, Some(99));
+        assert!(conn.page_info.has_previous_page);
+        assert!(!conn.page_info.has_next_page);
+        assert_eq!(conn.page_info.end_cursor, Some(OffsetCursor::new(7)));
+
+        let built: Vec<(Option<User>, Option<OffsetCursor>)> = conn
+            .edges
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .map(|edge| (edge.node, edge.cursor))
+            .collect();
+        let expected: Vec<(Option<User>, Option<OffsetCursor>)> = edges
+            .into_iter()
+            .map(|edge| (edge.node, edge.cursor))
+            .collect();
+        assert_eq!(built, expected);
+
+        let conn = WeaponRelayConnection::from_edges(
+            vec![],
+            None,
+            WeaponRelayConnectionPageInfo::new(false, false, None, None),
+        );
+        assert_eq!(conn.count, None);
+        assert_eq!(conn.page_size, None);
+        assert_eq!(conn.edges.map(|e| e.len()), Some(0));
+    }
+
     #[test]
     fn edge_types_are_generated() {
         let edge = UserRelayEdge {
@@ -76,4 +495,929 @@ mod tests {
         assert_eq!(edge.node.unwrap().name, "Lune");
         assert_eq!(edge.cursor, Some(OffsetCursor::new(27)));
     }
+
+    #[test]
+    fn connection_from_metadata() {
+        let nodes = vec![
+            Some(User {
+                name: "Lune".to_owned(),
+            }),
+            Some(User {
+                name: "Sciel".to_owned(),
+            }),
+        ];
+        let conn = UserRelayConnection::from_metadata(
+            Some(&nodes),
+            PaginationMetadata {
+                total_count: None,
+                page_request: Some(PageRequest::new(Some(2), None, None)),
+                more_available: Some(true),
+            },
+            OffsetCursorProvider::new(),
+        );
+
+        assert_eq!(conn.count, None);
+        assert_eq!(conn.edges.unwrap().len(), 2);
+        assert!(conn.page_info.has_next_page);
+        assert_eq!(conn.page_info.end_cursor, Some(OffsetCursor::new(1)));
+    }
+
+    #[test]
+    fn connection_exposes_clamped_page_size() {
+        let requested_first = Some(500);
+        let first = requested_first.map(|f: i32| f.min(50));
+
+        let nodes = vec![Some(Weapon {
+            name: "Lunerim".to_owned(),
+        })];
+        let conn = WeaponRelayConnection::new(
+            Some(&nodes),
+            Some(1),
+            OffsetCursorProvider::new(),
+            Some(PageRequest::new(first, None, None)),
+        );
+        assert_eq!(conn.page_size, Some(50));
+
+        let conn =
+            WeaponRelayConnection::new(Some(&nodes), Some(1), OffsetCursorProvider::new(), None);
+        assert_eq!(conn.page_size, None);
+    }
+
+    #[test]
+    fn connection_cursor_for_id() {
+        let nodes: Vec<Option<User>> = ["Lune", "Sciel", "Monoco"]
+            .into_iter()
+            .map(|name| {
+                Some(User {
+                    name: name.to_owned(),
+                })
+            })
+            .collect();
+        let conn = UserRelayConnection::new(
+            Some(&nodes),
+            Some(10),
+            OffsetCursorProvider::new(),
+            Some(PageRequest::new(Some(3), Some(OffsetCursor::new(4)), None)),
+        );
+
+        let cursor = conn.cursor_for_id("Sciel", |user| user.name.as_str());
+        assert_eq!(cursor, Some(&OffsetCursor::new(6)));
+        assert_eq!(conn.cursor_for_id("Verso", |user| user.name.as_str()), None);
+    }
+
+    /// Offset provider that ran its own count query.
+    struct CountingProvider;
+
+    impl CursorProvider<User> for CountingProvider {
+        type CursorType = OffsetCursor;
+
+        fn get_cursor_for_item(
+            &self,
+            metadata: &PaginationMetadata<OffsetCursor>,
+            item_idx: i32,
+            item: Option<&User>,
+        ) -> OffsetCursor {
+            OffsetCursorProvider::new().get_cursor_for_item(metadata, item_idx, item)
+        }
+
+        fn get_page_info<PageInfoType>(
+            &self,
+            metadata: &PaginationMetadata<OffsetCursor>,
+            items: Option<&[Option<User>]>,
+        ) -> PageInfoType
+        where
+            PageInfoType: PageInfoFactory<OffsetCursor>,
+        {
+            OffsetCursorProvider::new().get_page_info(metadata, items)
+        }
+
+        fn total_count(&self, _metadata: &PaginationMetadata<OffsetCursor>) -> Option<i32> {
+            Some(40)
+        }
+    }
+
+    #[test]
+    fn connection_provider_total_overrides_given_total() {
+        let nodes = vec![Some(User {
+            name: "Esquie".to_owned(),
+        })];
+        let page_request = Some(PageRequest::new(Some(1), None, None));
+
+        let conn = UserRelayConnection::new(
+            Some(&nodes),
+            Some(1),
+            CountingProvider,
+            page_request.clone(),
+        );
+        assert_eq!(conn.count, Some(40));
+        // The provider's total is also what the page info is worked out from.
+        assert!(conn.page_info.has_next_page);
+
+        let conn = UserRelayConnection::from_iter(
+            nodes.clone().into_iter().flatten(),
+            None,
+            CountingProvider,
+            page_request.clone(),
+        );
+        assert_eq!(conn.count, Some(40));
+
+        let conn = UserRelayConnection::try_new(
+            Some(&nodes),
+            Some(1),
+            CountingProvider,
+            page_request.clone(),
+        )
+        .unwrap();
+        assert_eq!(conn.count, Some(40));
+
+        // Providers without a total of their own leave the given one alone.
+        let conn = UserRelayConnection::new(
+            Some(&nodes),
+            Some(1),
+            OffsetCursorProvider::new(),
+            page_request,
+        );
+        assert_eq!(conn.count, Some(1));
+        assert!(!conn.page_info.has_next_page);
+    }
+
+    /// Builds its cursors in one batch, counting the batches.
+    #[derive(Default)]
+    struct BatchingProvider {
+        batches: AtomicUsize,
+    }
+
+    impl CursorProvider<User> for &BatchingProvider {
+        type CursorType = OffsetCursor;
+
+        fn get_cursor_for_item(
+            &self,
+            _metadata: &PaginationMetadata<OffsetCursor>,
+            _item_idx: i32,
+            _item: Option<&User>,
+        ) -> OffsetCursor {
+            unreachable!("cursors should be built in a batch")
+        }
+
+        fn get_cursors_for_items(
+            &self,
+            _metadata: &PaginationMetadata<OffsetCursor>,
+            items: &[Option<User>],
+        ) -> Vec<OffsetCursor> {
+            self.batches.fetch_add(1, AtomicOrdering::SeqCst);
+            (0..items.len() as i32)
+                .map(|idx| OffsetCursor::new(idx + 100))
+                .collect()
+        }
+
+        fn get_page_info<PageInfoType>(
+            &self,
+            metadata: &PaginationMetadata<OffsetCursor>,
+            items: Option<&[Option<User>]>,
+        ) -> PageInfoType
+        where
+            PageInfoType: PageInfoFactory<OffsetCursor>,
+        {
+            OffsetCursorProvider::new().get_page_info(metadata, items)
+        }
+    }
+
+    #[test]
+    fn connection_cursors_built_in_one_batch() {
+        let nodes: Vec<Option<User>> = ["Esquie", "Monoco", "Sciel"]
+            .into_iter()
+            .map(|name| {
+                Some(User {
+                    name: name.to_owned(),
+                })
+            })
+            .collect();
+        let provider = BatchingProvider::default();
+
+        let conn = UserRelayConnection::new(Some(&nodes), None, &provider, None);
+        assert_eq!(provider.batches.load(AtomicOrdering::SeqCst), 1);
+        let cursors: Vec<Option<OffsetCursor>> = conn
+            .edges
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .map(|edge| edge.cursor)
+            .collect();
+        assert_eq!(
+            cursors,
+            vec![
+                Some(OffsetCursor::new(100)),
+                Some(OffsetCursor::new(101)),
+                Some(OffsetCursor::new(102))
+            ]
+        );
+
+        UserRelayConnection::from_iter(nodes.into_iter().flatten(), None, &provider, None);
+        assert_eq!(provider.batches.load(AtomicOrdering::SeqCst), 2);
+    }
+
+    /// Counts clones, so tests can check nodes are moved rather than cloned.
+    #[derive(Debug, Default)]
+    struct CloneCounter(Arc<AtomicUsize>);
+
+    impl Clone for CloneCounter {
+        fn clone(&self) -> Self {
+            self.0.fetch_add(1, AtomicOrdering::SeqCst);
+            CloneCounter(self.0.clone())
+        }
+    }
+
+    #[derive(Debug, GraphQLObject, RelayConnection, Clone)]
+    #[relay(cursor = OffsetCursor)]
+    pub struct Canvas {
+        name: String,
+        #[graphql(skip)]
+        clones: CloneCounter,
+    }
+
+    #[test]
+    fn connection_from_iter_moves_nodes() {
+        let clones = CloneCounter::default();
+        let nodes = ["Lumière", "Spring Meadows", "Gestral Village"].map(|name| Canvas {
+            name: name.to_owned(),
+            clones: CloneCounter(Arc::clone(&clones.0)),
+        });
+
+        let conn = CanvasRelayConnection::from_iter(
+            nodes,
+            Some(10),
+            OffsetCursorProvider::new(),
+            Some(PageRequest::new(Some(3), None, None)),
+        );
+
+        assert_eq!(conn.count, Some(10));
+        assert!(conn.page_info.has_next_page);
+        assert_eq!(conn.page_info.end_cursor, Some(OffsetCursor::new(2)));
+
+        let edges = conn.edges.unwrap();
+        assert_eq!(edges.len(), 3);
+        let last = edges[2].as_ref().unwrap();
+        assert_eq!(last.node.as_ref().unwrap().name, "Gestral Village");
+        assert_eq!(last.cursor, Some(OffsetCursor::new(2)));
+
+        // The counter is shared between all the nodes, so this covers every one of them.
+        let clones = &last.node.as_ref().unwrap().clones;
+        assert_eq!(clones.0.load(AtomicOrdering::SeqCst), 0);
+    }
+
+    // Nodes can't have lifetime parameters, but `'static` borrows need none.
+    #[derive(Debug, GraphQLObject, RelayConnection, Clone, Eq, PartialEq)]
+    #[relay(cursor = OffsetCursor)]
+    pub struct Fresco {
+        name: &'static str,
+    }
+
+    #[test]
+    fn static_borrowed_nodes() {
+        let conn = FrescoRelayConnection::from_iter(
+            [
+                Fresco { name: "Lumière" },
+                Fresco {
+                    name: "Old Lumière",
+                },
+            ],
+            Some(2),
+            OffsetCursorProvider::new(),
+            None,
+        );
+
+        assert_eq!(conn.len(), 2);
+        assert_eq!(
+            conn.edges.unwrap()[1].as_ref().unwrap().node,
+            Some(Fresco {
+                name: "Old Lumière"
+            })
+        );
+    }
+
+    #[derive(Debug, GraphQLObject, RelayConnection, Clone, Eq, PartialEq)]
+    #[relay(cursor = OffsetCursor, rc_node, with_nodes)]
+    pub struct Painting {
+        name: String,
+    }
+
+    #[test]
+    fn rc_node_connections_share_nodes() {
+        let paintings: Vec<Option<Arc<Painting>>> = ["Lumière", "The Monolith"]
+            .into_iter()
+            .map(|name| {
+                Some(Arc::new(Painting {
+                    name: name.to_owned(),
+                }))
+            })
+            .collect();
+
+        let recent = PaintingRelayConnection::new(
+            Some(&paintings),
+            Some(2),
+            OffsetCursorProvider::new(),
+            None,
+        );
+        let featured = PaintingRelayConnection::from_iter(
+            paintings.iter().rev().flatten().cloned(),
+            Some(2),
+            OffsetCursorProvider::new(),
+            None,
+        );
+
+        let node = |conn: &PaintingRelayConnection, idx: usize| {
+            Arc::clone(
+                conn.edges.as_ref().unwrap()[idx]
+                    .as_ref()
+                    .unwrap()
+                    .node
+                    .as_ref()
+                    .unwrap(),
+            )
+        };
+        assert!(Arc::ptr_eq(&node(&recent, 0), &node(&featured, 1)));
+        assert!(Arc::ptr_eq(&node(&recent, 1), &node(&featured, 0)));
+        assert!(Arc::ptr_eq(
+            &recent.nodes[0],
+            paintings[0].as_ref().unwrap()
+        ));
+    }
+
+    #[test]
+    fn empty_and_single_connections() {
+        let empty = PaintingRelayConnection::empty();
+        assert_eq!(empty.count, Some(0));
+        assert_eq!(empty.edges.as_ref().map(Vec::len), Some(0));
+        assert!(empty.nodes.is_empty());
+        assert!(!empty.page_info.has_previous_page);
+        assert!(!empty.page_info.has_next_page);
+        assert_eq!(empty.page_info.start_cursor, None);
+        assert_eq!(empty.page_info.end_cursor, None);
+
+        let single = UserRelayConnection::single(
+            User {
+                name: "Esquie".to_string(),
+            },
+            OffsetCursor::new(4),
+        );
+        assert_eq!(single.count, Some(1));
+        assert_eq!(single.len(), 1);
+        let edge = single.edges.as_ref().unwrap()[0].as_ref().unwrap();
+        assert_eq!(edge.node.as_ref().unwrap().name, "Esquie");
+        assert_eq!(edge.cursor, Some(OffsetCursor::new(4)));
+        assert!(!single.page_info.has_previous_page);
+        assert!(!single.page_info.has_next_page);
+        assert_eq!(single.page_info.start_cursor, Some(OffsetCursor::new(4)));
+        assert_eq!(single.page_info.end_cursor, Some(OffsetCursor::new(4)));
+    }
+
+    #[test]
+    fn merge_connections() {
+        let users = |names: [&str; 2]| {
+            names
+                .map(|name| {
+                    Some(User {
+                        name: name.to_string(),
+                    })
+                })
+                .to_vec()
+        };
+        let first_shard = UserRelayConnection::new(
+            Some(&users(["Gustave", "Lune"])),
+            Some(4),
+            OffsetCursorProvider::new(),
+            Some(PageRequest::new(Some(2), None, None)),
+        );
+        let second_shard = UserRelayConnection::new(
+            Some(&users(["Maelle", "Sciel"])),
+            Some(6),
+            OffsetCursorProvider::new(),
+            Some(PageRequest::new(Some(2), Some(OffsetCursor::new(1)), None)),
+        );
+
+        let merged = first_shard.merge(second_shard);
+        assert_eq!(merged.count, Some(10));
+        let names: Vec<&str> = merged
+            .edges
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|edge| edge.as_ref().unwrap().node.as_ref().unwrap().name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Gustave", "Lune", "Maelle", "Sciel"]);
+        assert_eq!(merged.page_info.start_cursor, Some(OffsetCursor::new(0)));
+        assert_eq!(merged.page_info.end_cursor, Some(OffsetCursor::new(3)));
+        assert!(merged.page_info.has_previous_page);
+        assert!(merged.page_info.has_next_page);
+    }
+
+    #[test]
+    fn merge_with_empty_connection() {
+        let single = UserRelayConnection::single(
+            User {
+                name: "Esquie".to_string(),
+            },
+            OffsetCursor::new(4),
+        );
+        let no_count =
+            UserRelayConnection::from_edges(vec![], None, UserRelayConnectionPageInfo::empty());
+
+        let merged = UserRelayConnection::empty().merge(single.clone());
+        assert_eq!(merged.count, Some(1));
+        assert_eq!(merged.page_info.start_cursor, Some(OffsetCursor::new(4)));
+        assert_eq!(merged.page_info.end_cursor, Some(OffsetCursor::new(4)));
+        assert!(!merged.page_info.has_next_page);
+
+        // Without both counts, the total isn't known.
+        assert_eq!(single.merge(no_count).count, None);
+    }
+
+    #[test]
+    fn reverse_connection() {
+        let users: Vec<Option<User>> = ["Verso", "Monoco", "Esquie"]
+            .map(|name| {
+                Some(User {
+                    name: name.to_string(),
+                })
+            })
+            .to_vec();
+        let conn = UserRelayConnection::new(
+            Some(&users),
+            Some(10),
+            OffsetCursorProvider::new(),
+            Some(PageRequest::new(Some(3), None, None)),
+        );
+        assert!(conn.page_info.has_next_page);
+        assert!(!conn.page_info.has_previous_page);
+
+        let reversed = conn.reverse();
+        assert_eq!(reversed.count, Some(10));
+        let edges: Vec<(&str, Option<OffsetCursor>)> = reversed
+            .edges
+            .as_ref()
+            .unwrap()
+            .iter()
+            .flatten()
+            .map(|edge| {
+                (
+                    edge.node.as_ref().unwrap().name.as_str(),
+                    edge.cursor.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            edges,
+            vec![
+                ("Esquie", Some(OffsetCursor::new(2))),
+                ("Monoco", Some(OffsetCursor::new(1))),
+                ("Verso", Some(OffsetCursor::new(0))),
+            ]
+        );
+        assert_eq!(reversed.page_info.start_cursor, Some(OffsetCursor::new(2)));
+        assert_eq!(reversed.page_info.end_cursor, Some(OffsetCursor::new(0)));
+        assert!(!reversed.page_info.has_next_page);
+        assert!(reversed.page_info.has_previous_page);
+    }
+
+    #[test]
+    fn reverse_empty_and_single_connections() {
+        let empty = UserRelayConnection::empty().reverse();
+        assert_eq!(empty.count, Some(0));
+        assert!(empty.is_empty());
+        assert_eq!(empty.page_info.start_cursor, None);
+        assert_eq!(empty.page_info.end_cursor, None);
+
+        let single = UserRelayConnection::single(
+            User {
+                name: "Esquie".to_string(),
+            },
+            OffsetCursor::new(4),
+        )
+        .reverse();
+        assert_eq!(single.count, Some(1));
+        assert_eq!(single.len(), 1);
+        assert_eq!(single.page_info.start_cursor, Some(OffsetCursor::new(4)));
+        assert_eq!(single.page_info.end_cursor, Some(OffsetCursor::new(4)));
+        assert!(!single.page_info.has_next_page);
+        assert!(!single.page_info.has_previous_page);
+    }
+
+    #[derive(Debug, GraphQLObject, RelayConnection, Clone, Eq, PartialEq)]
+    #[relay(
+        cursor = OffsetCursor,
+        with_nodes,
+        partial_errors,
+        expose_page_size,
+        extra_field(name = "unread", ty = "i32")
+    )]
+    pub struct Letter {
+        from: String,
+    }
+
+    #[test]
+    fn merge_keeps_other_fields() {
+        let letter = |from: &str| {
+            vec![Some(Letter {
+                from: from.to_string(),
+            })]
+        };
+        let mut first = LetterRelayConnection::new_partial(
+            Some(&letter("Verso")),
+            vec!["Renoir's letter was lost".to_string()],
+            Some(2),
+            OffsetCursorProvider::new(),
+            Some(PageRequest::new(Some(1), None, None)),
+        );
+        first.unread = 1;
+        let second = LetterRelayConnection::new_partial(
+            Some(&letter("Aline")),
+            vec!["Clea's letter was lost".to_string()],
+            Some(2),
+            OffsetCursorProvider::new(),
+            Some(PageRequest::new(Some(1), Some(OffsetCursor::new(0)), None)),
+        );
+
+        let merged = first.merge(second);
+        let froms: Vec<&str> = merged.nodes.iter().map(|l| l.from.as_str()).collect();
+        assert_eq!(froms, vec!["Verso", "Aline"]);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(
+            merged.errors,
+            vec![
+                "Renoir's letter was lost".to_string(),
+                "Clea's letter was lost".to_string()
+            ]
+        );
+        assert_eq!(merged.page_size, Some(1));
+        assert_eq!(merged.unread, 1);
+        assert_eq!(merged.count, Some(4));
+        assert_eq!(merged.page_info.start_cursor, Some(OffsetCursor::new(0)));
+        assert_eq!(merged.page_info.end_cursor, Some(OffsetCursor::new(1)));
+    }
+
+    #[test]
+    fn reverse_keeps_other_fields() {
+        let letters: Vec<Option<Letter>> = ["Verso", "Aline"]
+            .map(|from| {
+                Some(Letter {
+                    from: from.to_string(),
+                })
+            })
+            .to_vec();
+        let mut conn = LetterRelayConnection::new_partial(
+            Some(&letters),
+            vec!["Renoir's letter was lost".to_string()],
+            Some(3),
+            OffsetCursorProvider::new(),
+            Some(PageRequest::new(Some(2), None, None)),
+        );
+        conn.unread = 2;
+
+        let reversed = conn.reverse();
+        let froms: Vec<&str> = reversed.nodes.iter().map(|l| l.from.as_str()).collect();
+        assert_eq!(froms, vec!["Aline", "Verso"]);
+        assert_eq!(
+            reversed.edges.as_ref().unwrap()[0]
+                .as_ref()
+                .unwrap()
+                .node
+                .as_ref()
+                .unwrap()
+                .from,
+            "Aline"
+        );
+        assert_eq!(
+            reversed.errors,
+            vec!["Renoir's letter was lost".to_string()]
+        );
+        assert_eq!(reversed.page_size, Some(2));
+        assert_eq!(reversed.unread, 2);
+        assert_eq!(reversed.count, Some(3));
+        assert_eq!(reversed.page_info.start_cursor, Some(OffsetCursor::new(1)));
+        assert!(reversed.page_info.has_previous_page);
+        assert!(!reversed.page_info.has_next_page);
+    }
+
+    /// Two derives in one module, next to a type with the name the derive used to import
+    /// `RelayEdge` under. Nothing generated should clash with either.
+    mod shared_scope {
+        use crate::{OffsetCursor, RelayConnection};
+        use juniper::GraphQLObject;
+
+        #[allow(dead_code)]
+        pub struct ScrollRelayEdgeTrait;
+
+        #[derive(Debug, GraphQLObject, RelayConnection, Clone)]
+        #[relay(cursor = OffsetCursor)]
+        pub struct Scroll {
+            pub title: String,
+        }
+
+        #[derive(Debug, GraphQLObject, RelayConnection, Clone)]
+        #[relay(cursor = OffsetCursor)]
+        pub struct Quill {
+            pub owner: String,
+        }
+    }
+
+    mod visibility {
+        use crate::{OffsetCursor, RelayConnection};
+        use juniper::GraphQLObject;
+
+        /// The connection follows the node and is visible to the rest of the crate.
+        #[derive(Debug, GraphQLObject, RelayConnection, Clone)]
+        #[relay(cursor = OffsetCursor)]
+        pub(crate) struct Sketch {
+            pub title: String,
+        }
+
+        /// A public node whose connection is kept within the crate.
+        #[derive(Debug, GraphQLObject, RelayConnection, Clone)]
+        #[relay(cursor = OffsetCursor, visibility = "pub(crate)")]
+        pub struct Study {
+            pub title: String,
+        }
+    }
+
+    #[test]
+    fn generated_types_follow_visibility() {
+        let sketches = visibility::SketchRelayConnection::empty();
+        let studies = visibility::StudyRelayConnection::single(
+            visibility::Study {
+                title: "Monolith".to_string(),
+            },
+            OffsetCursor::new(0),
+        );
+        let _: &visibility::StudyRelayConnectionPageInfo = &studies.page_info;
+
+        assert!(sketches.is_empty());
+        assert_eq!(studies.len(), 1);
+    }
+
+    #[test]
+    fn derives_share_a_module() {
+        use shared_scope::{Quill, QuillRelayConnection, Scroll, ScrollRelayConnection};
+
+        let scrolls = ScrollRelayConnection::single(
+            Scroll {
+                title: "Gommage".to_string(),
+            },
+            OffsetCursor::new(0),
+        );
+        let quills = QuillRelayConnection::single(
+            Quill {
+                owner: "Aline".to_string(),
+            },
+            OffsetCursor::new(0),
+        );
+        assert_eq!(scrolls.len(), 1);
+        assert_eq!(quills.len(), 1);
+    }
+
+    #[derive(Debug, GraphQLObject, RelayConnection, Clone)]
+    #[relay(cursor = OffsetCursor, non_null_cursor)]
+    pub struct Palette {
+        name: String,
+    }
+
+    #[test]
+    fn non_null_cursor_edges() {
+        let palettes = vec![
+            Some(Palette {
+                name: "Flying Waters".to_string(),
+            }),
+            Some(Palette {
+                name: "Old Lumière".to_string(),
+            }),
+        ];
+        let conn = PaletteRelayConnection::new(
+            Some(&palettes),
+            Some(2),
+            OffsetCursorProvider::new(),
+            None,
+        );
+
+        let cursors: Vec<OffsetCursor> = conn
+            .edges
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .map(|edge| edge.cursor)
+            .collect();
+        assert_eq!(cursors, vec![OffsetCursor::new(0), OffsetCursor::new(1)]);
+
+        let edge = PaletteRelayEdge::try_from_parts(None, Some(OffsetCursor::new(3))).unwrap();
+        assert_eq!(edge.cursor(), Some(&OffsetCursor::new(3)));
+        assert!(PaletteRelayEdge::try_from_parts(None, None).is_none());
+
+        // Edges without a cursor are left out when mapping into non-null cursor edges.
+        let edges = vec![
+            UserRelayEdge::try_from_parts(
+                Some(User {
+                    name: "Lumière".to_string(),
+                }),
+                Some(OffsetCursor::new(0)),
+            ),
+            UserRelayEdge::try_from_parts(
+                Some(User {
+                    name: "Sirène".to_string(),
+                }),
+                None,
+            ),
+        ];
+        let mapped: Vec<Option<PaletteRelayEdge>> =
+            map_edges(edges, |user: User| Palette { name: user.name });
+        assert_eq!(mapped[0].as_ref().unwrap().cursor, OffsetCursor::new(0));
+        assert!(mapped[1].is_none());
+    }
+
+    #[test]
+    fn map_nodes_keeps_cursors_and_page_info() {
+        let users: Vec<Option<User>> = ["Gustave", "Lune", "Maelle"]
+            .into_iter()
+            .map(|name| {
+                Some(User {
+                    name: name.to_string(),
+                })
+            })
+            .collect();
+        let users = UserRelayConnection::new(
+            Some(&users),
+            Some(10),
+            OffsetCursorProvider::new(),
+            Some(PageRequest::new(Some(3), Some(OffsetCursor::new(2)), None)),
+        );
+
+        let weapons: WeaponRelayConnection = users.clone().map_nodes(|user| Weapon {
+            name: format!("{}'s weapon", user.name),
+        });
+        assert_eq!(weapons.count, Some(10));
+        assert!(weapons.page_info.has_previous_page);
+        assert!(weapons.page_info.has_next_page);
+        assert_eq!(weapons.page_info.start_cursor, users.page_info.start_cursor);
+        assert_eq!(weapons.page_info.end_cursor, users.page_info.end_cursor);
+
+        let edges: Vec<(String, Option<OffsetCursor>)> = weapons
+            .edges
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .map(|edge| (edge.node.unwrap().name, edge.cursor))
+            .collect();
+        assert_eq!(
+            edges,
+            vec![
+                ("Gustave's weapon".to_string(), Some(OffsetCursor::new(3))),
+                ("Lune's weapon".to_string(), Some(OffsetCursor::new(4))),
+                ("Maelle's weapon".to_string(), Some(OffsetCursor::new(5))),
+            ]
+        );
+
+        let failed: Result<WeaponRelayConnection, String> = users.try_map_nodes(|user| {
+            if user.name == "Lune" {
+                Err(format!("no weapon for {}", user.name))
+            } else {
+                Ok(Weapon { name: user.name })
+            }
+        });
+        assert_eq!(failed.err(), Some("no weapon for Lune".to_string()));
+    }
+
+    #[test]
+    fn map_edges_keeps_missing_edges() {
+        let edges = vec![
+            UserRelayEdge::try_from_parts(
+                Some(User {
+                    name: "Sciel".to_string(),
+                }),
+                Some(OffsetCursor::new(0)),
+            ),
+            None,
+            UserRelayEdge::try_from_parts(None, None),
+        ];
+
+        let mapped: Vec<Option<WeaponRelayEdge>> = map_edges(edges, |user: User| Weapon {
+            name: user.name.to_uppercase(),
+        });
+        let parts: Vec<_> = mapped
+            .into_iter()
+            .map(|edge| edge.map(RelayEdge::into_parts))
+            .collect();
+        assert_eq!(
+            parts,
+            vec![
+                Some((
+                    Some(Weapon {
+                        name: "SCIEL".to_string()
+                    }),
+                    Some(OffsetCursor::new(0))
+                )),
+                None,
+                Some((None, None)),
+            ]
+        );
+    }
+
+    #[test]
+    fn connection_from_cow_moves_owned_nodes() {
+        let clones = CloneCounter::default();
+        let nodes: Vec<Option<Canvas>> = ["Lumière", "Spring Meadows"]
+            .into_iter()
+            .map(|name| {
+                Some(Canvas {
+                    name: name.to_owned(),
+                    clones: CloneCounter(Arc::clone(&clones.0)),
+                })
+            })
+            .chain([None])
+            .collect();
+        let request = || Some(PageRequest::new(Some(3), None, None));
+
+        let conn = CanvasRelayConnection::from_cow(
+            &nodes[..],
+            Some(10),
+            OffsetCursorProvider::new(),
+            request(),
+        );
+        assert_eq!(clones.0.load(AtomicOrdering::SeqCst), 2);
+        assert_eq!(conn.page_info.end_cursor, Some(OffsetCursor::new(2)));
+
+        let conn = CanvasRelayConnection::from_cow(
+            nodes,
+            Some(10),
+            OffsetCursorProvider::new(),
+            request(),
+        );
+        assert_eq!(clones.0.load(AtomicOrdering::SeqCst), 2);
+        assert_eq!(conn.count, Some(10));
+        assert_eq!(conn.page_info.end_cursor, Some(OffsetCursor::new(2)));
+
+        let edges = conn.edges.unwrap();
+        assert_eq!(edges.len(), 3);
+        assert_eq!(
+            edges[1].as_ref().unwrap().node.as_ref().unwrap().name,
+            "Spring Meadows"
+        );
+        assert!(edges[2].as_ref().unwrap().node.is_none());
+    }
+
+    fn edge_count<ConnectionT: RelayConnection>(conn: &ConnectionT) -> (usize, bool) {
+        (conn.len(), conn.is_empty())
+    }
+
+    #[test]
+    fn connection_len_and_is_empty() {
+        let nodes = vec![
+            Some(User {
+                name: "Lune".to_owned(),
+            }),
+            None,
+        ];
+        let conn = UserRelayConnection::new(Some(&nodes), None, OffsetCursorProvider::new(), None);
+        assert_eq!(edge_count(&conn), (2, false));
+
+        let empty = UserRelayConnection::new(Some(&[]), None, OffsetCursorProvider::new(), None);
+        assert_eq!(edge_count(&empty), (0, true));
+
+        let no_edges = UserRelayConnection::new(None, None, OffsetCursorProvider::new(), None);
+        assert_eq!(edge_count(&no_edges), (0, true));
+    }
+
+    #[cfg(feature = "serde")]
+    mod serde_page_info {
+        use crate::{OffsetCursor, OffsetCursorProvider, PageRequest, RelayConnection};
+        use juniper::GraphQLObject;
+
+        #[derive(Debug, GraphQLObject, RelayConnection, Clone, Eq, PartialEq)]
+        #[relay(cursor = OffsetCursor, serde)]
+        pub struct Gradient {
+            name: String,
+        }
+
+        #[test]
+        fn page_info_round_trips_through_serde() {
+            let nodes = vec![Some(Gradient {
+                name: "Gradient Charge".to_owned(),
+            })];
+            let conn = GradientRelayConnection::new(
+                Some(&nodes),
+                Some(3),
+                OffsetCursorProvider::new(),
+                Some(PageRequest::new(Some(1), None, None)),
+            );
+
+            let json = serde_json::to_string(&conn.page_info).unwrap();
+            let decoded: GradientRelayConnectionPageInfo = serde_json::from_str(&json).unwrap();
+            assert!(decoded.has_next_page);
+            assert_eq!(decoded.start_cursor, Some(OffsetCursor::new(0)));
+            assert_eq!(decoded.end_cursor, conn.page_info.end_cursor);
+
+            let request = PageRequest::new(Some(1), decoded.end_cursor, None);
+            let json = serde_json::to_string(&request).unwrap();
+            assert_eq!(
+                serde_json::from_str::<PageRequest<OffsetCursor>>(&json).unwrap(),
+                request
+            );
+        }
+    }
 }
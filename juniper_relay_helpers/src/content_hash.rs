@@ -0,0 +1,234 @@
+use crate::cursor_provider::{built_cursor, full_page_info};
+use crate::{CursorError, CursorProvider, PageInfoFactory, PaginationMetadata, StringCursor};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// Cursor provider for immutable, content-addressed data. Each item's cursor is the SHA-256 of its
+/// JSON serialization, so the cursor for an item stays the same however the set is ordered, and
+/// identical items always get identical cursors.
+///
+/// A hash says nothing about where an item sits in the set, so:
+///
+/// - Resolvers find the `after` item by comparing `ContentHashCursorProvider::cursor_for` against
+///   the cursor, much like the `KeyedCursorProvider`.
+/// - If an `after` is provided, it's assumed there is a previous page.
+/// - `has_next_page` comes from `PaginationMetadata::more_available` if it's set. Otherwise a full
+///   page (as many items as `first`) is taken to mean more may follow. Overfetching by one with an
+///   `OverfetchPaginator` gives an exact answer.
+///
+/// Items that can't be serialized to JSON, such as maps with non-string keys, get an empty cursor.
+/// The fallible `try_` methods (used by `RelayConnection::try_new`) return
+/// `CursorError::UnhashableItem` for them instead.
+///
+/// Requires the `content-hash` feature.
+pub struct ContentHashCursorProvider;
+
+impl ContentHashCursorProvider {
+    /// Builds the cursor for an item, for finding the `after` item when resuming. Items that can't
+    /// be serialized get an empty cursor.
+    pub fn cursor_for<ItemT: Serialize>(item: &ItemT) -> StringCursor {
+        Self::try_cursor_for(item).unwrap_or_default()
+    }
+
+    /// As `cursor_for`, but returns `CursorError::UnhashableItem` for items that can't be serialized.
+    pub fn try_cursor_for<ItemT: Serialize>(item: &ItemT) -> Result<StringCursor, CursorError> {
+        let json =
+            serde_json::to_vec(item).map_err(|err| CursorError::UnhashableItem(err.to_string()))?;
+        let digest = Sha256::digest(json);
+        Ok(StringCursor::new(
+            digest.iter().map(|b| format!("{:02x}", b)).collect(),
+        ))
+    }
+}
+
+impl<ItemT> CursorProvider<ItemT> for ContentHashCursorProvider
+where
+    ItemT: Serialize,
+{
+    type CursorType = StringCursor;
+
+    fn get_cursor_for_item(
+        &self,
+        _metadata: &PaginationMetadata<StringCursor>,
+        _item_idx: i32,
+        item: Option<&ItemT>,
+    ) -> StringCursor {
+        item.map(Self::cursor_for).unwrap_or_default()
+    }
+
+    fn try_get_cursor_for_item(
+        &self,
+        _metadata: &PaginationMetadata<StringCursor>,
+        _item_idx: i32,
+        item: Option<&ItemT>,
+    ) -> Result<StringCursor, CursorError> {
+        item.map(Self::try_cursor_for)
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+
+    fn try_get_page_info<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<StringCursor>,
+        items: Option<&[Option<ItemT>]>,
+    ) -> Result<PageInfoType, CursorError>
+    where
+        PageInfoType: PageInfoFactory<StringCursor>,
+    {
+        // Only the boundary items end up in the page info.
+        if let Some(items) = items {
+            for item in [items.first(), items.last()]
+                .into_iter()
+                .flatten()
+                .flatten()
+            {
+                Self::try_cursor_for(item)?;
+            }
+        }
+        Ok(self.get_page_info(metadata, items))
+    }
+
+    fn get_page_info<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<StringCursor>,
+        items: Option<&[Option<ItemT>]>,
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<StringCursor>,
+    {
+        full_page_info(metadata, items, |idx, item: &Option<ItemT>| {
+            self.get_cursor_for_item(metadata, idx, item.as_ref())
+        })
+    }
+
+    fn get_page_info_for_cursors<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<StringCursor>,
+        items: Option<&[Option<ItemT>]>,
+        cursors: &[StringCursor],
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<StringCursor>,
+    {
+        full_page_info(metadata, items, |idx, item: &Option<ItemT>| {
+            built_cursor(cursors, idx, || {
+                self.get_cursor_for_item(metadata, idx, item.as_ref())
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ContentHashCursorProvider, CursorError, CursorProvider, PageInfoParts, PageRequest,
+        PaginationMetadata, RelayConnection, StringCursor,
+    };
+    use juniper::GraphQLObject;
+    use serde::Serialize;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Clone, GraphQLObject, RelayConnection, Serialize)]
+    pub struct Lumina {
+        name: String,
+        cost: i32,
+    }
+
+    fn lumina(name: &str, cost: i32) -> Option<Lumina> {
+        Some(Lumina {
+            name: name.to_string(),
+            cost,
+        })
+    }
+
+    #[test]
+    fn test_identical_nodes_have_identical_cursors() {
+        let metadata = PaginationMetadata {
+            total_count: None,
+            page_request: None,
+            more_available: None,
+        };
+        let p = ContentHashCursorProvider;
+
+        let a = p.get_cursor_for_item(&metadata, 0, lumina("Dodger", 1).as_ref());
+        let b = p.get_cursor_for_item(&metadata, 7, lumina("Dodger", 1).as_ref());
+        let c = p.get_cursor_for_item(&metadata, 0, lumina("Dodger", 2).as_ref());
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(a.value.len(), 64);
+        assert_eq!(
+            a,
+            ContentHashCursorProvider::cursor_for(&lumina("Dodger", 1))
+        );
+    }
+
+    #[test]
+    fn test_page_info() {
+        let items = vec![lumina("Dodger", 1), lumina("Empowering Parry", 5)];
+        let after = ContentHashCursorProvider::cursor_for(&lumina("Augmented Attack", 3));
+
+        let conn = LuminaRelayConnection::new(
+            Some(&items),
+            None,
+            ContentHashCursorProvider,
+            Some(PageRequest::new(Some(2), Some(after), None)),
+        );
+        assert!(conn.page_info.has_previous_page);
+        assert!(conn.page_info.has_next_page);
+        assert_eq!(
+            conn.page_info.end_cursor,
+            Some(ContentHashCursorProvider::cursor_for(&items[1]))
+        );
+
+        let conn = LuminaRelayConnection::from_metadata(
+            Some(&items),
+            PaginationMetadata {
+                total_count: None,
+                page_request: Some(PageRequest::new(Some(2), None, None)),
+                more_available: Some(false),
+            },
+            ContentHashCursorProvider,
+        );
+        assert!(!conn.page_info.has_previous_page);
+        assert!(!conn.page_info.has_next_page);
+
+        let conn = LuminaRelayConnection::new(
+            Some(&[]),
+            None,
+            ContentHashCursorProvider,
+            Some(PageRequest::<StringCursor>::new(Some(2), None, None)),
+        );
+        assert!(!conn.page_info.has_next_page);
+        assert_eq!(conn.page_info.start_cursor, None);
+    }
+
+    #[test]
+    fn test_unserializable_items() {
+        // JSON object keys have to be strings.
+        let mut item = HashMap::new();
+        item.insert((1, 2), "Gommage");
+        let metadata = PaginationMetadata {
+            total_count: None,
+            page_request: None,
+            more_available: None,
+        };
+        let p = ContentHashCursorProvider;
+
+        assert_eq!(
+            ContentHashCursorProvider::cursor_for(&item),
+            StringCursor::default()
+        );
+        assert!(matches!(
+            p.try_get_cursor_for_item(&metadata, 0, Some(&item)),
+            Err(CursorError::UnhashableItem(_))
+        ));
+
+        let items = vec![Some(item)];
+        let page_info: PageInfoParts<StringCursor> = p.get_page_info(&metadata, Some(&items));
+        assert_eq!(page_info.start_cursor, Some(StringCursor::default()));
+        let page_info: Result<PageInfoParts<StringCursor>, _> =
+            p.try_get_page_info(&metadata, Some(&items));
+        assert!(matches!(page_info, Err(CursorError::UnhashableItem(_))));
+    }
+}
@@ -1,59 +1,198 @@
 use std::string::FromUtf8Error;
+use thiserror::Error;
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Error, Eq, PartialEq)]
 pub enum CursorError {
-    /// Returned when the cursor is invalid - wrong number of segments, mismatch of types, etc.
+    /// Returned when the cursor is invalid - mismatch of types, unparseable values, etc.
+    #[error("Invalid cursor")]
     InvalidCursor,
 
-    /// Returned when the base64 encoding on the cursor is invalid.
-    InvalidCursorEncoding,
-}
+    /// Returned when the cursor isn't valid base64.
+    #[error("Invalid cursor encoding: {0}")]
+    Base64(#[from] base64::DecodeError),
 
-impl std::fmt::Display for CursorError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            CursorError::InvalidCursor => write!(f, "Invalid cursor"),
-            CursorError::InvalidCursorEncoding => write!(f, "Invalid cursor encoding"),
-        }
-    }
+    /// Returned when the decoded cursor isn't valid UTF-8.
+    #[error("Invalid cursor encoding: {0}")]
+    Utf8(#[from] FromUtf8Error),
+
+    /// Returned when the cursor doesn't have as many segments as the cursor type needs.
+    #[error("Malformed cursor: expected {expected} segments, got {got}")]
+    MalformedSegment { expected: usize, got: usize },
+
+    /// Returned when an item has no key to build a keyed cursor from.
+    #[error("Item has no cursor key")]
+    MissingKey,
+
+    /// Returned when a cursor built for one page size is used with a different one.
+    #[error("Page size changed during pagination")]
+    PageSizeChanged,
+
+    /// Returned when a `SignedCursor`'s signature is missing or doesn't match its contents.
+    #[error("Cursor signature mismatch")]
+    SignatureMismatch,
+
+    /// Returned when an `EncryptedCursor` can't be decrypted, because it was tampered with or
+    /// encrypted with a different key.
+    #[error("Cursor could not be decrypted")]
+    DecryptionFailed,
+
+    /// Returned when a `before` cursor doesn't come after the `after` cursor within the page.
+    #[error("The before cursor must come after the after cursor")]
+    InvertedBounds,
+
+    /// Returned when a `StoredCursor`'s token isn't in the `CursorStore`, because it's expired or
+    /// was never issued.
+    #[error("Cursor token is unknown or has expired")]
+    UnknownToken,
+
+    /// Returned when a `VersionedCursor` was written in a version of its format that's newer than the
+    /// current one, or no longer accepted. Holds the version.
+    #[error("Unsupported cursor version: {0}")]
+    UnsupportedVersion(u32),
+
+    /// Returned when an item can't be serialized to build its `ContentHashCursorProvider` cursor,
+    /// such as a map with non-string keys. Holds the serialization error.
+    #[error("Item could not be serialized for its content hash: {0}")]
+    UnhashableItem(String),
+
+    /// Returned when the pagination arguments decoded alongside the cursors are invalid, as
+    /// `PageRequest::from_juniper_args` does.
+    #[error(transparent)]
+    InvalidPageRequest(#[from] PageRequestError),
 }
 
-impl From<base64::DecodeError> for CursorError {
-    fn from(_: base64::DecodeError) -> Self {
-        CursorError::InvalidCursorEncoding
-    }
+/// Errors from validating a `PageRequest` against the Relay connection spec.
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum PageRequestError {
+    /// Both `first` and `last` were provided, which the spec strongly discourages.
+    #[error("Providing both first and last is not supported")]
+    FirstAndLast,
+
+    /// `first` was negative.
+    #[error("first cannot be negative")]
+    NegativeFirst,
+
+    /// `last` was negative.
+    #[error("last cannot be negative")]
+    NegativeLast,
+
+    /// Forward (`first`/`after`) and backward (`last`/`before`) arguments were mixed in one request.
+    #[error("Forward (first/after) and backward (last/before) arguments can't be mixed")]
+    MixedDirections,
+
+    /// The `after` cursor doesn't come before the `before` cursor, so no items can be in the page.
+    #[error("The after cursor must come before the before cursor")]
+    ContradictoryCursors,
 }
 
-impl From<FromUtf8Error> for CursorError {
-    fn from(_: FromUtf8Error) -> Self {
-        CursorError::InvalidCursorEncoding
-    }
+/// Errors from decoding a `RelayIdentifier`.
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum IdentifierError {
+    /// The identifier isn't valid base64.
+    #[error("Invalid base64 encoding: {0}")]
+    Base64(#[from] base64::DecodeError),
+
+    /// The decoded identifier isn't valid UTF-8.
+    #[error("Invalid UTF-8 encoding: {0}")]
+    Utf8(#[from] FromUtf8Error),
+
+    /// The decoded identifier isn't a `type::id` pair.
+    #[error("Invalid Relay identifier")]
+    MissingDelimiter,
+
+    /// The id segment couldn't be parsed into the id type.
+    #[error("Invalid identifier")]
+    InvalidId,
+
+    /// The type segment isn't one of the type discriminator's values. Holds the segment.
+    #[error("Invalid type delimiter: {0}")]
+    UnknownDiscriminator(String),
+
+    /// The identifier is for a different type than the one expected.
+    #[error("Expected a {expected} identifier, got {got}")]
+    UnexpectedType { expected: String, got: String },
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::cursor_errors::CursorError;
+    use crate::cursor_errors::{CursorError, PageRequestError};
 
     #[test]
     fn display_types() {
         assert_eq!(format!("{}", CursorError::InvalidCursor), "Invalid cursor");
         assert_eq!(
-            format!("{}", CursorError::InvalidCursorEncoding),
-            "Invalid cursor encoding"
+            format!(
+                "{}",
+                CursorError::Base64(base64::DecodeError::InvalidPadding)
+            ),
+            "Invalid cursor encoding: Invalid padding"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                CursorError::MalformedSegment {
+                    expected: 2,
+                    got: 1
+                }
+            ),
+            "Malformed cursor: expected 2 segments, got 1"
+        );
+        assert_eq!(
+            format!("{}", CursorError::PageSizeChanged),
+            "Page size changed during pagination"
+        );
+        assert_eq!(
+            format!("{}", CursorError::SignatureMismatch),
+            "Cursor signature mismatch"
+        );
+        assert_eq!(
+            format!("{}", CursorError::UnsupportedVersion(3)),
+            "Unsupported cursor version: 3"
+        );
+    }
+
+    #[test]
+    fn page_request_error_display_types() {
+        assert_eq!(
+            format!("{}", PageRequestError::FirstAndLast),
+            "Providing both first and last is not supported"
+        );
+        assert_eq!(
+            format!("{}", PageRequestError::NegativeFirst),
+            "first cannot be negative"
         );
     }
 
     #[test]
     fn from_utf8_error() {
         let error = String::from_utf8(vec![0x80]).unwrap_err();
-        let cursor_error = CursorError::from(error);
-        assert_eq!(cursor_error, CursorError::InvalidCursorEncoding);
+        let cursor_error = CursorError::from(error.clone());
+        assert_eq!(cursor_error, CursorError::Utf8(error));
     }
 
     #[test]
     fn base64_error() {
         let error = base64::DecodeError::InvalidPadding;
-        let cursor_error = CursorError::from(error);
-        assert_eq!(cursor_error, CursorError::InvalidCursorEncoding);
+        let cursor_error = CursorError::from(error.clone());
+        assert_eq!(cursor_error, CursorError::Base64(error));
+    }
+
+    #[test]
+    fn page_request_error() {
+        let cursor_error = CursorError::from(PageRequestError::FirstAndLast);
+        assert_eq!(
+            cursor_error,
+            CursorError::InvalidPageRequest(PageRequestError::FirstAndLast)
+        );
+        assert_eq!(
+            cursor_error.to_string(),
+            "Providing both first and last is not supported"
+        );
+    }
+
+    #[test]
+    fn is_std_error() {
+        let err: Box<dyn std::error::Error> = Box::new(CursorError::InvalidCursor);
+        assert_eq!(err.to_string(), "Invalid cursor");
     }
 }
@@ -1,6 +1,8 @@
+use crate::page_info_factory::PageInfoParts;
 use crate::pagination_metadata::PaginationMetadata;
-use crate::{PageInfoFactory, StringCursor};
+use crate::{CursorError, CursorKind, PageInfoFactory, StringCursor, peek_cursor_kind};
 use juniper_relay_helpers::{Cursor, OffsetCursor};
+use std::marker::PhantomData;
 
 /// Trait to implement when building a Relay cursor provider.
 ///
@@ -22,6 +24,23 @@ pub trait CursorProvider<ItemT> {
         item: Option<&ItemT>,
     ) -> Self::CursorType;
 
+    /// Builds the cursors for all of the items in one go, one per item and in the same order. Used by
+    /// `RelayConnection::new`, `from_metadata` and `from_iter`.
+    ///
+    /// Defaults to calling `get_cursor_for_item` for each item. Override it when building cursors is
+    /// expensive and can be batched, or needs state shared across the page.
+    fn get_cursors_for_items(
+        &self,
+        metadata: &PaginationMetadata<Self::CursorType>,
+        items: &[Option<ItemT>],
+    ) -> Vec<Self::CursorType> {
+        items
+            .iter()
+            .enumerate()
+            .map(|(idx, item)| self.get_cursor_for_item(metadata, idx as i32, item.as_ref()))
+            .collect()
+    }
+
     /// Builds the `PageInfo` to return to the RelayConnection
     fn get_page_info<PageInfoType>(
         &self,
@@ -30,22 +49,251 @@ pub trait CursorProvider<ItemT> {
     ) -> PageInfoType
     where
         PageInfoType: PageInfoFactory<Self::CursorType>;
+
+    /// As `get_page_info`, given the cursors `get_cursors_for_items` already built for `items`. The
+    /// connection constructors use this so providers whose start and end cursors are those of the
+    /// first and last items can reuse them, rather than building them a second time.
+    ///
+    /// Defaults to `get_page_info`, ignoring the cursors.
+    fn get_page_info_for_cursors<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<Self::CursorType>,
+        items: Option<&[Option<ItemT>]>,
+        _cursors: &[Self::CursorType],
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<Self::CursorType>,
+    {
+        self.get_page_info(metadata, items)
+    }
+
+    /// The total number of items in the set, if the provider knows it (for instance because it ran
+    /// the query). When this returns `Some`, it takes precedence over the `total_items` given to the
+    /// connection constructors, and is what the rest of the provider sees in `metadata.total_count`.
+    /// Defaults to `None`, leaving the total as given.
+    fn total_count(&self, _metadata: &PaginationMetadata<Self::CursorType>) -> Option<i32> {
+        None
+    }
+
+    /// Fallible version of `get_cursor_for_item`, for providers that can fail to build a cursor for
+    /// some items. Used by `RelayConnection::try_new`. Defaults to the infallible version.
+    fn try_get_cursor_for_item(
+        &self,
+        metadata: &PaginationMetadata<Self::CursorType>,
+        item_idx: i32,
+        item: Option<&ItemT>,
+    ) -> Result<Self::CursorType, CursorError> {
+        Ok(self.get_cursor_for_item(metadata, item_idx, item))
+    }
+
+    /// Fallible version of `get_page_info`. Used by `RelayConnection::try_new`. Defaults to the
+    /// infallible version.
+    fn try_get_page_info<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<Self::CursorType>,
+        items: Option<&[Option<ItemT>]>,
+    ) -> Result<PageInfoType, CursorError>
+    where
+        PageInfoType: PageInfoFactory<Self::CursorType>,
+    {
+        Ok(self.get_page_info(metadata, items))
+    }
+
+    /// As `get_page_info`, but with the boundary items given explicitly rather than taken from the
+    /// ends of `items`. Useful when the slice is sorted by something other than the cursor order, so
+    /// the first and last items in it aren't the ends of the page.
+    ///
+    /// Boundaries left as `None` fall back to the ends of `items`. Positional providers (such as
+    /// `OffsetCursorProvider`) still place the start and end cursors at the first and last index.
+    fn get_page_info_with_boundaries<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<Self::CursorType>,
+        items: Option<&[Option<ItemT>]>,
+        boundaries: Boundaries<'_, ItemT>,
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<Self::CursorType>,
+    {
+        let parts: PageInfoParts<Self::CursorType> = self.get_page_info(metadata, items);
+        let last_idx = items.map(|i| i.len()).unwrap_or(0).saturating_sub(1) as i32;
+
+        PageInfoType::new(
+            parts.has_prev_page,
+            parts.has_next_page,
+            match boundaries.first {
+                Some(first) => Some(self.get_cursor_for_item(metadata, 0, Some(first))),
+                None => parts.start_cursor,
+            },
+            match boundaries.last {
+                Some(last) => Some(self.get_cursor_for_item(metadata, last_idx, Some(last))),
+                None => parts.end_cursor,
+            },
+        )
+    }
+}
+
+/// The items at the start and end of a page, for `CursorProvider::get_page_info_with_boundaries`.
+#[derive(Debug)]
+pub struct Boundaries<'a, ItemT> {
+    /// The item the page starts with, used to build the start cursor.
+    pub first: Option<&'a ItemT>,
+
+    /// The item the page ends with, used to build the end cursor.
+    pub last: Option<&'a ItemT>,
+}
+
+impl<ItemT> Clone for Boundaries<'_, ItemT> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<ItemT> Copy for Boundaries<'_, ItemT> {}
+
+/// Extension to `CursorProvider` for providers that can also page backwards with `last` and `before`.
+///
+/// When paging backwards the page is taken from the tail of the result set, so the cursors and
+/// `has_previous_page`/`has_next_page` need working out from the end of the window instead of the start.
+pub trait BidirectionalCursorProvider<ItemT>: CursorProvider<ItemT> {
+    /// Builds the `PageInfo` for a page taken from the tail of the set, with `items` in their natural
+    /// (forward) order.
+    fn reverse_page_info<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<Self::CursorType>,
+        items: Option<&[Option<ItemT>]>,
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<Self::CursorType>;
 }
 
 // -------------- OffsetCursorProvider ---------------
 
 /// Built-in cursor provider that can handle Offset cursors. Serves as a reference implementation for
 /// your own cursor providers too.
+///
+/// Use `with_page_size_in_cursors` and `with_total_in_cursors` to embed the requested `first` and
+/// the total count into every cursor it builds, and `with_max_first` to cap the page size. These
+/// return a `ConfiguredOffsetCursorProvider`, which pages the same way with the options applied.
+///
+/// Only the request's `first` is used to work out `has_next_page`. A `first` embedded in the `after`
+/// cursor records the page size the cursor was built for, and is ignored here even when it
+/// disagrees with the request (or the request has no `first` at all). Resolvers that want to reject
+/// a changed page size can check with `OffsetCursor::ensure_same_page_size`.
 pub struct OffsetCursorProvider;
+
 impl<ItemT> CursorProvider<ItemT> for OffsetCursorProvider {
     type CursorType = OffsetCursor;
 
+    fn get_cursor_for_item(
+        &self,
+        metadata: &PaginationMetadata<OffsetCursor>,
+        item_idx: i32,
+        item: Option<&ItemT>,
+    ) -> OffsetCursor {
+        ConfiguredOffsetCursorProvider::new().get_cursor_for_item(metadata, item_idx, item)
+    }
+
+    fn get_page_info<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<OffsetCursor>,
+        items: Option<&[Option<ItemT>]>,
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<OffsetCursor>,
+    {
+        ConfiguredOffsetCursorProvider::new().get_page_info(metadata, items)
+    }
+
+    fn get_page_info_for_cursors<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<OffsetCursor>,
+        items: Option<&[Option<ItemT>]>,
+        cursors: &[OffsetCursor],
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<OffsetCursor>,
+    {
+        ConfiguredOffsetCursorProvider::new().get_page_info_for_cursors(metadata, items, cursors)
+    }
+}
+
+impl<ItemT> BidirectionalCursorProvider<ItemT> for OffsetCursorProvider {
+    fn reverse_page_info<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<OffsetCursor>,
+        items: Option<&[Option<ItemT>]>,
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<OffsetCursor>,
+    {
+        ConfiguredOffsetCursorProvider::new().reverse_page_info(metadata, items)
+    }
+}
+
+impl Default for OffsetCursorProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OffsetCursorProvider {
+    /// Shortcut method for creating a new instance of OffsetCursorProvider. May be used in the future
+    /// if we need to pass things into it.
+    pub const fn new() -> Self {
+        OffsetCursorProvider
+    }
+
+    /// Caps `first` at `max` when working out the page info. See
+    /// `ConfiguredOffsetCursorProvider::with_max_first`.
+    pub fn with_max_first(self, max: i32) -> ConfiguredOffsetCursorProvider {
+        ConfiguredOffsetCursorProvider::new().with_max_first(max)
+    }
+
+    /// Embeds the requested `first` into each cursor. See
+    /// `ConfiguredOffsetCursorProvider::with_page_size_in_cursors`.
+    pub fn with_page_size_in_cursors(self) -> ConfiguredOffsetCursorProvider {
+        ConfiguredOffsetCursorProvider::new().with_page_size_in_cursors()
+    }
+
+    /// Embeds the total count into each cursor. See
+    /// `ConfiguredOffsetCursorProvider::with_total_in_cursors`.
+    pub fn with_total_in_cursors(self) -> ConfiguredOffsetCursorProvider {
+        ConfiguredOffsetCursorProvider::new().with_total_in_cursors()
+    }
+}
+
+/// An `OffsetCursorProvider` with options set, built with its `with_` methods, which can be chained:
+///
+/// ```
+/// use juniper_relay_helpers::OffsetCursorProvider;
+///
+/// let provider = OffsetCursorProvider::new()
+///     .with_page_size_in_cursors()
+///     .with_total_in_cursors();
+/// ```
+///
+/// `with_max_first` caps the page size a client can ask for. The provider only applies the cap to
+/// its own page info, so the resolver must clamp the request with `PageRequest::clamp_first` and use
+/// the clamped `first` as the `LIMIT` of its query too, or the page info won't match the items.
+pub struct ConfiguredOffsetCursorProvider {
+    include_first: bool,
+    include_total: bool,
+    max_first: Option<i32>,
+}
+
+impl<ItemT> CursorProvider<ItemT> for ConfiguredOffsetCursorProvider {
+    type CursorType = OffsetCursor;
+
     fn get_cursor_for_item(
         &self,
         metadata: &PaginationMetadata<OffsetCursor>,
         item_idx: i32,
         _item: Option<&ItemT>,
     ) -> OffsetCursor {
+        if let Some(start) = Self::backward_window_start(metadata) {
+            return self.cursor_at(metadata, start.saturating_add(item_idx));
+        }
+
         // OK this is annoying. If there _was_ a cursor passed to `after`, the offset needs to start
         // at the next item. If there wasn't, the offset needs to start at the first item (0).
         let mut offset_adjust = 0;
@@ -62,7 +310,14 @@ impl<ItemT> CursorProvider<ItemT> for OffsetCursorProvider {
             None => default_cursor,
         };
 
-        OffsetCursor::new(current_cursor.offset + offset_adjust + item_idx)
+        // Offsets are client provided, so saturate rather than overflow on huge values.
+        self.cursor_at(
+            metadata,
+            current_cursor
+                .offset
+                .saturating_add(offset_adjust)
+                .saturating_add(item_idx),
+        )
     }
 
     fn get_page_info<PageInfoType>(
@@ -73,32 +328,93 @@ impl<ItemT> CursorProvider<ItemT> for OffsetCursorProvider {
     where
         PageInfoType: PageInfoFactory<OffsetCursor>,
     {
-        let default_cursor = OffsetCursor::default();
+        // An empty set has no pages either side, wherever the cursors claim to be. A count of 0 that
+        // came with items is stale, so the items win and the page info is worked out as usual.
+        if metadata.known_total_count() == Some(0) && items.is_none_or(<[_]>::is_empty) {
+            return PageInfoType::new(false, false, None, None);
+        }
+
+        if Self::backward_window_start(metadata).is_some() {
+            return self.reverse_page_info(metadata, items);
+        }
+
+        self.forward_page_info(metadata, items, |idx, item| {
+            self.get_cursor_for_item(metadata, idx, item)
+        })
+    }
+
+    fn get_page_info_for_cursors<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<OffsetCursor>,
+        items: Option<&[Option<ItemT>]>,
+        cursors: &[OffsetCursor],
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<OffsetCursor>,
+    {
+        if (metadata.known_total_count() == Some(0) && items.is_none_or(<[_]>::is_empty))
+            || Self::backward_window_start(metadata).is_some()
+        {
+            return self.get_page_info(metadata, items);
+        }
+
+        self.forward_page_info(metadata, items, |idx, item| {
+            built_cursor(cursors, idx, || {
+                self.get_cursor_for_item(metadata, idx, item)
+            })
+        })
+    }
+}
+
+impl ConfiguredOffsetCursorProvider {
+    /// PageInfo for a forward page, with `cursor_for` building the cursor for the item at an index.
+    fn forward_page_info<ItemT, PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<OffsetCursor>,
+        items: Option<&[Option<ItemT>]>,
+        cursor_for: impl Fn(i32, Option<&ItemT>) -> OffsetCursor,
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<OffsetCursor>,
+    {
         let current_cursor = metadata
-            .clone()
             .page_request
+            .as_ref()
             .and_then(|pr| pr.current_cursor())
-            .unwrap_or(default_cursor);
+            .unwrap_or_default();
 
-        let has_next_page = if let Some(pr) = &metadata.page_request {
-            // Check if we requested up to or over the total items.
-            if let Some(first) = pr.first {
-                current_cursor.offset + first < metadata.total_count.unwrap_or(0)
-            } else {
-                false
+        let has_next_page = if let Some(more_available) = metadata.more_available {
+            // The resolver has told us outright, so trust it over the counts.
+            more_available
+        } else if metadata.page_request.is_some() {
+            match (self.requested_first(metadata), metadata.known_total_count()) {
+                // Check if we requested up to or over the total items.
+                (Some(first), Some(total)) => current_cursor.offset.saturating_add(first) < total,
+                // Without a total, a full page is the best hint that more items follow it.
+                (Some(first), None) => {
+                    items.map(|i| i.len()).unwrap_or(0) as i32 >= first && first > 0
+                }
+                (None, _) => false,
             }
         } else {
             // We didn't request a first, which means the entire result set, therefore no next page
             false
         };
 
+        // Anything `after` a cursor has at least the cursor's own item before it, even at offset 0.
+        let has_previous_page = metadata
+            .page_request
+            .as_ref()
+            .is_some_and(|pr| pr.after.is_some())
+            || current_cursor.offset > 0;
+
         PageInfoType::new(
-            current_cursor.offset > 0,
+            has_previous_page,
             has_next_page,
             if let Some(items) = items
                 && !items.is_empty()
             {
-                Some(self.get_cursor_for_item(metadata, 0, items[0].as_ref()))
+                Some(cursor_for(0, items[0].as_ref()))
             } else {
                 None
             },
@@ -106,52 +422,441 @@ impl<ItemT> CursorProvider<ItemT> for OffsetCursorProvider {
                 && !items.is_empty()
             {
                 let last_index = items.len() - 1;
-                Some(self.get_cursor_for_item(
-                    metadata,
-                    last_index as i32,
-                    items[last_index].as_ref(),
-                ))
+                Some(cursor_for(last_index as i32, items[last_index].as_ref()))
             } else {
                 None
             },
         )
     }
-}
+}
+
+/// Pages backwards by treating the `before` offset as the exclusive upper bound of the window, and
+/// taking `last` items back from there. A `last` that runs past the start of the set is clamped to it.
+impl<ItemT> BidirectionalCursorProvider<ItemT> for ConfiguredOffsetCursorProvider {
+    fn reverse_page_info<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<OffsetCursor>,
+        items: Option<&[Option<ItemT>]>,
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<OffsetCursor>,
+    {
+        let before = metadata
+            .page_request
+            .as_ref()
+            .and_then(|pr| pr.before.as_ref());
+        let start = Self::window_start_before(metadata);
+
+        // The `before` item itself follows this page, as long as it's actually within the set.
+        let has_next_page = metadata.more_available.unwrap_or_else(|| {
+            before.is_some_and(|b| {
+                metadata
+                    .known_total_count()
+                    .is_none_or(|total| b.offset < total)
+            })
+        });
+
+        let item_count = items.map(|i| i.len()).unwrap_or(0) as i32;
+        PageInfoType::new(
+            start > 0,
+            has_next_page,
+            (item_count > 0).then(|| self.cursor_at(metadata, start)),
+            (item_count > 0)
+                .then(|| self.cursor_at(metadata, start.saturating_add(item_count - 1))),
+        )
+    }
+}
+
+impl ConfiguredOffsetCursorProvider {
+    /// No options set, which pages the same as a plain `OffsetCursorProvider`.
+    const fn new() -> Self {
+        ConfiguredOffsetCursorProvider {
+            include_first: false,
+            include_total: false,
+            max_first: None,
+        }
+    }
+
+    /// Caps `first` at `max` when working out the page info, so a request for a million items is
+    /// treated as a request for `max`. A request with no `first` at all is treated as one for `max`
+    /// too. See `PageRequest::clamp_first` for clamping the request itself.
+    pub fn with_max_first(mut self, max: i32) -> Self {
+        self.max_first = Some(max);
+        self
+    }
+
+    /// Embeds the requested `first` into each cursor, so a later request can check it's still
+    /// paginating with the same page size with `OffsetCursor::same_page_size`.
+    pub fn with_page_size_in_cursors(mut self) -> Self {
+        self.include_first = true;
+        self
+    }
+
+    /// Embeds the total count from the `PaginationMetadata` into each cursor, so clients can decode
+    /// it from any edge for things like progress bars. Makes the cursors slightly larger.
+    pub fn with_total_in_cursors(mut self) -> Self {
+        self.include_total = true;
+        self
+    }
+
+    /// Builds the cursor for the given offset, including any configured extras.
+    fn cursor_at(&self, metadata: &PaginationMetadata<OffsetCursor>, offset: i32) -> OffsetCursor {
+        let mut cursor = OffsetCursor::new(offset);
+        if self.include_first {
+            cursor = cursor.with_first(self.requested_first(metadata));
+        }
+        if self.include_total {
+            cursor = cursor.with_total(metadata.known_total_count());
+        }
+        cursor
+    }
+
+    /// The request's `first`, capped at the configured maximum.
+    fn requested_first(&self, metadata: &PaginationMetadata<OffsetCursor>) -> Option<i32> {
+        let first = metadata.page_request.as_ref().and_then(|pr| pr.first);
+        match self.max_first {
+            Some(max) => Some(first.map_or(max, |first| first.min(max))),
+            None => first,
+        }
+    }
+
+    /// Offset of the first item in a backwards page: `last` items back from the `before` offset (or
+    /// the end of the set), clamped to the start of the set.
+    fn window_start_before(metadata: &PaginationMetadata<OffsetCursor>) -> i32 {
+        let pr = metadata.page_request.as_ref();
+        let end = pr
+            .and_then(|pr| pr.before.as_ref())
+            .map(|before| before.offset)
+            .or(metadata.known_total_count())
+            .unwrap_or(0);
+
+        match pr.and_then(|pr| pr.last) {
+            Some(last) => end.saturating_sub(last).max(0),
+            None => 0,
+        }
+    }
+
+    /// Returns the start of the window if this is a backwards request (`last` without `after`).
+    fn backward_window_start(metadata: &PaginationMetadata<OffsetCursor>) -> Option<i32> {
+        let pr = metadata.page_request.as_ref()?;
+        if pr.last.is_some() && pr.after.is_none() {
+            Some(Self::window_start_before(metadata))
+        } else {
+            None
+        }
+    }
+}
+
+// ------------- Keyed cursor provider -------------
+
+/// Trait to implement to use with items in the `KeyedCursorProvider`.
+pub trait CursorByKey {
+    fn cursor_key(&self) -> String;
+}
+
+/// Lets `KeyedCursorProvider` work with connections derived with `#[relay(rc_node)]`.
+impl<T> CursorByKey for std::sync::Arc<T>
+where
+    T: CursorByKey + ?Sized,
+{
+    fn cursor_key(&self) -> String {
+        (**self).cursor_key()
+    }
+}
+
+/// This cursor provider is for working with something like DynamoDB. Each item's cursor is generated
+/// by implementing the `CursorByKey` trait, and the PageInfo is generated using the item cursors themselves.
+///
+/// If any `after` is provided, it's assumed that there is a previous page.
+/// If there are any items returned, it's assumed that there is a next page, unless the resolver
+/// sets `PaginationMetadata::more_available`.
+///
+/// NOTE - read that previous line again. This follows the style of opaque, web scale cursors where the only
+/// valid last page is an empty page. This can be unexpected to a lot of frontends.
+///
+/// To get an exact `has_next_page` instead, fetch one item more than `first` and build the page with
+/// an `OverfetchPaginator`, which trims the extra item and sets `more_available`.
+/// A full last page then reports no next page.
+///
+/// The fallible `try_` methods (used by `RelayConnection::try_new`) return `CursorError::MissingKey`
+/// for missing items, or items with an empty key, rather than building an empty cursor.
+pub struct KeyedCursorProvider;
+
+impl<ItemT> CursorProvider<ItemT> for KeyedCursorProvider
+where
+    ItemT: CursorByKey,
+{
+    type CursorType = StringCursor;
+
+    fn get_cursor_for_item(
+        &self,
+        _metadata: &PaginationMetadata<StringCursor>,
+        _item_idx: i32,
+        item: Option<&ItemT>,
+    ) -> StringCursor {
+        StringCursor::new(item.map(|i| i.cursor_key()).unwrap_or_default())
+    }
+
+    fn try_get_cursor_for_item(
+        &self,
+        _metadata: &PaginationMetadata<StringCursor>,
+        _item_idx: i32,
+        item: Option<&ItemT>,
+    ) -> Result<StringCursor, CursorError> {
+        try_key_cursor(item.map(|i| i.cursor_key()))
+    }
+
+    fn try_get_page_info<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<StringCursor>,
+        items: Option<&[Option<ItemT>]>,
+    ) -> Result<PageInfoType, CursorError>
+    where
+        PageInfoType: PageInfoFactory<StringCursor>,
+    {
+        try_keyed_page_info(metadata, items, |i: &ItemT| i.cursor_key())
+    }
+
+    fn get_page_info<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<StringCursor>,
+        items: Option<&[Option<ItemT>]>,
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<StringCursor>,
+    {
+        keyed_page_info(metadata, items, |i: &ItemT| i.cursor_key())
+    }
+
+    fn get_page_info_for_cursors<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<StringCursor>,
+        items: Option<&[Option<ItemT>]>,
+        cursors: &[StringCursor],
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<StringCursor>,
+    {
+        boundary_page_info(metadata, items, |idx, item: &Option<ItemT>| {
+            built_cursor(cursors, idx, || {
+                StringCursor::new(item.as_ref().map(|i| i.cursor_key()).unwrap_or_default())
+            })
+        })
+    }
+}
+
+impl KeyedCursorProvider {
+    /// Checks that `before` comes strictly after `after`, by finding the items they point at within
+    /// the fetched window. Keys can't be compared directly, so this is the only ordering available for
+    /// keyed cursors. Returns `CursorError::InvertedBounds` if `before` is at or before `after`.
+    ///
+    /// A cursor that isn't found in `items` could be anywhere outside the window, so it's not treated
+    /// as an error.
+    pub fn validate_bounds<ItemT>(
+        items: &[Option<ItemT>],
+        after: &StringCursor,
+        before: &StringCursor,
+    ) -> Result<(), CursorError>
+    where
+        ItemT: CursorByKey,
+    {
+        let position = |cursor: &StringCursor| {
+            items.iter().position(|item| {
+                item.as_ref()
+                    .is_some_and(|i| i.cursor_key() == cursor.value)
+            })
+        };
+
+        match (position(after), position(before)) {
+            (Some(after_idx), Some(before_idx)) if before_idx <= after_idx => {
+                Err(CursorError::InvertedBounds)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Builds a keyed cursor, failing if there's no item or its key is empty.
+fn try_key_cursor(key: Option<String>) -> Result<StringCursor, CursorError> {
+    match key {
+        Some(key) if !key.is_empty() => Ok(StringCursor::new(key)),
+        _ => Err(CursorError::MissingKey),
+    }
+}
+
+/// PageInfo for the keyed providers, with `key_fn` building the key for each item.
+fn keyed_page_info<ItemT, PageInfoType>(
+    metadata: &PaginationMetadata<StringCursor>,
+    items: Option<&[Option<ItemT>]>,
+    key_fn: impl Fn(&ItemT) -> String,
+) -> PageInfoType
+where
+    PageInfoType: PageInfoFactory<StringCursor>,
+{
+    boundary_page_info(metadata, items, |_, item: &Option<ItemT>| {
+        StringCursor::new(item.as_ref().map(&key_fn).unwrap_or_default())
+    })
+}
+
+/// PageInfo built from the cursors of the first and last items, with `cursor_for` building the
+/// cursor for an item at an index. Follows the rules of the `KeyedCursorProvider`.
+fn boundary_page_info<ItemT, CursorT, PageInfoType>(
+    metadata: &PaginationMetadata<CursorT>,
+    items: Option<&[Option<ItemT>]>,
+    cursor_for: impl Fn(i32, &Option<ItemT>) -> CursorT,
+) -> PageInfoType
+where
+    CursorT: Cursor,
+    PageInfoType: PageInfoFactory<CursorT>,
+{
+    let mut first_item_cursor: Option<CursorT> = None;
+    let mut last_item_cursor: Option<CursorT> = None;
+
+    if let Some(items) = items {
+        first_item_cursor = items.first().map(|item| cursor_for(0, item));
+        last_item_cursor = items
+            .last()
+            .map(|item| cursor_for(items.len() as i32 - 1, item));
+    }
 
-impl Default for OffsetCursorProvider {
-    fn default() -> Self {
-        Self::new()
+    let mut has_previous_page = false;
+    if let Some(pr) = &metadata.page_request
+        && pr.after.is_some()
+    {
+        has_previous_page = true;
     }
+
+    let has_next_page = if let Some(more_available) = metadata.more_available {
+        more_available
+    } else if let Some(items) = items {
+        !items.is_empty()
+    } else {
+        false
+    };
+
+    PageInfoType::new(
+        has_previous_page,
+        has_next_page,
+        first_item_cursor,
+        last_item_cursor,
+    )
 }
 
-impl OffsetCursorProvider {
-    /// Shortcut method for creating a new instance of OffsetCursorProvider. May be used in the future
-    /// if we need to pass things into it.
-    pub fn new() -> Self {
-        OffsetCursorProvider
-    }
+/// PageInfo built from the cursors of the first and last items, for providers that can't tell where
+/// the page sits in the set. There's a previous page if an `after` was given, and a next page if
+/// `PaginationMetadata::more_available` says so or, failing that, the page is full.
+pub(crate) fn full_page_info<ItemT, CursorT, PageInfoType>(
+    metadata: &PaginationMetadata<CursorT>,
+    items: Option<&[Option<ItemT>]>,
+    cursor_for: impl Fn(i32, &Option<ItemT>) -> CursorT,
+) -> PageInfoType
+where
+    CursorT: Cursor,
+    PageInfoType: PageInfoFactory<CursorT>,
+{
+    let items = items.unwrap_or_default();
+    let page_request = metadata.page_request.as_ref();
+
+    let has_next_page = metadata.more_available.unwrap_or_else(|| {
+        page_request
+            .and_then(|pr| pr.first)
+            .is_some_and(|first| first > 0 && items.len() as i32 >= first)
+    });
+
+    PageInfoType::new(
+        page_request.is_some_and(|pr| pr.after.is_some()),
+        has_next_page,
+        items.first().map(|item| cursor_for(0, item)),
+        items
+            .last()
+            .map(|item| cursor_for(items.len() as i32 - 1, item)),
+    )
 }
 
-// ------------- Keyed cursor provider -------------
+/// The cursor already built for the item at `idx`, or a new one from `build` if there isn't one.
+pub(crate) fn built_cursor<CursorT: Clone>(
+    cursors: &[CursorT],
+    idx: i32,
+    build: impl FnOnce() -> CursorT,
+) -> CursorT {
+    cursors.get(idx as usize).cloned().unwrap_or_else(build)
+}
 
-/// Trait to implement to use with items in the `KeyedCursorProvider`.
-pub trait CursorByKey {
-    fn cursor_key(&self) -> String;
+/// As `keyed_page_info`, but fails if either boundary item is missing a key.
+fn try_keyed_page_info<ItemT, PageInfoType>(
+    metadata: &PaginationMetadata<StringCursor>,
+    items: Option<&[Option<ItemT>]>,
+    key_fn: impl Fn(&ItemT) -> String,
+) -> Result<PageInfoType, CursorError>
+where
+    PageInfoType: PageInfoFactory<StringCursor>,
+{
+    // Only the boundary items end up in the page info.
+    if let Some(items) = items
+        && let (Some(first), Some(last)) = (items.first(), items.last())
+    {
+        try_key_cursor(first.as_ref().map(&key_fn))?;
+        try_key_cursor(last.as_ref().map(&key_fn))?;
+    }
+    Ok(keyed_page_info(metadata, items, key_fn))
 }
 
-/// This cursor provider is for working with something like DynamoDB. Each item's cursor is generated
-/// by implementing the `CursorByKey` trait, and the PageInfo is generated using the item cursors themselves.
+/// As `CursorByKey`, for items whose key depends on something in the request context, such as a
+/// per-user sort position.
+pub trait CursorByKeyWithCtx<Ctx> {
+    fn cursor_key(&self, ctx: &Ctx) -> String;
+}
+
+/// As `KeyedCursorProvider`, but for items implementing `CursorByKeyWithCtx`. The provider holds on
+/// to the context for the request and hands it to each item when building its cursor:
 ///
-/// If any `after` is provided, it's assumed that there is a previous page.
-/// If there are any items returned, it's assumed that there is a next page.
+/// ```
+/// use juniper::GraphQLObject;
+/// use juniper_relay_helpers::{
+///     CursorByKeyWithCtx, KeyedCursorProviderWithCtx, RelayConnection,
+/// };
 ///
-/// NOTE - read that previous line again. This follows the style of opaque, web scale cursors where the only
-/// valid last page is an empty page. This can be unexpected to a lot of frontends.
-pub struct KeyedCursorProvider;
+/// pub struct Context {
+///     pub user_id: String,
+/// }
+/// impl juniper::Context for Context {}
+///
+/// #[derive(Clone, GraphQLObject, RelayConnection)]
+/// #[relay(context = Context)]
+/// pub struct Bookmark {
+///     pub id: String,
+/// }
+///
+/// impl CursorByKeyWithCtx<Context> for Bookmark {
+///     fn cursor_key(&self, ctx: &Context) -> String {
+///         format!("{}#{}", ctx.user_id, self.id)
+///     }
+/// }
+///
+/// # let ctx = Context { user_id: "maelle".to_string() };
+/// let items = [Some(Bookmark { id: "b1".to_string() })];
+/// let conn = BookmarkRelayConnection::new(
+///     Some(&items),
+///     None,
+///     KeyedCursorProviderWithCtx::new(&ctx),
+///     None,
+/// );
+/// ```
+///
+/// Page info follows the same rules as the `KeyedCursorProvider`.
+pub struct KeyedCursorProviderWithCtx<'a, Ctx> {
+    ctx: &'a Ctx,
+}
 
-impl<ItemT> CursorProvider<ItemT> for KeyedCursorProvider
+impl<'a, Ctx> KeyedCursorProviderWithCtx<'a, Ctx> {
+    pub fn new(ctx: &'a Ctx) -> Self {
+        KeyedCursorProviderWithCtx { ctx }
+    }
+}
+
+impl<ItemT, Ctx> CursorProvider<ItemT> for KeyedCursorProviderWithCtx<'_, Ctx>
 where
-    ItemT: CursorByKey,
+    ItemT: CursorByKeyWithCtx<Ctx>,
 {
     type CursorType = StringCursor;
 
@@ -161,7 +866,27 @@ where
         _item_idx: i32,
         item: Option<&ItemT>,
     ) -> StringCursor {
-        StringCursor::new(item.map(|i| i.cursor_key()).unwrap_or_default())
+        StringCursor::new(item.map(|i| i.cursor_key(self.ctx)).unwrap_or_default())
+    }
+
+    fn try_get_cursor_for_item(
+        &self,
+        _metadata: &PaginationMetadata<StringCursor>,
+        _item_idx: i32,
+        item: Option<&ItemT>,
+    ) -> Result<StringCursor, CursorError> {
+        try_key_cursor(item.map(|i| i.cursor_key(self.ctx)))
+    }
+
+    fn try_get_page_info<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<StringCursor>,
+        items: Option<&[Option<ItemT>]>,
+    ) -> Result<PageInfoType, CursorError>
+    where
+        PageInfoType: PageInfoFactory<StringCursor>,
+    {
+        try_keyed_page_info(metadata, items, |i: &ItemT| i.cursor_key(self.ctx))
     }
 
     fn get_page_info<PageInfoType>(
@@ -172,54 +897,181 @@ where
     where
         PageInfoType: PageInfoFactory<StringCursor>,
     {
-        let mut first_item_cursor: Option<StringCursor> = None;
-        let mut last_item_cursor: Option<StringCursor> = None;
+        keyed_page_info(metadata, items, |i: &ItemT| i.cursor_key(self.ctx))
+    }
 
-        if let Some(items) = items {
-            if let Some(first_item) = items.first() {
-                first_item_cursor =
-                    Some(self.get_cursor_for_item(metadata, 0, first_item.as_ref()));
-            }
+    fn get_page_info_for_cursors<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<StringCursor>,
+        items: Option<&[Option<ItemT>]>,
+        cursors: &[StringCursor],
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<StringCursor>,
+    {
+        boundary_page_info(metadata, items, |idx, item: &Option<ItemT>| {
+            built_cursor(cursors, idx, || {
+                self.get_cursor_for_item(metadata, idx, item.as_ref())
+            })
+        })
+    }
+}
 
-            if let Some(last_item) = items.last() {
-                last_item_cursor = Some(self.get_cursor_for_item(
-                    metadata,
-                    items.len() as i32 - 1,
-                    last_item.as_ref(),
-                ));
-            }
-        }
+/// Cursor provider built from a closure, for one-off cursor rules that don't warrant implementing
+/// `CursorProvider`. The closure is given the metadata, the item's index and the item, and returns
+/// its cursor:
+///
+/// ```
+/// use juniper::GraphQLObject;
+/// use juniper_relay_helpers::{FnCursorProvider, OffsetCursor, RelayConnection};
+///
+/// #[derive(Clone, GraphQLObject, RelayConnection)]
+/// #[relay(cursor = OffsetCursor)]
+/// pub struct Expedition {
+///     pub number: i32,
+/// }
+///
+/// let items = [Some(Expedition { number: 33 }), Some(Expedition { number: 34 })];
+/// let conn = ExpeditionRelayConnection::new(
+///     Some(&items),
+///     None,
+///     FnCursorProvider::new(|_, idx, _: &Expedition| OffsetCursor::new(idx * 10)),
+///     None,
+/// );
+/// assert_eq!(conn.page_info.end_cursor, Some(OffsetCursor::new(10)));
+/// ```
+///
+/// Page info is built from the cursors of the first and last items, following the same rules as the
+/// `KeyedCursorProvider`. Missing items get the cursor type's default.
+pub struct FnCursorProvider<ItemT, CursorT, F> {
+    cursor_fn: F,
+    _types: PhantomData<fn(&ItemT) -> CursorT>,
+}
 
-        let mut has_previous_page = false;
-        if let Some(pr) = &metadata.page_request
-            && pr.after.is_some()
-        {
-            has_previous_page = true;
+impl<ItemT, CursorT, F> FnCursorProvider<ItemT, CursorT, F>
+where
+    CursorT: Cursor,
+    F: Fn(&PaginationMetadata<CursorT>, i32, &ItemT) -> CursorT,
+{
+    pub fn new(cursor_fn: F) -> Self {
+        FnCursorProvider {
+            cursor_fn,
+            _types: PhantomData,
         }
+    }
+}
 
-        let has_next_page = if let Some(items) = items {
-            !items.is_empty()
-        } else {
-            false
-        };
+impl<ItemT, CursorT, F> CursorProvider<ItemT> for FnCursorProvider<ItemT, CursorT, F>
+where
+    CursorT: Cursor + Default,
+    F: Fn(&PaginationMetadata<CursorT>, i32, &ItemT) -> CursorT,
+{
+    type CursorType = CursorT;
 
-        PageInfoType::new(
-            has_previous_page,
-            has_next_page,
-            first_item_cursor,
-            last_item_cursor,
-        )
+    fn get_cursor_for_item(
+        &self,
+        metadata: &PaginationMetadata<CursorT>,
+        item_idx: i32,
+        item: Option<&ItemT>,
+    ) -> CursorT {
+        item.map(|i| (self.cursor_fn)(metadata, item_idx, i))
+            .unwrap_or_default()
+    }
+
+    fn get_page_info<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<CursorT>,
+        items: Option<&[Option<ItemT>]>,
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<CursorT>,
+    {
+        boundary_page_info(metadata, items, |idx, item: &Option<ItemT>| {
+            self.get_cursor_for_item(metadata, idx, item.as_ref())
+        })
+    }
+
+    fn get_page_info_for_cursors<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<CursorT>,
+        items: Option<&[Option<ItemT>]>,
+        cursors: &[CursorT],
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<CursorT>,
+    {
+        boundary_page_info(metadata, items, |idx, item: &Option<ItemT>| {
+            built_cursor(cursors, idx, || {
+                self.get_cursor_for_item(metadata, idx, item.as_ref())
+            })
+        })
+    }
+}
+
+// ------------- Provider selection -------------
+
+/// Which built-in cursor provider matches an incoming cursor, as returned by `select_provider`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ProviderKind {
+    /// Use the `OffsetCursorProvider`.
+    Offset,
+
+    /// Use the `KeyedCursorProvider`.
+    Keyed,
+}
+
+/// Picks the provider for a resolver that accepts either offset or keyed cursors, based on the
+/// encoded `after` cursor.
+///
+/// A `StringCursor` selects `ProviderKind::Keyed`. Anything else, including no cursor at all (the
+/// first page), selects `ProviderKind::Offset`. Cursors that can't be decoded also select
+/// `ProviderKind::Offset`, so the error surfaces when the resolver decodes the cursor properly.
+///
+/// ```
+/// use juniper_relay_helpers::{select_provider, Cursor, ProviderKind, StringCursor};
+///
+/// let after = StringCursor::new("item-42".to_string()).to_encoded_string();
+/// match select_provider(Some(&after)) {
+///     ProviderKind::Offset => { /* page with the OffsetCursorProvider */ }
+///     ProviderKind::Keyed => { /* page with the KeyedCursorProvider */ }
+/// }
+/// ```
+pub fn select_provider(encoded_after: Option<&str>) -> ProviderKind {
+    match encoded_after.map(peek_cursor_kind) {
+        Some(Ok(CursorKind::String)) => ProviderKind::Keyed,
+        _ => ProviderKind::Offset,
     }
 }
 
 #[cfg(test)]
 mod tests {
+    mod select_provider {
+        use crate::{
+            Cursor, CursorKind, OffsetCursor, ProviderKind, StringCursor, peek_cursor_kind,
+            select_provider,
+        };
+
+        #[test]
+        fn test_selects_by_cursor_type() {
+            let offset = OffsetCursor::new(10).to_encoded_string();
+            let string = StringCursor::new("Esquie".to_string()).to_encoded_string();
+
+            assert_eq!(peek_cursor_kind(&offset), Ok(CursorKind::Offset));
+            assert_eq!(peek_cursor_kind(&string), Ok(CursorKind::String));
+
+            assert_eq!(select_provider(Some(&offset)), ProviderKind::Offset);
+            assert_eq!(select_provider(Some(&string)), ProviderKind::Keyed);
+            assert_eq!(select_provider(None), ProviderKind::Offset);
+            assert_eq!(select_provider(Some("not base64!")), ProviderKind::Offset);
+        }
+    }
+
     mod offset_cursor_provider {
         use crate::{
-            CursorProvider, OffsetCursor, OffsetCursorProvider, PageRequest, PaginationMetadata,
+            BidirectionalCursorProvider, CursorProvider, OffsetCursor, OffsetCursorProvider,
+            PageInfoParts, PageRequest, PaginationMetadata, RelayConnection,
         };
         use juniper::GraphQLObject;
-        use juniper_relay_helpers_codegen::RelayConnection;
 
         #[derive(Debug, Clone, GraphQLObject, RelayConnection)]
         #[relay(cursor = OffsetCursor)]
@@ -238,6 +1090,111 @@ mod tests {
             ]
         }
 
+        /// The provider is a unit struct, so it can be used as a value, literal or pattern.
+        #[test]
+        fn test_unit_struct_value() {
+            let conn = LocationRelayConnection::new(
+                Some(&data()),
+                Some(2),
+                OffsetCursorProvider,
+                Some(PageRequest::new(Some(2), None, None)),
+            );
+            assert_eq!(conn.page_info.end_cursor, Some(OffsetCursor::new(1)));
+            assert!(!conn.page_info.has_next_page);
+
+            let OffsetCursorProvider = OffsetCursorProvider {};
+        }
+
+        /// Reusing the edge cursors must give the same PageInfo as building it from scratch.
+        #[test]
+        fn test_page_info_for_cursors_matches() {
+            let p = OffsetCursorProvider::new();
+            let items = data();
+            let empty: Vec<Option<Location>> = vec![];
+
+            for (total_count, page_request) in [
+                (Some(10), None),
+                (Some(10), Some(PageRequest::new(Some(2), None, None))),
+                (
+                    Some(10),
+                    Some(PageRequest::new(Some(2), Some(OffsetCursor::new(3)), None)),
+                ),
+                (None, Some(PageRequest::new(Some(2), None, None))),
+                (Some(10), Some(PageRequest::new_backward(Some(2), None))),
+                (Some(0), Some(PageRequest::new(Some(2), None, None))),
+            ] {
+                let metadata = PaginationMetadata {
+                    total_count,
+                    page_request,
+                    more_available: None,
+                };
+                for items in [&items, &empty] {
+                    let cursors = p.get_cursors_for_items(&metadata, items);
+                    let expected: PageInfoParts<OffsetCursor> =
+                        p.get_page_info(&metadata, Some(items));
+                    let reused: PageInfoParts<OffsetCursor> =
+                        p.get_page_info_for_cursors(&metadata, Some(items), &cursors);
+                    assert_eq!(reused, expected, "{metadata:?}");
+                }
+            }
+        }
+
+        /// A query that legitimately matched nothing.
+        #[test]
+        fn test_page_info_empty_result() {
+            let p = OffsetCursorProvider::new();
+            let empty: Vec<Option<Location>> = vec![];
+            let page_info = |page_request| {
+                p.get_page_info::<LocationRelayConnectionPageInfo>(
+                    &PaginationMetadata {
+                        total_count: Some(0),
+                        page_request: Some(page_request),
+                        more_available: None,
+                    },
+                    Some(empty.as_slice()),
+                )
+            };
+
+            for pr in [
+                PageRequest::new(Some(10), None, None),
+                PageRequest::new(Some(10), Some(OffsetCursor::new(5)), None),
+                PageRequest::new_backward(Some(10), None),
+            ] {
+                let pi = page_info(pr);
+                assert!(!pi.has_previous_page);
+                assert!(!pi.has_next_page);
+                assert_eq!(pi.start_cursor, None);
+                assert_eq!(pi.end_cursor, None);
+            }
+        }
+
+        /// A total of 0 that came with items is stale, so the page info follows the items and agrees
+        /// with the edge cursors.
+        #[test]
+        fn test_page_info_zero_total_with_items() {
+            let conn = LocationRelayConnection::new(
+                Some(&data()),
+                Some(0),
+                OffsetCursorProvider::new(),
+                Some(PageRequest::new(Some(2), None, None)),
+            );
+
+            let edge_cursors: Vec<Option<OffsetCursor>> = conn
+                .edges
+                .iter()
+                .flatten()
+                .flatten()
+                .map(|edge| edge.cursor.clone())
+                .collect();
+            assert_eq!(
+                edge_cursors,
+                vec![Some(OffsetCursor::new(0)), Some(OffsetCursor::new(1))]
+            );
+            assert_eq!(conn.page_info.start_cursor, Some(OffsetCursor::new(0)));
+            assert_eq!(conn.page_info.end_cursor, Some(OffsetCursor::new(1)));
+            assert!(!conn.page_info.has_previous_page);
+        }
+
         /// Mimics a "complete" request - no `first` and no `after` with the total result set returned
         /// as part of the payload.
         #[test]
@@ -247,6 +1204,7 @@ mod tests {
                 &PaginationMetadata {
                     total_count: Some(2),
                     page_request: None,
+                    more_available: None,
                 },
                 Some(data().as_slice()),
             );
@@ -267,6 +1225,7 @@ mod tests {
                 &PaginationMetadata {
                     total_count: Some(27),
                     page_request: None,
+                    more_available: None,
                 },
                 Some(data().as_slice()),
             );
@@ -288,7 +1247,9 @@ mod tests {
                         first: Some(10),
                         after: None,
                         before: None,
+                        last: None,
                     }),
+                    more_available: None,
                 },
                 Some(data().as_slice()),
             );
@@ -329,7 +1290,9 @@ mod tests {
                         first: Some(5),
                         after: None,
                         before: None,
+                        last: None,
                     }),
+                    more_available: None,
                 },
                 Some(&data),
             );
@@ -345,7 +1308,9 @@ mod tests {
                         first: Some(5),
                         after: pi1.end_cursor.clone(),
                         before: None,
+                        last: None,
                     }),
+                    more_available: None,
                 },
                 Some(&data),
             );
@@ -354,21 +1319,326 @@ mod tests {
             assert_eq!(pi2.start_cursor, Some(OffsetCursor::new(5)));
             assert_eq!(pi2.end_cursor, Some(OffsetCursor::new(9)));
 
-            let pi3 = p.get_page_info::<LocationRelayConnectionPageInfo>(
+            let pi3 = p.get_page_info::<LocationRelayConnectionPageInfo>(
+                &PaginationMetadata {
+                    total_count: Some(total_items),
+                    page_request: Some(PageRequest {
+                        first: Some(5),
+                        after: pi2.end_cursor.clone(),
+                        before: None,
+                        last: None,
+                    }),
+                    more_available: None,
+                },
+                Some(&[data[0].clone(), data[1].clone(), data[2].clone()]),
+            );
+            assert!(pi3.has_previous_page);
+            assert!(!pi3.has_next_page);
+            assert_eq!(pi3.start_cursor, Some(OffsetCursor::new(10)));
+            assert_eq!(pi3.end_cursor, Some(OffsetCursor::new(12)));
+        }
+
+        #[test]
+        fn test_page_info_after_first_item() {
+            let p = OffsetCursorProvider::new();
+            let data = vec![
+                Some(Location {
+                    name: "Spring Meadows".to_owned(),
+                }),
+                Some(Location {
+                    name: "Flying Waters".to_owned(),
+                }),
+            ];
+
+            let pi = p.get_page_info::<LocationRelayConnectionPageInfo>(
+                &PaginationMetadata {
+                    total_count: Some(5),
+                    page_request: Some(PageRequest::new(Some(2), Some(OffsetCursor::new(0)), None)),
+                    more_available: None,
+                },
+                Some(&data),
+            );
+            assert!(pi.has_previous_page);
+            assert!(pi.has_next_page);
+            assert_eq!(pi.start_cursor, Some(OffsetCursor::new(1)));
+            assert_eq!(pi.end_cursor, Some(OffsetCursor::new(2)));
+        }
+
+        #[test]
+        fn test_page_info_request_first_wins_over_cursor_first() {
+            let p = OffsetCursorProvider::new();
+            let data: Vec<Option<Location>> = (0..5)
+                .map(|i| {
+                    Some(Location {
+                        name: format!("Location {i}"),
+                    })
+                })
+                .collect();
+            let page_info = |cursor_first: Option<i32>, request_first: Option<i32>| {
+                p.get_page_info::<LocationRelayConnectionPageInfo>(
+                    &PaginationMetadata {
+                        total_count: Some(20),
+                        page_request: Some(PageRequest::new(
+                            request_first,
+                            Some(OffsetCursor::new(4).with_first(cursor_first)),
+                            None,
+                        )),
+                        more_available: None,
+                    },
+                    Some(&data),
+                )
+            };
+
+            // With the cursor's 10 there'd be more to come; the request's 25 reaches past the end.
+            assert!(!page_info(Some(10), Some(25)).has_next_page);
+            // And the other way round.
+            assert!(page_info(Some(25), Some(10)).has_next_page);
+            // No `first` on the request means everything was asked for, whatever the cursor says.
+            assert!(!page_info(Some(10), None).has_next_page);
+        }
+
+        #[test]
+        fn test_page_info_with_max_first() {
+            let data: Vec<Option<Location>> = (0..10)
+                .map(|i| {
+                    Some(Location {
+                        name: format!("Location {i}"),
+                    })
+                })
+                .collect();
+            fn page_info(
+                p: &impl CursorProvider<Location, CursorType = OffsetCursor>,
+                data: &[Option<Location>],
+                first: Option<i32>,
+            ) -> LocationRelayConnectionPageInfo {
+                p.get_page_info(
+                    &PaginationMetadata {
+                        total_count: Some(30),
+                        page_request: Some(PageRequest::new(first, None, None)),
+                        more_available: None,
+                    },
+                    Some(data),
+                )
+            }
+
+            // Without a cap, asking for everything means nothing follows.
+            let uncapped = OffsetCursorProvider::new();
+            assert!(!page_info(&uncapped, &data, Some(1_000_000)).has_next_page);
+            assert!(!page_info(&uncapped, &data, None).has_next_page);
+
+            // With one, the huge request is treated as a request for 10.
+            let capped = OffsetCursorProvider::new()
+                .with_max_first(10)
+                .with_page_size_in_cursors();
+            let clamped = page_info(&capped, &data, Some(1_000_000));
+            assert!(clamped.has_next_page);
+            assert_eq!(clamped.end_cursor.unwrap().first, Some(10));
+            assert!(page_info(&capped, &data, None).has_next_page);
+
+            // A request under the cap is left alone.
+            let under = page_info(&capped, &data, Some(5));
+            assert!(under.has_next_page);
+            assert_eq!(under.end_cursor.unwrap().first, Some(5));
+        }
+
+        /// Paging backwards from the middle of the set - cursors come from the tail of the window.
+        #[test]
+        fn test_reverse_page_info_from_before() {
+            let p = OffsetCursorProvider::new();
+            let pi = p.reverse_page_info::<LocationRelayConnectionPageInfo>(
+                &PaginationMetadata {
+                    total_count: Some(10),
+                    page_request: Some(PageRequest::new_backward(
+                        Some(2),
+                        Some(OffsetCursor::new(8)),
+                    )),
+                    more_available: None,
+                },
+                Some(data().as_slice()),
+            );
+
+            assert!(pi.has_previous_page);
+            assert!(pi.has_next_page);
+            assert_eq!(pi.start_cursor, Some(OffsetCursor::new(6)));
+            assert_eq!(pi.end_cursor, Some(OffsetCursor::new(7)));
+        }
+
+        /// A `last` larger than the items before the cursor clamps to the start of the set.
+        #[test]
+        fn test_reverse_page_info_last_exceeds_remaining() {
+            let p = OffsetCursorProvider::new();
+            let metadata = PaginationMetadata {
+                total_count: Some(10),
+                page_request: Some(PageRequest::new_backward(
+                    Some(5),
+                    Some(OffsetCursor::new(2)),
+                )),
+                more_available: None,
+            };
+            let pi = p.reverse_page_info::<LocationRelayConnectionPageInfo>(
+                &metadata,
+                Some(data().as_slice()),
+            );
+
+            assert!(!pi.has_previous_page);
+            assert!(pi.has_next_page);
+            assert_eq!(pi.start_cursor, Some(OffsetCursor::new(0)));
+            assert_eq!(pi.end_cursor, Some(OffsetCursor::new(1)));
+
+            // Item cursors and the regular page info agree with the reverse page info:
+            assert_eq!(
+                p.get_cursor_for_item(&metadata, 1, data()[1].as_ref()),
+                OffsetCursor::new(1)
+            );
+            let forward_pi = p.get_page_info::<LocationRelayConnectionPageInfo>(
+                &metadata,
+                Some(data().as_slice()),
+            );
+            assert!(!forward_pi.has_previous_page);
+            assert_eq!(forward_pi.start_cursor, Some(OffsetCursor::new(0)));
+        }
+
+        /// `last` without `before` takes the tail of the entire set.
+        #[test]
+        fn test_reverse_page_info_last_without_before() {
+            let p = OffsetCursorProvider::new();
+            let pi = p.get_page_info::<LocationRelayConnectionPageInfo>(
+                &PaginationMetadata {
+                    total_count: Some(10),
+                    page_request: Some(PageRequest::new_backward(Some(2), None)),
+                    more_available: None,
+                },
+                Some(data().as_slice()),
+            );
+
+            assert!(pi.has_previous_page);
+            assert!(!pi.has_next_page);
+            assert_eq!(pi.start_cursor, Some(OffsetCursor::new(8)));
+            assert_eq!(pi.end_cursor, Some(OffsetCursor::new(9)));
+        }
+
+        /// The resolver overfetched and knows there's nothing more, even though the total says otherwise.
+        #[test]
+        fn test_page_info_more_available_overrides_total() {
+            let p = OffsetCursorProvider::new();
+            let request = PageRequest {
+                first: Some(2),
+                after: None,
+                before: None,
+                last: None,
+            };
+
+            let pi1 = p.get_page_info::<LocationRelayConnectionPageInfo>(
+                &PaginationMetadata {
+                    total_count: Some(27),
+                    page_request: Some(request.clone()),
+                    more_available: Some(false),
+                },
+                Some(data().as_slice()),
+            );
+            assert!(!pi1.has_next_page);
+
+            let pi2 = p.get_page_info::<LocationRelayConnectionPageInfo>(
                 &PaginationMetadata {
-                    total_count: Some(total_items),
-                    page_request: Some(PageRequest {
-                        first: Some(5),
-                        after: pi2.end_cursor.clone(),
-                        before: None,
-                    }),
+                    total_count: None,
+                    page_request: Some(request),
+                    more_available: Some(true),
                 },
-                Some(&[data[0].clone(), data[1].clone(), data[2].clone()]),
+                Some(data().as_slice()),
             );
-            assert!(pi3.has_previous_page);
-            assert!(!pi3.has_next_page);
-            assert_eq!(pi3.start_cursor, Some(OffsetCursor::new(10)));
-            assert_eq!(pi3.end_cursor, Some(OffsetCursor::new(12)));
+            assert!(pi2.has_next_page);
+            assert_eq!(pi2.start_cursor, Some(OffsetCursor::new(0)));
+            assert_eq!(pi2.end_cursor, Some(OffsetCursor::new(1)));
+        }
+
+        /// A `-1` total is the "unknown" sentinel, so `has_next_page` falls back to whether the page
+        /// was full, and the total is left out of cursors and the connection count.
+        #[test]
+        fn test_page_info_unknown_total_sentinel() {
+            let p = OffsetCursorProvider::new().with_total_in_cursors();
+            let metadata = |first| PaginationMetadata {
+                total_count: Some(-1),
+                page_request: Some(PageRequest::new(
+                    Some(first),
+                    Some(OffsetCursor::new(4)),
+                    None,
+                )),
+                more_available: None,
+            };
+
+            let full =
+                p.get_page_info::<LocationRelayConnectionPageInfo>(&metadata(2), Some(&data()));
+            assert!(full.has_next_page);
+            assert!(full.has_previous_page);
+            assert_eq!(full.start_cursor, Some(OffsetCursor::new(5)));
+            assert_eq!(full.end_cursor, Some(OffsetCursor::new(6)));
+
+            let short =
+                p.get_page_info::<LocationRelayConnectionPageInfo>(&metadata(5), Some(&data()));
+            assert!(!short.has_next_page);
+
+            let conn = LocationRelayConnection::from_metadata(Some(&data()), metadata(2), p);
+            assert_eq!(conn.count, None);
+        }
+
+        #[test]
+        fn test_cursors_with_total() {
+            let p = OffsetCursorProvider::new().with_total_in_cursors();
+            let metadata = PaginationMetadata {
+                total_count: Some(27),
+                page_request: Some(PageRequest::new(Some(2), None, None)),
+                more_available: None,
+            };
+
+            let cursor = p.get_cursor_for_item(&metadata, 1, data()[1].as_ref());
+            assert_eq!(cursor, OffsetCursor::new(1).with_total(Some(27)));
+
+            let pi = p.get_page_info::<LocationRelayConnectionPageInfo>(&metadata, Some(&data()));
+            assert_eq!(
+                pi.start_cursor,
+                Some(OffsetCursor::new(0).with_total(Some(27)))
+            );
+            assert_eq!(
+                pi.end_cursor,
+                Some(OffsetCursor::new(1).with_total(Some(27)))
+            );
+        }
+
+        #[test]
+        fn test_cursors_with_page_size() {
+            let p = OffsetCursorProvider::new().with_page_size_in_cursors();
+            let metadata = PaginationMetadata {
+                total_count: Some(27),
+                page_request: Some(PageRequest::new(Some(2), None, None)),
+                more_available: None,
+            };
+
+            let cursor = p.get_cursor_for_item(&metadata, 1, data()[1].as_ref());
+            assert_eq!(cursor, OffsetCursor::new(1).with_first(Some(2)));
+        }
+
+        /// Paginating right at the end of the i32 range saturates rather than panicking.
+        #[test]
+        fn test_page_info_near_i32_max() {
+            let p = OffsetCursorProvider::new();
+            let metadata = PaginationMetadata {
+                total_count: Some(i32::MAX),
+                page_request: Some(PageRequest::new(
+                    Some(10),
+                    Some(OffsetCursor::new(i32::MAX - 1)),
+                    None,
+                )),
+                more_available: None,
+            };
+
+            let cursor = p.get_cursor_for_item(&metadata, 1, data()[1].as_ref());
+            assert_eq!(cursor, OffsetCursor::new(i32::MAX));
+
+            let pi = p.get_page_info::<LocationRelayConnectionPageInfo>(&metadata, Some(&data()));
+            assert!(pi.has_previous_page);
+            assert!(!pi.has_next_page);
+            assert_eq!(pi.start_cursor, Some(OffsetCursor::new(i32::MAX)));
+            assert_eq!(pi.end_cursor, Some(OffsetCursor::new(i32::MAX)));
         }
 
         #[test]
@@ -384,7 +1654,9 @@ mod tests {
                         first: Some(5),
                         after: None,
                         before: None,
+                        last: None,
                     }),
+                    more_available: None,
                 },
                 Some(&data),
             );
@@ -393,12 +1665,61 @@ mod tests {
             assert_eq!(pi1.start_cursor, None);
             assert_eq!(pi1.end_cursor, None);
         }
+
+        /// Regression test: an empty page past the end of the set, with a request present, must
+        /// not underflow computing the last index.
+        #[test]
+        fn test_page_info_empty_list_with_request() {
+            let p = OffsetCursorProvider::new();
+            let data: Vec<Option<Location>> = vec![];
+
+            let forward = p.get_page_info::<LocationRelayConnectionPageInfo>(
+                &PaginationMetadata {
+                    total_count: Some(27),
+                    page_request: Some(PageRequest::new(
+                        Some(5),
+                        Some(OffsetCursor::new(30)),
+                        None,
+                    )),
+                    more_available: None,
+                },
+                Some(&data),
+            );
+            assert_eq!(forward.start_cursor, None);
+            assert_eq!(forward.end_cursor, None);
+
+            let backward = p.get_page_info::<LocationRelayConnectionPageInfo>(
+                &PaginationMetadata {
+                    total_count: Some(27),
+                    page_request: Some(PageRequest::new_backward(
+                        Some(5),
+                        Some(OffsetCursor::new(0)),
+                    )),
+                    more_available: None,
+                },
+                Some(&data),
+            );
+            assert_eq!(backward.start_cursor, None);
+            assert_eq!(backward.end_cursor, None);
+
+            let no_items = p.get_page_info::<LocationRelayConnectionPageInfo>(
+                &PaginationMetadata {
+                    total_count: Some(27),
+                    page_request: Some(PageRequest::new(Some(5), None, None)),
+                    more_available: None,
+                },
+                None::<&[Option<Location>]>,
+            );
+            assert_eq!(no_items.start_cursor, None);
+            assert_eq!(no_items.end_cursor, None);
+        }
     }
 
     mod keyed_cursor_provider {
         use crate::{
-            Cursor, CursorProvider, KeyedCursorProvider, PageRequest, PaginationMetadata,
-            RelayConnection, StringCursor,
+            Boundaries, Cursor, CursorError, CursorProvider, KeyedCursorProvider,
+            OverfetchPaginator, PageInfoParts, PageRequest, PaginationMetadata, RelayConnection,
+            StringCursor,
         };
         use juniper::GraphQLObject;
         use juniper_relay_helpers::cursor_provider::CursorByKey;
@@ -413,6 +1734,38 @@ mod tests {
             }
         }
 
+        #[test]
+        fn test_page_info_for_cursors_matches() {
+            let p = KeyedCursorProvider;
+            let items: Vec<Option<NoSQLItem>> = ["id-1", "id-2"]
+                .into_iter()
+                .map(|id| Some(NoSQLItem { id: id.to_string() }))
+                .chain([None])
+                .collect();
+
+            for page_request in [
+                None,
+                Some(PageRequest::new(Some(3), None, None)),
+                Some(PageRequest::new(
+                    Some(3),
+                    Some(StringCursor::new("id-0".to_string())),
+                    None,
+                )),
+            ] {
+                let metadata = PaginationMetadata {
+                    total_count: None,
+                    page_request,
+                    more_available: None,
+                };
+                let cursors = p.get_cursors_for_items(&metadata, &items);
+                let expected: PageInfoParts<StringCursor> =
+                    p.get_page_info(&metadata, Some(&items));
+                let reused: PageInfoParts<StringCursor> =
+                    p.get_page_info_for_cursors(&metadata, Some(&items), &cursors);
+                assert_eq!(reused, expected);
+            }
+        }
+
         #[test]
         fn test_item_cursors() {
             let p = KeyedCursorProvider;
@@ -435,6 +1788,7 @@ mod tests {
                     Some(StringCursor::new("".to_string())),
                     None,
                 )),
+                more_available: None,
             };
 
             let i1_cursor = p.get_cursor_for_item(&meta, 0, Some(&items[0]));
@@ -468,7 +1822,9 @@ mod tests {
                     first: Some(10),
                     after: None,
                     before: None,
+                    last: None,
                 }),
+                more_available: None,
             };
 
             let page_info =
@@ -485,6 +1841,52 @@ mod tests {
             );
         }
 
+        /// The slice is in display order, but the page boundaries are the items the store paged by.
+        #[test]
+        fn test_page_info_with_boundaries() {
+            let p = KeyedCursorProvider;
+            let items: Vec<Option<NoSQLItem>> = ["id-2", "id-1", "id-3"]
+                .into_iter()
+                .map(|id| Some(NoSQLItem { id: id.to_string() }))
+                .collect();
+            let meta = PaginationMetadata {
+                total_count: None,
+                page_request: Some(PageRequest::new(Some(3), None, None)),
+                more_available: None,
+            };
+
+            let page_info = p.get_page_info_with_boundaries::<NoSQLItemRelayConnectionPageInfo>(
+                &meta,
+                Some(&items),
+                Boundaries {
+                    first: items[1].as_ref(),
+                    last: None,
+                },
+            );
+            assert!(page_info.has_next_page);
+            assert_eq!(
+                page_info.start_cursor,
+                Some(StringCursor::new("id-1".to_string()))
+            );
+            assert_eq!(
+                page_info.end_cursor,
+                Some(StringCursor::new("id-3".to_string()))
+            );
+
+            let page_info = p.get_page_info_with_boundaries::<NoSQLItemRelayConnectionPageInfo>(
+                &meta,
+                Some(&items),
+                Boundaries {
+                    first: items[1].as_ref(),
+                    last: items[0].as_ref(),
+                },
+            );
+            assert_eq!(
+                page_info.end_cursor,
+                Some(StringCursor::new("id-2".to_string()))
+            );
+        }
+
         #[test]
         fn test_page_info_first_page_of_many() {
             let p = KeyedCursorProvider {};
@@ -506,7 +1908,9 @@ mod tests {
                     first: Some(10),
                     after: None,
                     before: None,
+                    last: None,
                 }),
+                more_available: None,
             };
 
             let page_info =
@@ -523,6 +1927,63 @@ mod tests {
             );
         }
 
+        #[test]
+        fn test_try_new_missing_key() {
+            let items = vec![
+                Some(NoSQLItem {
+                    id: "id-1".to_string(),
+                }),
+                Some(NoSQLItem { id: "".to_string() }),
+            ];
+
+            let conn = NoSQLItemRelayConnection::try_new(
+                Some(&items[..1]),
+                None,
+                KeyedCursorProvider,
+                Some(PageRequest::new(Some(2), None, None)),
+            )
+            .unwrap();
+            assert_eq!(
+                conn.page_info.end_cursor,
+                Some(StringCursor::new("id-1".to_string()))
+            );
+
+            let err = NoSQLItemRelayConnection::try_new(
+                Some(&items),
+                None,
+                KeyedCursorProvider,
+                Some(PageRequest::new(Some(2), None, None)),
+            )
+            .err();
+            assert_eq!(err, Some(CursorError::MissingKey));
+
+            let err =
+                NoSQLItemRelayConnection::try_new(Some(&[None]), None, KeyedCursorProvider, None)
+                    .err();
+            assert_eq!(err, Some(CursorError::MissingKey));
+        }
+
+        #[test]
+        fn test_validate_bounds() {
+            let items: Vec<Option<NoSQLItem>> = ["id-1", "id-2", "id-3"]
+                .into_iter()
+                .map(|id| Some(NoSQLItem { id: id.to_string() }))
+                .collect();
+            let cursor = |key: &str| StringCursor::new(key.to_string());
+            let validate = |after: &str, before: &str| {
+                KeyedCursorProvider::validate_bounds(&items, &cursor(after), &cursor(before))
+            };
+
+            assert_eq!(validate("id-1", "id-3"), Ok(()));
+            assert_eq!(validate("id-2", "id-3"), Ok(()));
+            assert_eq!(validate("id-3", "id-1"), Err(CursorError::InvertedBounds));
+            assert_eq!(validate("id-2", "id-2"), Err(CursorError::InvertedBounds));
+
+            // Outside the window, so nothing to compare against.
+            assert_eq!(validate("id-0", "id-1"), Ok(()));
+            assert_eq!(validate("id-3", "id-9"), Ok(()));
+        }
+
         #[test]
         fn test_page_info_last_page() {
             let p = KeyedCursorProvider {};
@@ -534,7 +1995,9 @@ mod tests {
                     first: Some(10), // More than items returned
                     after: Some(StringCursor::new("c3RyaW5nOmlkLTA=".to_string())), // id-0 - we're paginating.
                     before: None,
+                    last: None,
                 }),
+                more_available: None,
             };
 
             let page_info =
@@ -544,5 +2007,313 @@ mod tests {
             assert_eq!(page_info.start_cursor, None);
             assert_eq!(page_info.end_cursor, None);
         }
+
+        #[test]
+        fn test_overfetch_full_last_page() {
+            let paginator = OverfetchPaginator::new(PageRequest::new(
+                Some(2),
+                Some(StringCursor::new("id-2".to_string())),
+                None,
+            ));
+            // Fetched first + 1, but only the two items on the last page came back.
+            let items: Vec<NoSQLItem> = ["id-3", "id-4"]
+                .into_iter()
+                .map(|id| NoSQLItem { id: id.to_string() })
+                .collect();
+            let page = paginator.paginate(items, None);
+
+            let conn = NoSQLItemRelayConnection::from_metadata(
+                Some(&page.items),
+                page.metadata,
+                KeyedCursorProvider,
+            );
+            assert!(conn.page_info.has_previous_page);
+            assert!(!conn.page_info.has_next_page);
+            assert_eq!(
+                conn.page_info.end_cursor,
+                Some(StringCursor::new("id-4".to_string()))
+            );
+        }
+
+        #[test]
+        fn test_overfetch_more_pages() {
+            let paginator = OverfetchPaginator::new(PageRequest::new(Some(2), None, None));
+            let items: Vec<NoSQLItem> = ["id-1", "id-2", "id-3"]
+                .into_iter()
+                .map(|id| NoSQLItem { id: id.to_string() })
+                .collect();
+            let page = paginator.paginate(items, None);
+
+            let conn = NoSQLItemRelayConnection::from_metadata(
+                Some(&page.items),
+                page.metadata,
+                KeyedCursorProvider,
+            );
+            assert!(!conn.page_info.has_previous_page);
+            assert!(conn.page_info.has_next_page);
+            assert_eq!(conn.edges.map(|edges| edges.len()), Some(2));
+            assert_eq!(
+                conn.page_info.end_cursor,
+                Some(StringCursor::new("id-2".to_string()))
+            );
+        }
+
+        /// Without the overfetch, the fallback claims a next page after a full last page.
+        #[test]
+        fn test_no_overfetch_full_last_page() {
+            let items: Vec<Option<NoSQLItem>> = ["id-3", "id-4"]
+                .into_iter()
+                .map(|id| Some(NoSQLItem { id: id.to_string() }))
+                .collect();
+
+            let conn = NoSQLItemRelayConnection::new(
+                Some(&items),
+                None,
+                KeyedCursorProvider,
+                Some(PageRequest::new(Some(2), None, None)),
+            );
+            assert!(conn.page_info.has_next_page);
+        }
+
+        #[test]
+        fn test_page_info_more_available_false_on_full_page() {
+            let p = KeyedCursorProvider {};
+            let items = vec![
+                Some(NoSQLItem {
+                    id: "id-1".to_string(),
+                }),
+                Some(NoSQLItem {
+                    id: "id-2".to_string(),
+                }),
+            ];
+
+            let meta = PaginationMetadata {
+                total_count: None,
+                page_request: Some(PageRequest {
+                    first: Some(2),
+                    after: None,
+                    before: None,
+                    last: None,
+                }),
+                more_available: Some(false),
+            };
+
+            let page_info =
+                p.get_page_info::<NoSQLItemRelayConnectionPageInfo>(&meta, Some(&items));
+            assert!(!page_info.has_next_page);
+            assert_eq!(
+                page_info.end_cursor,
+                Some(StringCursor::new("id-2".to_string()))
+            );
+        }
+    }
+
+    mod keyed_cursor_provider_with_ctx {
+        use crate::{
+            CursorByKeyWithCtx, CursorError, KeyedCursorProviderWithCtx, PageRequest,
+            RelayConnection, StringCursor,
+        };
+        use juniper::GraphQLObject;
+
+        pub struct Context {
+            pub sort_positions: Vec<(String, i32)>,
+        }
+        impl juniper::Context for Context {}
+
+        #[derive(Debug, Clone, GraphQLObject, RelayConnection, Eq, PartialEq)]
+        #[relay(context = Context)]
+        pub struct Favourite {
+            id: String,
+        }
+        impl CursorByKeyWithCtx<Context> for Favourite {
+            fn cursor_key(&self, ctx: &Context) -> String {
+                ctx.sort_positions
+                    .iter()
+                    .find(|(id, _)| *id == self.id)
+                    .map(|(_, position)| format!("{position}#{}", self.id))
+                    .unwrap_or_default()
+            }
+        }
+
+        fn favourite(id: &str) -> Option<Favourite> {
+            Some(Favourite { id: id.to_string() })
+        }
+
+        #[test]
+        fn test_cursors_use_context() {
+            let ctx = Context {
+                sort_positions: vec![("lumiere".to_string(), 3), ("old-lumiere".to_string(), 7)],
+            };
+            let items = vec![favourite("lumiere"), favourite("old-lumiere")];
+
+            let conn = FavouriteRelayConnection::new(
+                Some(&items),
+                None,
+                KeyedCursorProviderWithCtx::new(&ctx),
+                Some(PageRequest::new(
+                    Some(2),
+                    Some(StringCursor::new("1#monolith".to_string())),
+                    None,
+                )),
+            );
+
+            let edges = conn.edges.unwrap();
+            assert_eq!(
+                edges[0].as_ref().unwrap().cursor,
+                Some(StringCursor::new("3#lumiere".to_string()))
+            );
+            assert_eq!(
+                conn.page_info.end_cursor,
+                Some(StringCursor::new("7#old-lumiere".to_string()))
+            );
+            assert!(conn.page_info.has_previous_page);
+            assert!(conn.page_info.has_next_page);
+
+            // Same items, different user: different cursors.
+            let other_ctx = Context {
+                sort_positions: vec![("lumiere".to_string(), 1), ("old-lumiere".to_string(), 2)],
+            };
+            let conn = FavouriteRelayConnection::new(
+                Some(&items),
+                None,
+                KeyedCursorProviderWithCtx::new(&other_ctx),
+                None,
+            );
+            assert_eq!(
+                conn.page_info.start_cursor,
+                Some(StringCursor::new("1#lumiere".to_string()))
+            );
+        }
+
+        #[test]
+        fn test_missing_key_from_context() {
+            let ctx = Context {
+                sort_positions: vec![("lumiere".to_string(), 3)],
+            };
+            let items = vec![favourite("lumiere"), favourite("gestral-village")];
+
+            let conn = FavouriteRelayConnection::try_new(
+                Some(&items),
+                None,
+                KeyedCursorProviderWithCtx::new(&ctx),
+                None,
+            );
+            assert_eq!(conn.err(), Some(CursorError::MissingKey));
+        }
+    }
+
+    mod fn_cursor_provider {
+        use crate::{
+            CursorProvider, DynCursorProvider, FnCursorProvider, OffsetCursor, PageRequest,
+            PaginationMetadata, RelayConnection, StringCursor,
+        };
+        use juniper::GraphQLObject;
+        use std::cell::Cell;
+
+        #[derive(Debug, Clone, GraphQLObject, RelayConnection)]
+        pub struct Gestral {
+            pub name: String,
+        }
+
+        fn data() -> Vec<Option<Gestral>> {
+            ["Golgra", "Sakapatate", "Jujubree"]
+                .into_iter()
+                .map(|name| {
+                    Some(Gestral {
+                        name: name.to_string(),
+                    })
+                })
+                .collect()
+        }
+
+        #[test]
+        fn test_cursors_from_closure() {
+            let conn = GestralRelayConnection::new(
+                Some(&data()),
+                None,
+                FnCursorProvider::new(|_, idx, g: &Gestral| {
+                    StringCursor::new(format!("{idx}-{}", g.name))
+                }),
+                Some(PageRequest::new(
+                    Some(3),
+                    Some(StringCursor::new("x".to_string())),
+                    None,
+                )),
+            );
+
+            let cursors: Vec<String> = conn
+                .edges
+                .into_iter()
+                .flatten()
+                .flatten()
+                .filter_map(|e| e.cursor.map(|c| c.value))
+                .collect();
+            assert_eq!(cursors, vec!["0-Golgra", "1-Sakapatate", "2-Jujubree"]);
+            assert_eq!(
+                conn.page_info.start_cursor,
+                Some(StringCursor::new("0-Golgra".to_string()))
+            );
+            assert_eq!(
+                conn.page_info.end_cursor,
+                Some(StringCursor::new("2-Jujubree".to_string()))
+            );
+            assert!(conn.page_info.has_previous_page);
+            assert!(conn.page_info.has_next_page);
+        }
+
+        #[test]
+        fn test_page_info_rules() {
+            let p = FnCursorProvider::new(|_, idx, _: &Gestral| OffsetCursor::new(idx));
+            let metadata = |more_available| PaginationMetadata {
+                total_count: None,
+                page_request: Some(PageRequest::new(Some(3), None, None)),
+                more_available,
+            };
+
+            let pi = p.get_page_info::<crate::page_info_factory::PageInfoParts<OffsetCursor>>(
+                &metadata(Some(false)),
+                Some(&data()),
+            );
+            assert!(!pi.has_prev_page);
+            assert!(!pi.has_next_page);
+            assert_eq!(pi.end_cursor, Some(OffsetCursor::new(2)));
+
+            let pi = p.get_page_info::<crate::page_info_factory::PageInfoParts<OffsetCursor>>(
+                &metadata(None),
+                Some(&[]),
+            );
+            assert!(!pi.has_next_page);
+            assert_eq!(pi.start_cursor, None);
+
+            assert_eq!(
+                p.get_cursor_for_item(&metadata(None), 4, None),
+                OffsetCursor::default()
+            );
+        }
+
+        #[test]
+        fn test_boundary_cursors_reused() {
+            let calls = Cell::new(0);
+            let provider = || {
+                FnCursorProvider::new(|_, idx, _: &Gestral| {
+                    calls.set(calls.get() + 1);
+                    StringCursor::new(idx.to_string())
+                })
+            };
+
+            let conn = GestralRelayConnection::new(Some(&data()), None, provider(), None);
+            assert_eq!(calls.get(), 3);
+            assert_eq!(
+                conn.page_info.end_cursor,
+                Some(StringCursor::new("2".to_string()))
+            );
+
+            // Also when the provider is picked at runtime.
+            calls.set(0);
+            let picked = provider();
+            let erased: &dyn DynCursorProvider<Gestral, StringCursor> = &picked;
+            GestralRelayConnection::new(Some(&data()), None, erased, None);
+            assert_eq!(calls.get(), 3);
+        }
     }
 }
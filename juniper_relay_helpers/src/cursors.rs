@@ -1,7 +1,19 @@
+mod composite_cursor;
 mod cursor;
 mod offset_cursor;
+mod offset_cursor_64;
 mod string_cursor;
+#[cfg(feature = "chrono")]
+mod timestamp_cursor;
+mod versioned_cursor;
+mod wrapped_cursor;
 
+pub use composite_cursor::*;
 pub use cursor::*;
 pub use offset_cursor::*;
+pub use offset_cursor_64::*;
 pub use string_cursor::*;
+#[cfg(feature = "chrono")]
+pub use timestamp_cursor::*;
+pub use versioned_cursor::*;
+pub(crate) use wrapped_cursor::*;
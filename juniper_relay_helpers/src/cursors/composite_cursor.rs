@@ -0,0 +1,180 @@
+use crate::{CURSOR_SEGMENT_DELIMITER, Cursor, CursorError};
+use juniper::GraphQLScalar;
+use std::fmt::{Display, Formatter};
+
+/// Cursor for keyset pagination over several columns, such as `(created_at, id)`.
+///
+/// Holds the column name/value pairs of the last row seen, in the order they're compared, so you can
+/// build a `WHERE (created_at, id) > (?, ?)` query from them. Each pair is encoded as its own
+/// `name=value` segment. Any `%`, `|` or `=` in the names and values is percent-escaped, so values
+/// containing the segment delimiter round-trip safely.
+///
+/// ```
+/// use juniper_relay_helpers::{Cursor, CompositeCursor};
+///
+/// let cursor = CompositeCursor::new(vec![
+///     ("created_at".to_string(), "2025-04-24T10:00:00Z".to_string()),
+///     ("id".to_string(), "42".to_string()),
+/// ]);
+///
+/// let decoded = CompositeCursor::from_encoded_string(&cursor.to_encoded_string()).unwrap();
+/// assert_eq!(decoded.get("created_at"), Some("2025-04-24T10:00:00Z"));
+/// assert_eq!(decoded, cursor);
+/// ```
+#[derive(Debug, GraphQLScalar, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[graphql(
+    name = "CompositeCursor",
+    to_output_with = Self::to_output,
+    from_input_with = Self::from_input
+)]
+pub struct CompositeCursor {
+    /// The column name/value pairs, in comparison order.
+    pub columns: Vec<(String, String)>,
+}
+
+impl CompositeCursor {
+    pub fn new(columns: Vec<(String, String)>) -> Self {
+        CompositeCursor { columns }
+    }
+
+    /// Returns the value for the given column, if it's in the cursor.
+    pub fn get(&self, column: &str) -> Option<&str> {
+        self.columns
+            .iter()
+            .find(|(name, _)| name == column)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '%' => escaped.push_str("%25"),
+            '|' => escaped.push_str("%7C"),
+            '=' => escaped.push_str("%3D"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(value: &str) -> Result<String, CursorError> {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(idx) = rest.find('%') {
+        unescaped.push_str(&rest[..idx]);
+        let c = match rest.get(idx + 1..idx + 3) {
+            Some("25") => '%',
+            Some("7C") => '|',
+            Some("3D") => '=',
+            _ => return Err(CursorError::InvalidCursor),
+        };
+        unescaped.push(c);
+        rest = &rest[idx + 3..];
+    }
+    unescaped.push_str(rest);
+    Ok(unescaped)
+}
+
+impl Cursor for CompositeCursor {
+    type CursorType = CompositeCursor;
+
+    fn to_raw_string(&self) -> String {
+        let mut raw = "composite".to_string();
+        for (name, value) in &self.columns {
+            raw.push_str(&format!(
+                "{}{}={}",
+                CURSOR_SEGMENT_DELIMITER,
+                escape(name),
+                escape(value)
+            ));
+        }
+        raw
+    }
+
+    fn new(_raw: &str, parts: Vec<&str>) -> Result<CompositeCursor, CursorError> {
+        if parts.first() != Some(&"composite") {
+            return Err(CursorError::InvalidCursor);
+        }
+
+        let columns = parts[1..]
+            .iter()
+            .map(|part| {
+                let (name, value) = part.split_once('=').ok_or(CursorError::InvalidCursor)?;
+                Ok((unescape(name)?, unescape(value)?))
+            })
+            .collect::<Result<Vec<_>, CursorError>>()?;
+
+        Ok(CompositeCursor { columns })
+    }
+}
+
+impl Display for CompositeCursor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_raw_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CursorError;
+    use crate::cursors::{CompositeCursor, Cursor};
+    use base64::prelude::*;
+
+    fn cursor() -> CompositeCursor {
+        CompositeCursor::new(vec![
+            ("created_at".to_string(), "2025-04-24".to_string()),
+            ("id".to_string(), "42".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_raw_string() {
+        assert_eq!(
+            cursor().to_string(),
+            "composite||created_at=2025-04-24||id=42"
+        );
+    }
+
+    #[test]
+    fn test_round_trip_keeps_order() {
+        let reversed = CompositeCursor::new(cursor().columns.into_iter().rev().collect());
+        let decoded = CompositeCursor::from_encoded_string(&reversed.to_encoded_string()).unwrap();
+        assert_eq!(decoded, reversed);
+        assert_eq!(decoded.columns[0].0, "id");
+    }
+
+    #[test]
+    fn test_round_trip_escapes_delimiter() {
+        let cursor = CompositeCursor::new(vec![
+            ("name".to_string(), "Gustave || Maelle".to_string()),
+            ("a=b".to_string(), "100%|".to_string()),
+            ("empty".to_string(), "".to_string()),
+        ]);
+        assert_eq!(
+            cursor.to_string(),
+            "composite||name=Gustave %7C%7C Maelle||a%3Db=100%25%7C||empty="
+        );
+
+        let decoded = CompositeCursor::from_encoded_string(&cursor.to_encoded_string()).unwrap();
+        assert_eq!(decoded, cursor);
+    }
+
+    #[test]
+    fn test_invalid_cursors() {
+        for raw in [
+            "offset||1",
+            "composite||no-equals",
+            "composite||id=%ZZ",
+            "composite||id=%2",
+        ] {
+            assert_eq!(
+                CompositeCursor::from_encoded_string(&BASE64_URL_SAFE.encode(raw)),
+                Err(CursorError::InvalidCursor),
+                "{raw}"
+            );
+        }
+    }
+}
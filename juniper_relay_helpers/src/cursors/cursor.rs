@@ -1,9 +1,97 @@
 use crate::CursorError;
 use base64::prelude::*;
 use juniper::{FromInputValue, ParseScalarResult, ParseScalarValue, ScalarToken, ScalarValue};
+use std::cell::RefCell;
+use std::cmp::Ordering;
 
 pub const CURSOR_SEGMENT_DELIMITER: &str = "||";
 
+/// Buffers larger than this aren't kept between cursors, so one huge cursor doesn't pin the memory.
+const RAW_BUFFER_RETAINED_CAPACITY: usize = 4096;
+
+thread_local! {
+    /// Scratch space for the raw string of a cursor while it's encoded, reused across cursors so
+    /// building a connection doesn't allocate for each one.
+    static RAW_BUFFER: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// Base64 encodes whatever `write_raw` writes, building the raw form in the thread's reused buffer
+/// and encoding it into a `String` allocated at its final size.
+pub(crate) fn encode_raw_with(write_raw: impl FnOnce(&mut String)) -> String {
+    RAW_BUFFER.with(|buffer| match buffer.try_borrow_mut() {
+        Ok(mut raw) => {
+            raw.clear();
+            write_raw(&mut raw);
+            let encoded = encode_raw_bytes(raw.as_bytes());
+            if raw.capacity() > RAW_BUFFER_RETAINED_CAPACITY {
+                *raw = String::new();
+            }
+            encoded
+        }
+        // A cursor that encodes another cursor within its own raw form is already using the buffer.
+        Err(_) => {
+            let mut raw = String::new();
+            write_raw(&mut raw);
+            encode_raw_bytes(raw.as_bytes())
+        }
+    })
+}
+
+fn encode_raw_bytes(raw: &[u8]) -> String {
+    let mut encoded = String::with_capacity(base64::encoded_len(raw.len(), true).unwrap_or(0));
+    BASE64_URL_SAFE.encode_string(raw, &mut encoded);
+    encoded
+}
+
+/// Joins the segments with `CURSOR_SEGMENT_DELIMITER` and base64 encodes them with the url safe
+/// alphabet, the same way the built-in cursors are encoded.
+///
+/// ```
+/// use juniper_relay_helpers::{encode_cursor_segments, Cursor, OffsetCursor};
+///
+/// assert_eq!(
+///     encode_cursor_segments(&["offset", "10"]),
+///     OffsetCursor::new(10).to_encoded_string()
+/// );
+/// ```
+pub fn encode_cursor_segments(parts: &[&str]) -> String {
+    encode_raw_with(|raw| {
+        for (idx, part) in parts.iter().enumerate() {
+            if idx > 0 {
+                raw.push_str(CURSOR_SEGMENT_DELIMITER);
+            }
+            raw.push_str(part);
+        }
+    })
+}
+
+/// The reverse of `encode_cursor_segments`, decoding the base64 and splitting it into segments.
+///
+/// This splits naively on every delimiter, so a segment that itself contained `||` comes back in
+/// pieces. Cursors that need such values whole read them from the raw string instead, as
+/// `StringCursor::new` does, so decode those with `Cursor::from_encoded_string`.
+pub fn decode_cursor_segments(encoded: &str) -> Result<Vec<String>, CursorError> {
+    let raw = decode_raw_string(&decode_raw_bytes(encoded)?)?;
+    Ok(split_segments(&raw)
+        .into_iter()
+        .map(str::to_string)
+        .collect())
+}
+
+/// Base64 decodes a cursor with the url safe alphabet, the reverse of `encode_raw_with`.
+fn decode_raw_bytes(encoded: &str) -> Result<Vec<u8>, CursorError> {
+    Ok(BASE64_URL_SAFE.decode(encoded)?)
+}
+
+fn decode_raw_string(bytes: &[u8]) -> Result<String, CursorError> {
+    Ok(String::from_utf8(bytes.to_vec())?)
+}
+
+/// Splits a raw cursor string on every `CURSOR_SEGMENT_DELIMITER`.
+fn split_segments(raw: &str) -> Vec<&str> {
+    raw.split(CURSOR_SEGMENT_DELIMITER).collect()
+}
+
 /// Cursor struct that builds into an opaque string.
 /// Cursors are present both in the edges and in the PageInfo within the Connection.
 ///
@@ -11,6 +99,7 @@ pub const CURSOR_SEGMENT_DELIMITER: &str = "||";
 /// You can also use the built-in Cursors:
 ///     - OffsetCursor
 ///     - StringCursor
+///     - CompositeCursor
 ///
 /// This trait implements the common methods needed to be considered a `GraphQlScalar`
 /// which means you can add the following to your struct and it will work
@@ -34,25 +123,47 @@ pub trait Cursor: Clone + FromInputValue {
     /// Serialize the cursor into a string ready to be base64 encoded.
     fn to_raw_string(&self) -> String;
 
+    /// Appends the raw string to `out`, which is how `to_encoded_string` builds it. By default this
+    /// pushes `to_raw_string`; cursors can override it to write themselves without allocating.
+    fn write_raw_string(&self, out: &mut String) {
+        out.push_str(&self.to_raw_string());
+    }
+
     /// Constructor that given the raw string, and a vector of parts (the colon separated segments)
     /// will return a Result of the CursorType. Return a CursorError if the decoding fails.
+    ///
+    /// The built-in cursors check the first segment is their type tag (`offset`, `string`, ...) and
+    /// return `CursorError::InvalidCursor` if it isn't, so one type is never decoded as another.
     fn new(raw: &str, parts: Vec<&str>) -> Result<Self::CursorType, CursorError>;
 
+    /// Builds the CursorType from the base64 decoded bytes of the cursor.
+    ///
+    /// By default the bytes must be UTF-8, and are split into segments and passed to `new`. Cursor
+    /// types carrying binary state (such as a serialized DynamoDB `LastEvaluatedKey`) can override
+    /// this to read the bytes directly. If you do, override `to_encoded_string` to match.
+    fn from_raw_bytes(bytes: &[u8]) -> Result<Self::CursorType, CursorError> {
+        let raw = decode_raw_string(bytes)?;
+        Self::new(&raw, split_segments(&raw))
+    }
+
     /// Builds the CursorType from a base64 encoded string.
     /// Returns a CursorError if the decoding fails.
     fn from_encoded_string(input: &str) -> Result<Self::CursorType, CursorError> {
-        let decoded = BASE64_URL_SAFE.decode(input)?;
-        let decoded_string = String::from_utf8(decoded)?;
-        Self::new(
-            decoded_string.as_str(),
-            decoded_string.split(CURSOR_SEGMENT_DELIMITER).collect(),
-        )
+        Self::from_raw_bytes(&decode_raw_bytes(input)?)
+    }
+
+    /// Compares where two cursors point within the same result set, if the cursor type is able to.
+    /// This is used to spot `after`/`before` pairs that contradict each other.
+    ///
+    /// Opaque cursors can't be compared, so by default this returns `None`.
+    fn compare_position(&self, _other: &Self) -> Option<Ordering> {
+        None
     }
 
     /// Builds the base64 encoded variant of the cursor.
     /// Uses the url safe alphabet.
     fn to_encoded_string(&self) -> String {
-        BASE64_URL_SAFE.encode(self.to_raw_string().as_bytes())
+        encode_raw_with(|raw| self.write_raw_string(raw))
     }
 
     // ------------- GraphQLScalar implementations --------------
@@ -94,3 +205,255 @@ where
     let cursor = T::from_encoded_string(input)?;
     Ok(cursor)
 }
+
+/// The built-in cursor types, as identified by `peek_cursor_kind`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CursorKind {
+    /// An `OffsetCursor`.
+    Offset,
+
+    /// A `StringCursor`.
+    String,
+
+    /// A `CompositeCursor`.
+    Composite,
+
+    /// Decodes fine, but isn't one of the built-in cursor types.
+    Unknown,
+}
+
+/// Works out which built-in cursor type a base64 encoded cursor is from its leading segment,
+/// without fully decoding it. Useful for resolvers that accept more than one kind of cursor.
+///
+/// Only the prefix is checked, so the cursor can still fail to decode as the type returned.
+///
+/// ```
+/// use juniper_relay_helpers::{peek_cursor_kind, Cursor, CursorKind, OffsetCursor};
+///
+/// let kind = peek_cursor_kind(&OffsetCursor::new(10).to_encoded_string());
+/// assert_eq!(kind, Ok(CursorKind::Offset));
+/// ```
+pub fn peek_cursor_kind(input: &str) -> Result<CursorKind, CursorError> {
+    let kind = match cursor_type_tag(input)?.as_str() {
+        "offset" => CursorKind::Offset,
+        "string" => CursorKind::String,
+        "composite" => CursorKind::Composite,
+        _ => CursorKind::Unknown,
+    };
+    Ok(kind)
+}
+
+/// Returns the leading segment of a base64 encoded cursor, which is the type tag for the built-in
+/// cursors (`"offset"`, `"string"` and so on). The rest of the cursor isn't parsed.
+///
+/// Unlike `peek_cursor_kind` this also works for your own cursor types, so it can be used to pick
+/// which `Cursor` impl to decode with:
+///
+/// ```
+/// use juniper_relay_helpers::{cursor_type_tag, Cursor, StringCursor};
+///
+/// let encoded = StringCursor::new("lumiere".to_string()).to_encoded_string();
+/// assert_eq!(cursor_type_tag(&encoded).unwrap(), "string");
+/// ```
+pub fn cursor_type_tag(encoded: &str) -> Result<String, CursorError> {
+    let decoded = String::from_utf8(BASE64_URL_SAFE.decode(encoded)?)?;
+    Ok(decoded
+        .split(CURSOR_SEGMENT_DELIMITER)
+        .next()
+        .unwrap_or_default()
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CompositeCursor, Cursor, CursorError, OffsetCursor, OffsetCursor64, StringCursor,
+        cursor_type_tag, decode_cursor_segments, encode_cursor_segments,
+    };
+    use base64::prelude::*;
+    use juniper::GraphQLScalar;
+
+    /// Carries raw bytes, with no string form of its own.
+    #[derive(Debug, GraphQLScalar, Clone, Eq, PartialEq)]
+    #[graphql(
+        name = "BinaryCursor",
+        to_output_with = Self::to_output,
+        from_input_with = Self::from_input
+    )]
+    struct BinaryCursor {
+        key: Vec<u8>,
+    }
+
+    impl Cursor for BinaryCursor {
+        type CursorType = BinaryCursor;
+
+        fn to_raw_string(&self) -> String {
+            unreachable!("binary cursors are only ever encoded")
+        }
+
+        fn new(_raw: &str, _parts: Vec<&str>) -> Result<Self::CursorType, CursorError> {
+            Err(CursorError::InvalidCursor)
+        }
+
+        fn from_raw_bytes(bytes: &[u8]) -> Result<Self::CursorType, CursorError> {
+            Ok(BinaryCursor {
+                key: bytes.to_vec(),
+            })
+        }
+
+        fn to_encoded_string(&self) -> String {
+            BASE64_URL_SAFE.encode(&self.key)
+        }
+    }
+
+    #[test]
+    fn test_binary_cursor_round_trip() {
+        let cursor = BinaryCursor {
+            key: vec![0x00, 0x80, 0xff, 0xfe],
+        };
+        let decoded = BinaryCursor::from_encoded_string(&cursor.to_encoded_string()).unwrap();
+        assert_eq!(decoded, cursor);
+    }
+
+    /// Holds another cursor in its encoded form, so encoding it encodes the inner cursor too.
+    #[derive(Debug, GraphQLScalar, Clone, Eq, PartialEq)]
+    #[graphql(
+        name = "NestingCursor",
+        to_output_with = Self::to_output,
+        from_input_with = Self::from_input
+    )]
+    struct NestingCursor {
+        inner: OffsetCursor,
+    }
+
+    impl Cursor for NestingCursor {
+        type CursorType = NestingCursor;
+
+        fn to_raw_string(&self) -> String {
+            format!("nesting||{}", self.inner.to_encoded_string())
+        }
+
+        fn write_raw_string(&self, out: &mut String) {
+            out.push_str("nesting||");
+            out.push_str(&self.inner.to_encoded_string());
+        }
+
+        fn new(_raw: &str, parts: Vec<&str>) -> Result<Self::CursorType, CursorError> {
+            match parts.as_slice() {
+                ["nesting", inner] => Ok(NestingCursor {
+                    inner: OffsetCursor::from_encoded_string(inner)?,
+                }),
+                _ => Err(CursorError::InvalidCursor),
+            }
+        }
+    }
+
+    #[test]
+    fn test_encoding_reuses_buffer_safely() {
+        // Encoding the inner cursor happens while the outer one is writing its raw form.
+        let nesting = NestingCursor {
+            inner: OffsetCursor::new(7).with_total(Some(20)),
+        };
+        let encoded = nesting.to_encoded_string();
+        assert_eq!(
+            encoded,
+            BASE64_URL_SAFE.encode(nesting.to_raw_string().as_bytes())
+        );
+        assert_eq!(NestingCursor::from_encoded_string(&encoded), Ok(nesting));
+
+        // A cursor larger than the retained buffer, followed by a small one, both encode in full.
+        let long = StringCursor::new("ab".repeat(5000));
+        assert_eq!(
+            StringCursor::from_encoded_string(&long.to_encoded_string()),
+            Ok(long)
+        );
+        assert_eq!(
+            OffsetCursor::new(3).to_encoded_string(),
+            BASE64_URL_SAFE.encode("offset||3")
+        );
+    }
+
+    #[test]
+    fn test_cursor_segments_round_trip() {
+        let encoded = encode_cursor_segments(&["offset", "3", "first=10"]);
+        assert_eq!(
+            OffsetCursor::from_encoded_string(&encoded).unwrap(),
+            OffsetCursor::new(3).with_first(Some(10))
+        );
+        assert_eq!(
+            decode_cursor_segments(&encoded).unwrap(),
+            vec!["offset", "3", "first=10"]
+        );
+        // Split naively, while the cursor itself reads its value whole.
+        let string_cursor = StringCursor::new("a||b".to_string()).to_encoded_string();
+        assert_eq!(
+            decode_cursor_segments(&string_cursor).unwrap(),
+            vec!["string", "a", "b"]
+        );
+        assert_eq!(
+            StringCursor::from_encoded_string(&string_cursor).unwrap(),
+            StringCursor::new("a||b".to_string())
+        );
+        assert!(matches!(
+            decode_cursor_segments("not base64!"),
+            Err(CursorError::Base64(_))
+        ));
+    }
+
+    #[test]
+    fn test_cursor_type_tag() {
+        assert_eq!(
+            cursor_type_tag(&OffsetCursor::new(4).to_encoded_string()).unwrap(),
+            "offset"
+        );
+        assert_eq!(
+            cursor_type_tag(&CompositeCursor::new(vec![]).to_encoded_string()).unwrap(),
+            "composite"
+        );
+
+        // Only the tag is looked at, so a broken remainder still peeks fine.
+        assert_eq!(
+            cursor_type_tag(&encode_cursor_segments(&["custom", "not-a-number"])).unwrap(),
+            "custom"
+        );
+        assert_eq!(cursor_type_tag(&BASE64_URL_SAFE.encode("")).unwrap(), "");
+        assert!(cursor_type_tag("!!").is_err());
+    }
+
+    #[test]
+    fn test_cursor_types_reject_each_other() {
+        let offset = OffsetCursor::new(5).to_encoded_string();
+        let string = StringCursor::new("5".to_string()).to_encoded_string();
+        let composite =
+            CompositeCursor::new(vec![("id".to_string(), "5".to_string())]).to_encoded_string();
+
+        for encoded in [&string, &composite] {
+            assert_eq!(
+                OffsetCursor::from_encoded_string(encoded),
+                Err(CursorError::InvalidCursor)
+            );
+            assert_eq!(
+                OffsetCursor64::from_encoded_string(encoded),
+                Err(CursorError::InvalidCursor)
+            );
+        }
+        for encoded in [&offset, &composite] {
+            assert_eq!(
+                StringCursor::from_encoded_string(encoded),
+                Err(CursorError::InvalidCursor)
+            );
+        }
+        for encoded in [&offset, &string] {
+            assert_eq!(
+                CompositeCursor::from_encoded_string(encoded),
+                Err(CursorError::InvalidCursor)
+            );
+        }
+
+        // The two offset cursors share a format, so they do decode each other.
+        assert_eq!(
+            OffsetCursor64::from_encoded_string(&offset),
+            Ok(OffsetCursor64::new(5))
+        );
+    }
+}
@@ -1,9 +1,21 @@
-use crate::{CURSOR_SEGMENT_DELIMITER, Cursor, CursorError};
+use crate::{CURSOR_SEGMENT_DELIMITER, Cursor, CursorError, PageRequest, decode_cursor_segments};
 use juniper::GraphQLScalar;
-use std::fmt::{Display, Formatter};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter, Write};
+
+/// Tag for the optional segment carrying the page size the cursor was built for.
+const FIRST_SEGMENT: &str = "first=";
+
+/// Tag for the optional segment carrying the total count of the result set.
+const TOTAL_SEGMENT: &str = "total=";
 
 /// A simple offset-based cursor.
+///
+/// Can optionally carry the page size (`first`) it was built for, and the total number of items in
+/// the result set so clients can show progress by decoding any edge cursor. When present these are
+/// encoded as extra `first=` and `total=` segments.
 #[derive(Debug, GraphQLScalar, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[graphql(
     name = "OffsetCursor",
     to_output_with = Self::to_output,
@@ -12,11 +24,123 @@ use std::fmt::{Display, Formatter};
 pub struct OffsetCursor {
     /// The offset of the cursor (how many items to skip).
     pub offset: i32,
+
+    /// The page size the cursor was built for, if it was embedded in the cursor. This is only a
+    /// record: the `first` on the request always decides the page size.
+    pub first: Option<i32>,
+
+    /// The total number of items in the result set, if it was embedded in the cursor.
+    pub total: Option<i32>,
 }
 
 impl OffsetCursor {
     pub fn new(offset: i32) -> Self {
-        OffsetCursor { offset }
+        OffsetCursor {
+            offset,
+            first: None,
+            total: None,
+        }
+    }
+
+    /// Returns the cursor with the given page size embedded in it.
+    pub fn with_first(mut self, first: Option<i32>) -> Self {
+        self.first = first;
+        self
+    }
+
+    /// Returns the cursor with the given total count embedded in it.
+    pub fn with_total(mut self, total: Option<i32>) -> Self {
+        self.total = total;
+        self
+    }
+
+    /// Checks whether two cursors were built for the same page size. If a client changes `first`
+    /// part way through paginating, offsets from the old page size no longer line up with the new one.
+    pub fn same_page_size(&self, other: &OffsetCursor) -> bool {
+        self.first == other.first
+    }
+
+    /// As `same_page_size`, but returns `CursorError::PageSizeChanged` if they differ so resolvers
+    /// can reject the request with `?`.
+    pub fn ensure_same_page_size(&self, other: &OffsetCursor) -> Result<(), CursorError> {
+        if self.same_page_size(other) {
+            Ok(())
+        } else {
+            Err(CursorError::PageSizeChanged)
+        }
+    }
+
+    /// As `from_encoded_string`, but an offset segment that doesn't parse falls back to offset 0
+    /// rather than being rejected. This was the behaviour before decoding became strict, so it's
+    /// only really useful for accepting cursors issued by older versions.
+    pub fn from_encoded_string_lenient(input: &str) -> Result<OffsetCursor, CursorError> {
+        let segments = decode_cursor_segments(input)?;
+        OffsetCursor::from_parts(
+            &segments.iter().map(String::as_str).collect::<Vec<_>>(),
+            true,
+        )
+    }
+
+    fn from_parts(parts: &[&str], lenient_offset: bool) -> Result<OffsetCursor, CursorError> {
+        if parts.len() < 2 {
+            return Err(CursorError::MalformedSegment {
+                expected: 2,
+                got: parts.len(),
+            });
+        }
+        if parts[0] != "offset" {
+            return Err(CursorError::InvalidCursor);
+        }
+        let offset = match parts[1].parse::<i32>() {
+            Ok(offset) => offset,
+            Err(_) if lenient_offset => 0,
+            Err(_) => return Err(CursorError::InvalidCursor),
+        };
+
+        let mut first = None;
+        let mut total = None;
+        for part in &parts[2..] {
+            let (segment, value) = if let Some(value) = part.strip_prefix(FIRST_SEGMENT) {
+                (&mut first, value)
+            } else if let Some(value) = part.strip_prefix(TOTAL_SEGMENT) {
+                (&mut total, value)
+            } else {
+                return Err(CursorError::InvalidCursor);
+            };
+            *segment = Some(
+                value
+                    .parse::<i32>()
+                    .map_err(|_| CursorError::InvalidCursor)?,
+            );
+        }
+
+        Ok(OffsetCursor {
+            offset,
+            first,
+            total,
+        })
+    }
+
+    /// The `LIMIT` and `OFFSET` to query for the page after this cursor, as `(limit, offset)`.
+    ///
+    /// The cursor points at the last item already seen, so the offset is one past it. The limit is
+    /// the page's `first`, or `i64::MAX` when `first` is missing, as that asks for the rest of the
+    /// set. Negative values are clamped to 0. This cursor is taken as the `after`, so only `first`
+    /// is read from `page`.
+    ///
+    /// ```
+    /// use juniper_relay_helpers::{OffsetCursor, PageRequest};
+    ///
+    /// let after = OffsetCursor::new(19);
+    /// let page = PageRequest::new(Some(10), Some(after.clone()), None);
+    /// assert_eq!(after.to_sql_limit_offset(&page), (10, 20));
+    /// ```
+    ///
+    /// With no `after` cursor to call this on, use `PageRequest::to_sql_limit_offset`, which starts
+    /// at offset 0. Only forward requests are handled: `last` and `before` are ignored.
+    pub fn to_sql_limit_offset(&self, page: &PageRequest<OffsetCursor>) -> (i64, i64) {
+        let offset = (i64::from(self.offset) + 1).max(0);
+        (sql_limit(page), offset)
     }
 
     /// Returns the "next" cursor based on adding to the current one. This is obviously not guaranteed to be
@@ -59,20 +183,82 @@ impl OffsetCursor {
     }
 }
 
+impl PageRequest<OffsetCursor> {
+    /// The `LIMIT` and `OFFSET` to query for this page, as `(limit, offset)`. This is
+    /// `OffsetCursor::to_sql_limit_offset` on the `after` cursor, or an offset of 0 without one.
+    ///
+    /// ```
+    /// use juniper_relay_helpers::{OffsetCursor, PageRequest};
+    ///
+    /// let page = PageRequest::new(Some(10), None::<OffsetCursor>, None);
+    /// assert_eq!(page.to_sql_limit_offset(), (10, 0));
+    /// ```
+    pub fn to_sql_limit_offset(&self) -> (i64, i64) {
+        match &self.after {
+            Some(after) => after.to_sql_limit_offset(self),
+            None => (sql_limit(self), 0),
+        }
+    }
+}
+
+/// The `LIMIT` for a page: its `first`, or `i64::MAX` for the rest of the set.
+fn sql_limit(page: &PageRequest<OffsetCursor>) -> i64 {
+    page.first.map_or(i64::MAX, |first| i64::from(first.max(0)))
+}
+
 impl Cursor for OffsetCursor {
     type CursorType = OffsetCursor;
 
     fn to_raw_string(&self) -> String {
-        format!("offset{}{}", CURSOR_SEGMENT_DELIMITER, self.offset)
+        let mut raw = String::new();
+        self.write_raw_string(&mut raw);
+        raw
     }
 
-    fn new(_raw: &str, parts: Vec<&str>) -> Result<OffsetCursor, CursorError> {
-        if parts.len() != 2 {
-            return Err(CursorError::InvalidCursor);
+    fn write_raw_string(&self, out: &mut String) {
+        // Writing to a String can't fail.
+        let _ = write!(out, "offset{}{}", CURSOR_SEGMENT_DELIMITER, self.offset);
+        if let Some(first) = self.first {
+            let _ = write!(
+                out,
+                "{}{}{}",
+                CURSOR_SEGMENT_DELIMITER, FIRST_SEGMENT, first
+            );
+        }
+        if let Some(total) = self.total {
+            let _ = write!(
+                out,
+                "{}{}{}",
+                CURSOR_SEGMENT_DELIMITER, TOTAL_SEGMENT, total
+            );
         }
-        let offset = parts[1].parse::<i32>().unwrap_or(0);
-        Ok(OffsetCursor { offset })
     }
+
+    fn new(_raw: &str, parts: Vec<&str>) -> Result<OffsetCursor, CursorError> {
+        OffsetCursor::from_parts(&parts, false)
+    }
+
+    fn compare_position(&self, other: &Self) -> Option<Ordering> {
+        Some(self.offset.cmp(&other.offset))
+    }
+}
+
+/// Encodes the `after` cursor for an absolute offset, with the page size embedded if given. Useful
+/// for linking straight to a page, such as the one containing a particular item.
+///
+/// ```
+/// use juniper_relay_helpers::{offset_after_cursor, Cursor, OffsetCursor};
+///
+/// let after = offset_after_cursor(12344, Some(20));
+/// assert_eq!(
+///     OffsetCursor::from_encoded_string(&after).unwrap(),
+///     OffsetCursor::new(12344).with_first(Some(20))
+/// );
+/// ```
+pub fn offset_after_cursor(offset: i32, first: Option<i32>) -> String {
+    OffsetCursor::new(offset)
+        .with_first(first)
+        .to_encoded_string()
 }
 
 impl Display for OffsetCursor {
@@ -83,7 +269,9 @@ impl Display for OffsetCursor {
 
 #[cfg(test)]
 mod tests {
-    use crate::cursors::{Cursor, OffsetCursor};
+    use crate::cursors::{Cursor, OffsetCursor, offset_after_cursor};
+    use crate::{CursorError, PageRequest};
+    use base64::prelude::*;
 
     #[test]
     fn test_new_offset_first() {
@@ -91,6 +279,20 @@ mod tests {
         assert_eq!(cursor.offset, 1);
     }
 
+    #[test]
+    fn test_offset_after_cursor() {
+        assert_eq!(
+            offset_after_cursor(12344, Some(20)),
+            OffsetCursor::new(12344)
+                .with_first(Some(20))
+                .to_encoded_string()
+        );
+        assert_eq!(
+            offset_after_cursor(1, None),
+            OffsetCursor::new(1).to_encoded_string()
+        );
+    }
+
     #[test]
     fn test_default() {
         let cursor = OffsetCursor::default();
@@ -99,13 +301,21 @@ mod tests {
 
     #[test]
     fn test_raw_string() {
-        let cursor = OffsetCursor { offset: 1 };
+        let cursor = OffsetCursor {
+            offset: 1,
+            first: None,
+            total: None,
+        };
         assert_eq!(cursor.to_string(), "offset||1");
     }
 
     #[test]
     fn test_encoded_string() {
-        let cursor = OffsetCursor { offset: 1 };
+        let cursor = OffsetCursor {
+            offset: 1,
+            first: None,
+            total: None,
+        };
         assert_eq!(cursor.to_encoded_string(), "b2Zmc2V0fHwx");
     }
 
@@ -158,4 +368,158 @@ mod tests {
         let prev_page = cursor.previous_page(None);
         assert!(prev_page.is_none());
     }
+
+    #[test]
+    fn test_encoded_string_with_total() {
+        let cursor = OffsetCursor::new(1).with_total(Some(27));
+        assert_eq!(cursor.to_string(), "offset||1||total=27");
+
+        let decoded = OffsetCursor::from_encoded_string(&cursor.to_encoded_string()).unwrap();
+        assert_eq!(decoded.offset, 1);
+        assert_eq!(decoded.total, Some(27));
+    }
+
+    #[test]
+    fn test_from_encoded_string_without_total() {
+        let cursor = OffsetCursor::from_encoded_string("b2Zmc2V0fHwx").unwrap();
+        assert_eq!(cursor.total, None);
+        assert_eq!(cursor, OffsetCursor::new(1));
+    }
+
+    #[test]
+    fn test_from_encoded_string_invalid_total() {
+        let encoded = BASE64_URL_SAFE.encode("offset||1||total=lots");
+        assert_eq!(
+            OffsetCursor::from_encoded_string(&encoded),
+            Err(CursorError::InvalidCursor)
+        );
+
+        let encoded = BASE64_URL_SAFE.encode("offset||1||unknown=1");
+        assert_eq!(
+            OffsetCursor::from_encoded_string(&encoded),
+            Err(CursorError::InvalidCursor)
+        );
+    }
+
+    #[test]
+    fn test_from_encoded_string_invalid_offset() {
+        for raw in ["offset||garbage", "offset||", "string||5"] {
+            assert_eq!(
+                OffsetCursor::from_encoded_string(&BASE64_URL_SAFE.encode(raw)),
+                Err(CursorError::InvalidCursor),
+                "{raw}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_encoded_string_missing_segments() {
+        assert_eq!(
+            OffsetCursor::from_encoded_string(&BASE64_URL_SAFE.encode("offset")),
+            Err(CursorError::MalformedSegment {
+                expected: 2,
+                got: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_from_encoded_string_bad_encoding() {
+        assert!(matches!(
+            OffsetCursor::from_encoded_string("not base64!"),
+            Err(CursorError::Base64(_))
+        ));
+        assert!(matches!(
+            OffsetCursor::from_encoded_string(&BASE64_URL_SAFE.encode([0x80])),
+            Err(CursorError::Utf8(_))
+        ));
+    }
+
+    #[test]
+    fn test_from_encoded_string_lenient() {
+        let encoded = BASE64_URL_SAFE.encode("offset||garbage||total=27");
+        assert_eq!(
+            OffsetCursor::from_encoded_string_lenient(&encoded),
+            Ok(OffsetCursor::new(0).with_total(Some(27)))
+        );
+        assert_eq!(
+            OffsetCursor::from_encoded_string_lenient("b2Zmc2V0fHwx"),
+            Ok(OffsetCursor::new(1))
+        );
+        assert_eq!(
+            OffsetCursor::from_encoded_string_lenient(&BASE64_URL_SAFE.encode("string||5")),
+            Err(CursorError::InvalidCursor)
+        );
+    }
+
+    #[test]
+    fn test_encoded_string_with_first_and_total() {
+        let cursor = OffsetCursor::new(1)
+            .with_first(Some(10))
+            .with_total(Some(27));
+        assert_eq!(cursor.to_string(), "offset||1||first=10||total=27");
+
+        let decoded = OffsetCursor::from_encoded_string(&cursor.to_encoded_string()).unwrap();
+        assert_eq!(decoded, cursor);
+    }
+
+    #[test]
+    fn test_same_page_size() {
+        let a = OffsetCursor::new(10).with_first(Some(10));
+        let b = OffsetCursor::new(20).with_first(Some(10));
+        let c = OffsetCursor::new(20).with_first(Some(25));
+
+        assert!(a.same_page_size(&b));
+        assert!(!a.same_page_size(&c));
+        assert!(!a.same_page_size(&OffsetCursor::new(20)));
+
+        assert_eq!(a.ensure_same_page_size(&b), Ok(()));
+        assert_eq!(
+            a.ensure_same_page_size(&c),
+            Err(CursorError::PageSizeChanged)
+        );
+    }
+
+    #[test]
+    fn test_to_sql_limit_offset_without_after() {
+        let page = PageRequest::new(Some(10), None::<OffsetCursor>, None);
+        assert_eq!(page.to_sql_limit_offset(), (10, 0));
+
+        let page = PageRequest::new(None, None::<OffsetCursor>, None);
+        assert_eq!(page.to_sql_limit_offset(), (i64::MAX, 0));
+    }
+
+    #[test]
+    fn test_to_sql_limit_offset_with_after() {
+        let after = OffsetCursor::new(0);
+        let page = PageRequest::new(Some(5), Some(after.clone()), None);
+        assert_eq!(after.to_sql_limit_offset(&page), (5, 1));
+        assert_eq!(page.to_sql_limit_offset(), (5, 1));
+
+        // Too large for an i32 offset, but not for the query.
+        let after = OffsetCursor::new(i32::MAX);
+        let page = PageRequest::new(Some(5), Some(after.clone()), None);
+        assert_eq!(
+            after.to_sql_limit_offset(&page),
+            (5, i64::from(i32::MAX) + 1)
+        );
+    }
+
+    #[test]
+    fn test_to_sql_limit_offset_clamps_negatives() {
+        let after = OffsetCursor::new(-10);
+        let page = PageRequest::new(Some(-3), Some(after.clone()), None);
+        assert_eq!(after.to_sql_limit_offset(&page), (0, 0));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let cursor = OffsetCursor::new(10)
+            .with_first(Some(5))
+            .with_total(Some(27));
+        let json = serde_json::to_string(&cursor).unwrap();
+        assert_eq!(json, r#"{"offset":10,"first":5,"total":27}"#);
+        assert_eq!(serde_json::from_str::<OffsetCursor>(&json).unwrap(), cursor);
+    }
 }
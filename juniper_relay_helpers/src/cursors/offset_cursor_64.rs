@@ -0,0 +1,217 @@
+use crate::{CURSOR_SEGMENT_DELIMITER, Cursor, CursorError, OffsetCursor};
+use juniper::GraphQLScalar;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter, Write};
+
+/// Tag for the optional segment carrying the page size the cursor was built for.
+const FIRST_SEGMENT: &str = "first=";
+
+/// Tag for the optional segment carrying the total count of the result set.
+const TOTAL_SEGMENT: &str = "total=";
+
+/// An offset cursor with 64 bit offsets, for result sets too large for `OffsetCursor`. Use it with
+/// the `OffsetCursorProvider64`.
+///
+/// It's encoded the same way as `OffsetCursor`, so cursors issued by one decode as the other as
+/// long as the values fit, which makes it possible to switch a field over without breaking the
+/// cursors clients already hold.
+///
+/// ```
+/// use juniper_relay_helpers::{Cursor, OffsetCursor, OffsetCursor64};
+///
+/// let cursor = OffsetCursor64::new(5_000_000_000);
+/// assert_eq!(cursor.to_string(), "offset||5000000000");
+///
+/// let old = OffsetCursor::new(10).to_encoded_string();
+/// assert_eq!(OffsetCursor64::from_encoded_string(&old), Ok(OffsetCursor64::new(10)));
+/// ```
+#[derive(Debug, GraphQLScalar, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[graphql(
+    name = "OffsetCursor64",
+    to_output_with = Self::to_output,
+    from_input_with = Self::from_input
+)]
+pub struct OffsetCursor64 {
+    /// The offset of the cursor (how many items to skip).
+    pub offset: i64,
+
+    /// The page size the cursor was built for, if it was embedded in the cursor. As with
+    /// `OffsetCursor`, the `first` on the request always decides the page size.
+    pub first: Option<i64>,
+
+    /// The total number of items in the result set, if it was embedded in the cursor.
+    pub total: Option<i64>,
+}
+
+impl OffsetCursor64 {
+    pub fn new(offset: i64) -> Self {
+        OffsetCursor64 {
+            offset,
+            first: None,
+            total: None,
+        }
+    }
+
+    /// Returns the cursor with the given page size embedded in it.
+    pub fn with_first(mut self, first: Option<i64>) -> Self {
+        self.first = first;
+        self
+    }
+
+    /// Returns the cursor with the given total count embedded in it.
+    pub fn with_total(mut self, total: Option<i64>) -> Self {
+        self.total = total;
+        self
+    }
+}
+
+impl Cursor for OffsetCursor64 {
+    type CursorType = OffsetCursor64;
+
+    fn to_raw_string(&self) -> String {
+        let mut raw = String::new();
+        self.write_raw_string(&mut raw);
+        raw
+    }
+
+    fn write_raw_string(&self, out: &mut String) {
+        // Writing to a String can't fail.
+        let _ = write!(out, "offset{}{}", CURSOR_SEGMENT_DELIMITER, self.offset);
+        if let Some(first) = self.first {
+            let _ = write!(
+                out,
+                "{}{}{}",
+                CURSOR_SEGMENT_DELIMITER, FIRST_SEGMENT, first
+            );
+        }
+        if let Some(total) = self.total {
+            let _ = write!(
+                out,
+                "{}{}{}",
+                CURSOR_SEGMENT_DELIMITER, TOTAL_SEGMENT, total
+            );
+        }
+    }
+
+    fn new(_raw: &str, parts: Vec<&str>) -> Result<OffsetCursor64, CursorError> {
+        if parts.len() < 2 {
+            return Err(CursorError::MalformedSegment {
+                expected: 2,
+                got: parts.len(),
+            });
+        }
+        if parts[0] != "offset" {
+            return Err(CursorError::InvalidCursor);
+        }
+        let parse = |value: &str| value.parse::<i64>().map_err(|_| CursorError::InvalidCursor);
+
+        let mut cursor = OffsetCursor64::new(parse(parts[1])?);
+        for part in &parts[2..] {
+            if let Some(value) = part.strip_prefix(FIRST_SEGMENT) {
+                cursor.first = Some(parse(value)?);
+            } else if let Some(value) = part.strip_prefix(TOTAL_SEGMENT) {
+                cursor.total = Some(parse(value)?);
+            } else {
+                return Err(CursorError::InvalidCursor);
+            }
+        }
+        Ok(cursor)
+    }
+
+    fn compare_position(&self, other: &Self) -> Option<Ordering> {
+        Some(self.offset.cmp(&other.offset))
+    }
+}
+
+impl From<OffsetCursor> for OffsetCursor64 {
+    fn from(cursor: OffsetCursor) -> Self {
+        OffsetCursor64 {
+            offset: cursor.offset.into(),
+            first: cursor.first.map(i64::from),
+            total: cursor.total.map(i64::from),
+        }
+    }
+}
+
+/// Fails with `CursorError::InvalidCursor` if any of the values are too large for an `OffsetCursor`.
+impl TryFrom<OffsetCursor64> for OffsetCursor {
+    type Error = CursorError;
+
+    fn try_from(cursor: OffsetCursor64) -> Result<Self, Self::Error> {
+        let narrow = |value: i64| i32::try_from(value).map_err(|_| CursorError::InvalidCursor);
+        Ok(OffsetCursor {
+            offset: narrow(cursor.offset)?,
+            first: cursor.first.map(narrow).transpose()?,
+            total: cursor.total.map(narrow).transpose()?,
+        })
+    }
+}
+
+impl Display for OffsetCursor64 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_raw_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CursorError;
+    use crate::cursors::{Cursor, OffsetCursor, OffsetCursor64};
+    use base64::prelude::*;
+
+    #[test]
+    fn test_round_trip_beyond_i32() {
+        let cursor = OffsetCursor64::new(i64::from(i32::MAX) + 1)
+            .with_first(Some(50))
+            .with_total(Some(9_000_000_000));
+        assert_eq!(
+            cursor.to_string(),
+            "offset||2147483648||first=50||total=9000000000"
+        );
+        assert_eq!(
+            OffsetCursor64::from_encoded_string(&cursor.to_encoded_string()),
+            Ok(cursor)
+        );
+    }
+
+    #[test]
+    fn test_interchangeable_with_offset_cursor() {
+        let small = OffsetCursor::new(12)
+            .with_first(Some(5))
+            .with_total(Some(40));
+        let wide = OffsetCursor64::from_encoded_string(&small.to_encoded_string()).unwrap();
+        assert_eq!(wide, OffsetCursor64::from(small.clone()));
+        assert_eq!(
+            OffsetCursor::from_encoded_string(&wide.to_encoded_string()),
+            Ok(small.clone())
+        );
+        assert_eq!(OffsetCursor::try_from(wide), Ok(small));
+
+        let large = OffsetCursor64::new(5_000_000_000);
+        assert_eq!(
+            OffsetCursor::try_from(large.clone()),
+            Err(CursorError::InvalidCursor)
+        );
+        assert_eq!(
+            OffsetCursor::from_encoded_string(&large.to_encoded_string()),
+            Err(CursorError::InvalidCursor)
+        );
+    }
+
+    #[test]
+    fn test_rejects_malformed() {
+        let decode = |raw: &str| OffsetCursor64::from_encoded_string(&BASE64_URL_SAFE.encode(raw));
+
+        assert_eq!(
+            decode("offset"),
+            Err(CursorError::MalformedSegment {
+                expected: 2,
+                got: 1
+            })
+        );
+        assert_eq!(decode("offset||ten"), Err(CursorError::InvalidCursor));
+        assert_eq!(decode("offset||1||page=2"), Err(CursorError::InvalidCursor));
+        assert_eq!(decode("string||1"), Err(CursorError::InvalidCursor));
+    }
+}
@@ -5,6 +5,7 @@ use std::fmt::{Display, Formatter};
 /// Built-in cursor type for when the cursor is just a string. Usually useful for things like
 /// NoSQL systems that return something opaque to you.
 #[derive(Debug, GraphQLScalar, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StringCursor {
     /// The value of the cursor.
     pub value: String,
@@ -20,13 +21,24 @@ impl Cursor for StringCursor {
     type CursorType = StringCursor;
 
     fn to_raw_string(&self) -> String {
-        format!("string{}{}", CURSOR_SEGMENT_DELIMITER, self.value.clone())
+        format!("string{}{}", CURSOR_SEGMENT_DELIMITER, self.value)
     }
 
-    fn new(_raw: &str, parts: Vec<&str>) -> Result<Self::CursorType, CursorError> {
-        let raw_parts_value = parts[1].to_string();
+    fn write_raw_string(&self, out: &mut String) {
+        out.push_str("string");
+        out.push_str(CURSOR_SEGMENT_DELIMITER);
+        out.push_str(&self.value);
+    }
+
+    fn new(raw: &str, _parts: Vec<&str>) -> Result<Self::CursorType, CursorError> {
+        // The value is the only segment, so take everything after the prefix rather than splitting.
+        // That way values containing the delimiter come back intact.
+        let value = raw
+            .strip_prefix("string")
+            .and_then(|rest| rest.strip_prefix(CURSOR_SEGMENT_DELIMITER))
+            .ok_or(CursorError::InvalidCursor)?;
         Ok(StringCursor {
-            value: raw_parts_value,
+            value: value.to_string(),
         })
     }
 }
@@ -46,7 +58,8 @@ impl Default for StringCursor {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Cursor, StringCursor};
+    use crate::{Cursor, CursorError, StringCursor};
+    use base64::prelude::*;
 
     #[test]
     fn test_string_cursor_raw_string() {
@@ -69,4 +82,22 @@ mod tests {
         let cursor = StringCursor::from_encoded_string("c3RyaW5nfHxzb21lLWN1cnNvcg==").unwrap();
         assert_eq!(cursor.value, "some-cursor");
     }
+
+    #[test]
+    fn test_string_cursor_round_trips_delimiter() {
+        let cursor = StringCursor::new("a||b||c".to_string());
+        let decoded = StringCursor::from_encoded_string(&cursor.to_encoded_string()).unwrap();
+        assert_eq!(decoded.value, "a||b||c");
+    }
+
+    #[test]
+    fn test_string_cursor_invalid() {
+        for raw in ["string", "offset||1", ""] {
+            assert_eq!(
+                StringCursor::from_encoded_string(&BASE64_URL_SAFE.encode(raw)),
+                Err(CursorError::InvalidCursor),
+                "{raw}"
+            );
+        }
+    }
 }
@@ -0,0 +1,170 @@
+use crate::{CURSOR_SEGMENT_DELIMITER, Cursor, CursorError};
+use chrono::{DateTime, SecondsFormat, Utc};
+use juniper::GraphQLScalar;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+/// Cursor for time-ordered feeds, holding the timestamp of the last item seen and its id to break
+/// ties between items created at the same instant. Requires the `chrono` feature.
+///
+/// The timestamp is encoded as RFC 3339 with as many fractional digits as it needs, so it
+/// round-trips exactly. Cursors order by `at`, then `id`, matching an `ORDER BY created_at, id`.
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use juniper_relay_helpers::{Cursor, TimestampCursor};
+///
+/// let cursor = TimestampCursor::new(
+///     Utc.with_ymd_and_hms(1905, 3, 14, 9, 30, 0).unwrap(),
+///     "gustave".to_string(),
+/// );
+/// assert_eq!(cursor.to_string(), "timestamp||1905-03-14T09:30:00Z||gustave");
+///
+/// let decoded = TimestampCursor::from_encoded_string(&cursor.to_encoded_string()).unwrap();
+/// assert_eq!(decoded, cursor);
+/// ```
+#[derive(Debug, GraphQLScalar, Default, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[graphql(
+    name = "TimestampCursor",
+    to_output_with = Self::to_output,
+    from_input_with = Self::from_input
+)]
+pub struct TimestampCursor {
+    /// When the item was created (or whatever the feed is ordered by).
+    pub at: DateTime<Utc>,
+
+    /// The item's id, to order items sharing the same timestamp.
+    pub id: String,
+}
+
+impl TimestampCursor {
+    pub fn new(at: DateTime<Utc>, id: String) -> Self {
+        TimestampCursor { at, id }
+    }
+}
+
+impl Cursor for TimestampCursor {
+    type CursorType = TimestampCursor;
+
+    fn to_raw_string(&self) -> String {
+        format!(
+            "timestamp{}{}{}{}",
+            CURSOR_SEGMENT_DELIMITER,
+            self.at.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+            CURSOR_SEGMENT_DELIMITER,
+            self.id
+        )
+    }
+
+    fn new(raw: &str, parts: Vec<&str>) -> Result<TimestampCursor, CursorError> {
+        if parts.first() != Some(&"timestamp") {
+            return Err(CursorError::InvalidCursor);
+        }
+
+        // The id is everything after the timestamp, so ids containing the delimiter survive.
+        let (at, id) = raw
+            .strip_prefix("timestamp")
+            .and_then(|rest| rest.strip_prefix(CURSOR_SEGMENT_DELIMITER))
+            .and_then(|rest| rest.split_once(CURSOR_SEGMENT_DELIMITER))
+            .ok_or(CursorError::MalformedSegment {
+                expected: 3,
+                got: parts.len(),
+            })?;
+
+        let at = DateTime::parse_from_rfc3339(at)
+            .map_err(|_| CursorError::InvalidCursor)?
+            .with_timezone(&Utc);
+
+        Ok(TimestampCursor {
+            at,
+            id: id.to_string(),
+        })
+    }
+
+    fn compare_position(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for TimestampCursor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_raw_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CursorError;
+    use crate::cursors::{Cursor, TimestampCursor};
+    use base64::prelude::*;
+    use chrono::{TimeZone, Utc};
+
+    fn cursor(secs: i64, nanos: u32, id: &str) -> TimestampCursor {
+        TimestampCursor::new(Utc.timestamp_opt(secs, nanos).unwrap(), id.to_string())
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for cursor in [
+            cursor(0, 0, "1"),
+            cursor(1_745_488_800, 123_456_789, "verso"),
+            cursor(1_745_488_800, 0, "a||b"),
+            cursor(-2_051_222_400, 500_000_000, ""),
+        ] {
+            assert_eq!(
+                TimestampCursor::from_encoded_string(&cursor.to_encoded_string()),
+                Ok(cursor)
+            );
+        }
+    }
+
+    #[test]
+    fn test_ordering() {
+        let mut cursors = vec![
+            cursor(20, 0, "a"),
+            cursor(10, 5, "a"),
+            cursor(10, 0, "b"),
+            cursor(10, 0, "a"),
+        ];
+        cursors.sort();
+        assert_eq!(
+            cursors,
+            vec![
+                cursor(10, 0, "a"),
+                cursor(10, 0, "b"),
+                cursor(10, 5, "a"),
+                cursor(20, 0, "a"),
+            ]
+        );
+        assert_eq!(
+            cursor(10, 0, "b").compare_position(&cursor(20, 0, "a")),
+            Some(std::cmp::Ordering::Less)
+        );
+    }
+
+    #[test]
+    fn test_rejects_malformed() {
+        let decode = |raw: &str| TimestampCursor::from_encoded_string(&BASE64_URL_SAFE.encode(raw));
+
+        assert_eq!(
+            decode("timestamp||1905-03-14T09:30:00Z"),
+            Err(CursorError::MalformedSegment {
+                expected: 3,
+                got: 2
+            })
+        );
+        assert_eq!(
+            decode("timestamp"),
+            Err(CursorError::MalformedSegment {
+                expected: 3,
+                got: 1
+            })
+        );
+        assert_eq!(
+            decode("timestamp||yesterday||1"),
+            Err(CursorError::InvalidCursor)
+        );
+        assert_eq!(decode("offset||1||2"), Err(CursorError::InvalidCursor));
+    }
+}
@@ -0,0 +1,291 @@
+use crate::{CURSOR_SEGMENT_DELIMITER, Cursor, CursorError};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter, Write};
+
+/// A cursor layout that can change over time, for wrapping in a `VersionedCursor`.
+///
+/// New cursors are always written in the current `VERSION`, and `decode_versioned` reads cursors
+/// written by any version still accepted, migrating older layouts to the current one.
+pub trait VersionedCursorFormat: Cursor<CursorType = Self> {
+    /// The version new cursors are written in.
+    const VERSION: u32;
+
+    /// Decodes a cursor written in `version`. Like `Cursor::new`, it's given both the raw string
+    /// following the version segment and that string split into segments, so values that may
+    /// contain the delimiter can be read from `raw`. Return `CursorError::UnsupportedVersion` for
+    /// versions no longer accepted.
+    ///
+    /// Versions newer than `VERSION` are rejected before this is called.
+    fn decode_versioned(version: u32, raw: &str, parts: Vec<&str>) -> Result<Self, CursorError>;
+}
+
+/// Wraps another cursor and prefixes its raw string with a `v{n}` version segment, so the layout
+/// of the inner cursor can change without breaking cursors already held by clients.
+///
+/// ```
+/// use juniper::GraphQLScalar;
+/// use juniper_relay_helpers::{
+///     encode_cursor_segments, Cursor, CursorError, VersionedCursor, VersionedCursorFormat,
+/// };
+///
+/// /// Version 1 held the page number, with pages of 10. Version 2 holds the offset.
+/// #[derive(Debug, Clone, Eq, PartialEq, GraphQLScalar)]
+/// #[graphql(to_output_with = Self::to_output, from_input_with = Self::from_input)]
+/// struct FeedCursor {
+///     offset: i32,
+/// }
+///
+/// impl Cursor for FeedCursor {
+///     type CursorType = FeedCursor;
+///
+///     fn to_raw_string(&self) -> String {
+///         self.offset.to_string()
+///     }
+///
+///     fn new(raw: &str, parts: Vec<&str>) -> Result<FeedCursor, CursorError> {
+///         Self::decode_versioned(Self::VERSION, raw, parts)
+///     }
+/// }
+///
+/// impl VersionedCursorFormat for FeedCursor {
+///     const VERSION: u32 = 2;
+///
+///     fn decode_versioned(
+///         version: u32,
+///         raw: &str,
+///         _parts: Vec<&str>,
+///     ) -> Result<FeedCursor, CursorError> {
+///         let value = raw.parse::<i32>().map_err(|_| CursorError::InvalidCursor)?;
+///         match version {
+///             1 => Ok(FeedCursor { offset: value * 10 }),
+///             2 => Ok(FeedCursor { offset: value }),
+///             _ => Err(CursorError::UnsupportedVersion(version)),
+///         }
+///     }
+/// }
+///
+/// // Issued before the layout changed:
+/// let old = encode_cursor_segments(&["v1", "3"]);
+/// let decoded = VersionedCursor::<FeedCursor>::from_encoded_string(&old).unwrap();
+/// assert_eq!(decoded.inner, FeedCursor { offset: 30 });
+///
+/// // And re-issued in the current layout:
+/// assert_eq!(decoded.to_string(), "v2||30");
+/// ```
+///
+/// Cursors without a version segment are rejected with `CursorError::InvalidCursor`, and cursors
+/// from a version newer than `VERSION` with `CursorError::UnsupportedVersion`.
+///
+/// It's named after the inner cursor in the schema, so `VersionedCursor<FeedCursor>` is a
+/// `VersionedFeedCursor` scalar, and versioning different cursors gives different scalars.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct VersionedCursor<C>
+where
+    C: VersionedCursorFormat,
+{
+    /// The cursor that was versioned.
+    pub inner: C,
+}
+
+impl<C> VersionedCursor<C>
+where
+    C: VersionedCursorFormat,
+{
+    pub fn new(inner: C) -> Self {
+        VersionedCursor { inner }
+    }
+
+    /// Unwraps the versioned cursor into the inner one.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+impl<C> Cursor for VersionedCursor<C>
+where
+    C: VersionedCursorFormat,
+{
+    type CursorType = VersionedCursor<C>;
+
+    fn to_raw_string(&self) -> String {
+        let mut raw = String::new();
+        self.write_raw_string(&mut raw);
+        raw
+    }
+
+    fn write_raw_string(&self, out: &mut String) {
+        // Writing to a String can't fail.
+        let _ = write!(out, "v{}{}", C::VERSION, CURSOR_SEGMENT_DELIMITER);
+        self.inner.write_raw_string(out);
+    }
+
+    fn new(raw: &str, parts: Vec<&str>) -> Result<Self::CursorType, CursorError> {
+        let (version_segment, rest) = raw
+            .split_once(CURSOR_SEGMENT_DELIMITER)
+            .unwrap_or((raw, ""));
+        let version = version_segment
+            .strip_prefix('v')
+            .and_then(|version| version.parse::<u32>().ok())
+            .ok_or(CursorError::InvalidCursor)?;
+        if version > C::VERSION {
+            return Err(CursorError::UnsupportedVersion(version));
+        }
+
+        let rest_parts = parts.into_iter().skip(1).collect();
+        Ok(VersionedCursor::new(C::decode_versioned(
+            version, rest, rest_parts,
+        )?))
+    }
+
+    fn compare_position(&self, other: &Self) -> Option<Ordering> {
+        self.inner.compare_position(&other.inner)
+    }
+}
+
+impl<C> Display for VersionedCursor<C>
+where
+    C: VersionedCursorFormat,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_raw_string())
+    }
+}
+
+crate::wrapped_cursor_scalar!(
+    VersionedCursor<C>,
+    prefix = "Versioned",
+    description = "A cursor prefixed with the version of its layout.",
+    where C: VersionedCursorFormat
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Cursor, CursorError, OffsetCursor, StringCursor, VersionedCursor, VersionedCursorFormat,
+        encode_cursor_segments,
+    };
+    use base64::prelude::*;
+    use juniper::{DefaultScalarValue, GraphQLType};
+
+    /// Version 1 wrote `page||{n}` with pages of 10, version 2 writes an `OffsetCursor`, and
+    /// version 3 is yet to be released.
+    impl VersionedCursorFormat for OffsetCursor {
+        const VERSION: u32 = 2;
+
+        fn decode_versioned(
+            version: u32,
+            raw: &str,
+            parts: Vec<&str>,
+        ) -> Result<Self, CursorError> {
+            match (version, parts.as_slice()) {
+                (1, ["page", page]) => page
+                    .parse::<i32>()
+                    .map(|page| OffsetCursor::new(page * 10))
+                    .map_err(|_| CursorError::InvalidCursor),
+                (1, _) => Err(CursorError::InvalidCursor),
+                (2, _) => <OffsetCursor as Cursor>::new(raw, parts),
+                _ => Err(CursorError::UnsupportedVersion(version)),
+            }
+        }
+    }
+
+    impl VersionedCursorFormat for StringCursor {
+        const VERSION: u32 = 1;
+
+        fn decode_versioned(
+            version: u32,
+            raw: &str,
+            parts: Vec<&str>,
+        ) -> Result<Self, CursorError> {
+            match version {
+                1 => <StringCursor as Cursor>::new(raw, parts),
+                _ => Err(CursorError::UnsupportedVersion(version)),
+            }
+        }
+    }
+
+    type VersionedOffset = VersionedCursor<OffsetCursor>;
+
+    #[test]
+    fn test_round_trip() {
+        let cursor = VersionedOffset::new(OffsetCursor::new(10).with_first(Some(5)));
+        assert_eq!(cursor.to_string(), "v2||offset||10||first=5");
+
+        let decoded = VersionedOffset::from_encoded_string(&cursor.to_encoded_string()).unwrap();
+        assert_eq!(decoded, cursor);
+    }
+
+    #[test]
+    fn test_round_trip_with_delimiter_in_value() {
+        let cursor = VersionedCursor::new(StringCursor::new("a||b||".to_string()));
+        assert_eq!(cursor.to_string(), "v1||string||a||b||");
+
+        let decoded =
+            VersionedCursor::<StringCursor>::from_encoded_string(&cursor.to_encoded_string())
+                .unwrap();
+        assert_eq!(decoded, cursor);
+    }
+
+    #[test]
+    fn test_named_after_inner_cursor() {
+        assert_eq!(
+            <VersionedOffset as GraphQLType<DefaultScalarValue>>::name(&()).as_deref(),
+            Some("VersionedOffsetCursor")
+        );
+        assert_eq!(
+            <VersionedCursor<StringCursor> as GraphQLType<DefaultScalarValue>>::name(&())
+                .as_deref(),
+            Some("VersionedStringCursor")
+        );
+    }
+
+    #[test]
+    fn test_migrates_old_versions() {
+        let decoded =
+            VersionedOffset::from_encoded_string(&encode_cursor_segments(&["v1", "page", "3"]))
+                .unwrap();
+        assert_eq!(decoded.into_inner(), OffsetCursor::new(30));
+    }
+
+    #[test]
+    fn test_unsupported_versions() {
+        // Newer than the current version, so never handed to the format.
+        assert_eq!(
+            VersionedOffset::from_encoded_string(&encode_cursor_segments(&["v3", "offset", "10"])),
+            Err(CursorError::UnsupportedVersion(3))
+        );
+
+        // Older, and no longer accepted by the format.
+        assert_eq!(
+            VersionedOffset::from_encoded_string(&encode_cursor_segments(&["v0", "offset", "10"])),
+            Err(CursorError::UnsupportedVersion(0))
+        );
+    }
+
+    #[test]
+    fn test_invalid_cursors() {
+        for raw in [
+            "offset||10",
+            "v||offset||10",
+            "vtwo||offset||10",
+            "",
+            "v1||page||x",
+        ] {
+            assert_eq!(
+                VersionedOffset::from_encoded_string(&BASE64_URL_SAFE.encode(raw)),
+                Err(CursorError::InvalidCursor),
+                "{raw}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_compare_position() {
+        let earlier = VersionedOffset::new(OffsetCursor::new(1));
+        let later = VersionedOffset::new(OffsetCursor::new(5));
+        assert_eq!(
+            earlier.compare_position(&later),
+            Some(std::cmp::Ordering::Less)
+        );
+    }
+}
@@ -0,0 +1,188 @@
+/// Implements the GraphQL scalar for a cursor wrapping another one, named after the inner cursor
+/// with `$prefix` in front, so `SignedCursor<OffsetCursor, K>` is a `SignedOffsetCursor` scalar.
+///
+/// The `GraphQLScalar` derive can only give a generic type one name, so every wrapper would share
+/// it whatever it wraps, and a schema using two of them would register different types under it.
+///
+/// The bounds are those of the wrapper's `Cursor` impl, which the scalar reads and writes through.
+macro_rules! wrapped_cursor_scalar {
+    (
+        $wrapper:ident<$inner:ident $(, $param:ident)*>,
+        prefix = $prefix:literal,
+        description = $description:literal,
+        where $($bounds:tt)*
+    ) => {
+        impl<$inner, $($param,)* __S> ::juniper::GraphQLValue<__S> for $wrapper<$inner $(, $param)*>
+        where
+            $($bounds)*,
+            $inner: ::juniper::GraphQLType<__S, TypeInfo = ()>,
+            __S: ::juniper::ScalarValue,
+        {
+            type Context = ();
+            type TypeInfo = ();
+
+            fn type_name(&self, info: &()) -> Option<::juniper::ArcStr> {
+                <Self as ::juniper::GraphQLType<__S>>::name(info)
+            }
+
+            fn resolve(
+                &self,
+                _info: &(),
+                _selection: Option<&[::juniper::Selection<'_, __S>]>,
+                _executor: &::juniper::Executor<'_, '_, (), __S>,
+            ) -> ::juniper::ExecutionResult<__S> {
+                Ok(::juniper::Value::Scalar(
+                    ::juniper::ToScalarValue::<__S>::to_scalar_value(self),
+                ))
+            }
+        }
+
+        impl<$inner, $($param,)* __S> ::juniper::GraphQLValueAsync<__S>
+            for $wrapper<$inner $(, $param)*>
+        where
+            $($bounds)*,
+            $inner: ::juniper::GraphQLType<__S, TypeInfo = ()>,
+            Self: Sync,
+            __S: ::juniper::ScalarValue + Send + Sync,
+        {
+            fn resolve_async<'b>(
+                &'b self,
+                info: &'b (),
+                selection_set: Option<&'b [::juniper::Selection<'_, __S>]>,
+                executor: &'b ::juniper::Executor<'_, '_, (), __S>,
+            ) -> ::juniper::BoxFuture<'b, ::juniper::ExecutionResult<__S>> {
+                let value = ::juniper::GraphQLValue::resolve(self, info, selection_set, executor);
+                Box::pin(::juniper::futures::future::ready(value))
+            }
+        }
+
+        impl<$inner, $($param,)* __S> ::juniper::GraphQLType<__S> for $wrapper<$inner $(, $param)*>
+        where
+            $($bounds)*,
+            $inner: ::juniper::GraphQLType<__S, TypeInfo = ()>,
+            __S: ::juniper::ScalarValue,
+        {
+            fn name(info: &()) -> Option<::juniper::ArcStr> {
+                $inner::name(info).map(|cursor| format!(concat!($prefix, "{}"), cursor).into())
+            }
+
+            fn meta(
+                info: &(),
+                registry: &mut ::juniper::Registry<__S>,
+            ) -> ::juniper::meta::MetaType<__S> {
+                registry
+                    .build_scalar_type::<Self>(info)
+                    .description($description)
+                    .into_meta()
+            }
+        }
+
+        impl<$inner, $($param,)* __S> ::juniper::ToScalarValue<__S>
+            for $wrapper<$inner $(, $param)*>
+        where
+            $($bounds)*,
+            __S: ::juniper::ScalarValue,
+        {
+            fn to_scalar_value(&self) -> __S {
+                __S::from($crate::Cursor::to_output(self))
+            }
+        }
+
+        impl<$inner, $($param,)* __S> ::juniper::ToInputValue<__S> for $wrapper<$inner $(, $param)*>
+        where
+            $($bounds)*,
+            __S: ::juniper::ScalarValue,
+        {
+            fn to_input_value(&self) -> ::juniper::InputValue<__S> {
+                ::juniper::InputValue::Scalar(::juniper::ToScalarValue::<__S>::to_scalar_value(
+                    self,
+                ))
+            }
+        }
+
+        impl<$inner, $($param,)* __S> ::juniper::FromInputValue<__S>
+            for $wrapper<$inner $(, $param)*>
+        where
+            $($bounds)*,
+            __S: ::juniper::ScalarValue,
+        {
+            type Error = ::juniper::FieldError<__S>;
+
+            fn from_input_value(
+                value: &::juniper::InputValue<__S>,
+            ) -> Result<Self, ::juniper::FieldError<__S>> {
+                let input = value
+                    .as_scalar()
+                    .and_then(::juniper::ScalarValue::try_as_str)
+                    .ok_or_else(|| {
+                        ::juniper::FieldError::<__S>::from(format!(
+                            "Expected the cursor as a string, found: {value}"
+                        ))
+                    })?;
+                <Self as $crate::Cursor>::from_input(input)
+                    .map_err(|err| ::juniper::FieldError::<__S>::from(err.to_string()))
+            }
+        }
+
+        impl<$inner, $($param,)* __S> ::juniper::ParseScalarValue<__S>
+            for $wrapper<$inner $(, $param)*>
+        where
+            $($bounds)*,
+            __S: ::juniper::ScalarValue,
+        {
+            fn from_str(
+                token: ::juniper::parser::ScalarToken<'_>,
+            ) -> ::juniper::ParseScalarResult<__S> {
+                <Self as $crate::Cursor>::parse_token(token)
+            }
+        }
+
+        impl<$inner, $($param,)* __S> ::juniper::marker::IsInputType<__S>
+            for $wrapper<$inner $(, $param)*>
+        where
+            $($bounds)*,
+            $inner: ::juniper::GraphQLType<__S, TypeInfo = ()>,
+            __S: ::juniper::ScalarValue,
+        {
+        }
+
+        impl<$inner, $($param,)* __S> ::juniper::marker::IsOutputType<__S>
+            for $wrapper<$inner $(, $param)*>
+        where
+            $($bounds)*,
+            $inner: ::juniper::GraphQLType<__S, TypeInfo = ()>,
+            __S: ::juniper::ScalarValue,
+        {
+        }
+
+        // Used by Juniper's compile time checks of interface fields. The name has to be a constant,
+        // so it can't include the inner cursor's, which makes wrappers of different cursors look
+        // alike there.
+        impl<$inner, $($param,)* __S> ::juniper::macros::reflect::BaseType<__S>
+            for $wrapper<$inner $(, $param)*>
+        where
+            $($bounds)*,
+        {
+            const NAME: ::juniper::macros::reflect::Type = stringify!($wrapper);
+        }
+
+        impl<$inner, $($param,)* __S> ::juniper::macros::reflect::BaseSubTypes<__S>
+            for $wrapper<$inner $(, $param)*>
+        where
+            $($bounds)*,
+        {
+            const NAMES: ::juniper::macros::reflect::Types =
+                &[<Self as ::juniper::macros::reflect::BaseType<__S>>::NAME];
+        }
+
+        impl<$inner, $($param,)* __S> ::juniper::macros::reflect::WrappedType<__S>
+            for $wrapper<$inner $(, $param)*>
+        where
+            $($bounds)*,
+        {
+            const VALUE: ::juniper::macros::reflect::WrappedValue = 1;
+        }
+    };
+}
+
+pub(crate) use wrapped_cursor_scalar;
@@ -0,0 +1,341 @@
+use crate::page_info_factory::PageInfoParts;
+use crate::{Cursor, CursorError, CursorProvider, PageInfoFactory, PaginationMetadata};
+
+/// A cursor with its type erased, for when all that's needed is the encoded string.
+pub trait ErasedCursor {
+    /// As `Cursor::to_encoded_string`.
+    fn to_encoded_string(&self) -> String;
+}
+
+impl<C: Cursor> ErasedCursor for C {
+    fn to_encoded_string(&self) -> String {
+        Cursor::to_encoded_string(self)
+    }
+}
+
+/// Object safe version of `CursorProvider`, so a provider can be picked at runtime and used as
+/// `&dyn DynCursorProvider` or `Box<dyn DynCursorProvider>`. Every `CursorProvider` implements it,
+/// and both of those implement `CursorProvider` in turn, so they can be handed straight to the
+/// connection constructors:
+///
+/// ```
+/// use juniper::GraphQLObject;
+/// use juniper_relay_helpers::{
+///     DynCursorProvider, FnCursorProvider, OffsetCursor, OffsetCursorProvider, RelayConnection,
+/// };
+///
+/// #[derive(Clone, GraphQLObject, RelayConnection)]
+/// #[relay(cursor = OffsetCursor)]
+/// pub struct Canvas {
+///     pub name: String,
+/// }
+///
+/// fn provider(spaced: bool) -> Box<dyn DynCursorProvider<Canvas, OffsetCursor>> {
+///     if spaced {
+///         Box::new(FnCursorProvider::new(|_, idx, _: &Canvas| OffsetCursor::new(idx * 100)))
+///     } else {
+///         Box::new(OffsetCursorProvider::new())
+///     }
+/// }
+///
+/// let items = [Some(Canvas { name: "Lumière".to_string() })];
+/// let conn = CanvasRelayConnection::new(Some(&items), None, provider(true), None);
+/// ```
+///
+/// The cursor type is a parameter rather than an associated type, as the connection it feeds
+/// fixes it anyway. For the encoded cursor alone, `erased_cursor_for_item` returns it as a
+/// `Box<dyn ErasedCursor>`.
+pub trait DynCursorProvider<ItemT, CursorT: Cursor> {
+    /// As `CursorProvider::get_cursor_for_item`.
+    fn dyn_cursor_for_item(
+        &self,
+        metadata: &PaginationMetadata<CursorT>,
+        item_idx: i32,
+        item: Option<&ItemT>,
+    ) -> CursorT;
+
+    /// As `CursorProvider::get_cursors_for_items`.
+    fn dyn_cursors_for_items(
+        &self,
+        metadata: &PaginationMetadata<CursorT>,
+        items: &[Option<ItemT>],
+    ) -> Vec<CursorT>;
+
+    /// As `CursorProvider::try_get_cursor_for_item`.
+    fn dyn_try_cursor_for_item(
+        &self,
+        metadata: &PaginationMetadata<CursorT>,
+        item_idx: i32,
+        item: Option<&ItemT>,
+    ) -> Result<CursorT, CursorError>;
+
+    /// As `CursorProvider::get_page_info`, returning the plain values.
+    fn dyn_page_info(
+        &self,
+        metadata: &PaginationMetadata<CursorT>,
+        items: Option<&[Option<ItemT>]>,
+    ) -> PageInfoParts<CursorT>;
+
+    /// As `CursorProvider::get_page_info_for_cursors`, returning the plain values.
+    fn dyn_page_info_for_cursors(
+        &self,
+        metadata: &PaginationMetadata<CursorT>,
+        items: Option<&[Option<ItemT>]>,
+        cursors: &[CursorT],
+    ) -> PageInfoParts<CursorT>;
+
+    /// As `CursorProvider::try_get_page_info`, returning the plain values.
+    fn dyn_try_page_info(
+        &self,
+        metadata: &PaginationMetadata<CursorT>,
+        items: Option<&[Option<ItemT>]>,
+    ) -> Result<PageInfoParts<CursorT>, CursorError>;
+
+    /// As `CursorProvider::total_count`.
+    fn dyn_total_count(&self, metadata: &PaginationMetadata<CursorT>) -> Option<i32>;
+
+    /// The cursor for an item, with its type erased.
+    fn erased_cursor_for_item(
+        &self,
+        metadata: &PaginationMetadata<CursorT>,
+        item_idx: i32,
+        item: Option<&ItemT>,
+    ) -> Box<dyn ErasedCursor>
+    where
+        CursorT: 'static,
+    {
+        Box::new(self.dyn_cursor_for_item(metadata, item_idx, item))
+    }
+}
+
+impl<ItemT, P> DynCursorProvider<ItemT, P::CursorType> for P
+where
+    P: CursorProvider<ItemT>,
+{
+    fn dyn_cursor_for_item(
+        &self,
+        metadata: &PaginationMetadata<P::CursorType>,
+        item_idx: i32,
+        item: Option<&ItemT>,
+    ) -> P::CursorType {
+        self.get_cursor_for_item(metadata, item_idx, item)
+    }
+
+    fn dyn_cursors_for_items(
+        &self,
+        metadata: &PaginationMetadata<P::CursorType>,
+        items: &[Option<ItemT>],
+    ) -> Vec<P::CursorType> {
+        self.get_cursors_for_items(metadata, items)
+    }
+
+    fn dyn_try_cursor_for_item(
+        &self,
+        metadata: &PaginationMetadata<P::CursorType>,
+        item_idx: i32,
+        item: Option<&ItemT>,
+    ) -> Result<P::CursorType, CursorError> {
+        self.try_get_cursor_for_item(metadata, item_idx, item)
+    }
+
+    fn dyn_page_info(
+        &self,
+        metadata: &PaginationMetadata<P::CursorType>,
+        items: Option<&[Option<ItemT>]>,
+    ) -> PageInfoParts<P::CursorType> {
+        self.get_page_info(metadata, items)
+    }
+
+    fn dyn_page_info_for_cursors(
+        &self,
+        metadata: &PaginationMetadata<P::CursorType>,
+        items: Option<&[Option<ItemT>]>,
+        cursors: &[P::CursorType],
+    ) -> PageInfoParts<P::CursorType> {
+        self.get_page_info_for_cursors(metadata, items, cursors)
+    }
+
+    fn dyn_try_page_info(
+        &self,
+        metadata: &PaginationMetadata<P::CursorType>,
+        items: Option<&[Option<ItemT>]>,
+    ) -> Result<PageInfoParts<P::CursorType>, CursorError> {
+        self.try_get_page_info(metadata, items)
+    }
+
+    fn dyn_total_count(&self, metadata: &PaginationMetadata<P::CursorType>) -> Option<i32> {
+        self.total_count(metadata)
+    }
+}
+
+/// Implements `CursorProvider` for a `dyn DynCursorProvider` behind a pointer.
+macro_rules! dyn_cursor_provider {
+    ($($provider:ty),+) => {$(
+        impl<ItemT, CursorT: Cursor> CursorProvider<ItemT> for $provider {
+            type CursorType = CursorT;
+
+            fn get_cursor_for_item(
+                &self,
+                metadata: &PaginationMetadata<CursorT>,
+                item_idx: i32,
+                item: Option<&ItemT>,
+            ) -> CursorT {
+                (**self).dyn_cursor_for_item(metadata, item_idx, item)
+            }
+
+            fn get_cursors_for_items(
+                &self,
+                metadata: &PaginationMetadata<CursorT>,
+                items: &[Option<ItemT>],
+            ) -> Vec<CursorT> {
+                (**self).dyn_cursors_for_items(metadata, items)
+            }
+
+            fn get_page_info<PageInfoType>(
+                &self,
+                metadata: &PaginationMetadata<CursorT>,
+                items: Option<&[Option<ItemT>]>,
+            ) -> PageInfoType
+            where
+                PageInfoType: PageInfoFactory<CursorT>,
+            {
+                (**self).dyn_page_info(metadata, items).into_page_info()
+            }
+
+            fn get_page_info_for_cursors<PageInfoType>(
+                &self,
+                metadata: &PaginationMetadata<CursorT>,
+                items: Option<&[Option<ItemT>]>,
+                cursors: &[CursorT],
+            ) -> PageInfoType
+            where
+                PageInfoType: PageInfoFactory<CursorT>,
+            {
+                (**self)
+                    .dyn_page_info_for_cursors(metadata, items, cursors)
+                    .into_page_info()
+            }
+
+            fn total_count(&self, metadata: &PaginationMetadata<CursorT>) -> Option<i32> {
+                (**self).dyn_total_count(metadata)
+            }
+
+            fn try_get_cursor_for_item(
+                &self,
+                metadata: &PaginationMetadata<CursorT>,
+                item_idx: i32,
+                item: Option<&ItemT>,
+            ) -> Result<CursorT, CursorError> {
+                (**self).dyn_try_cursor_for_item(metadata, item_idx, item)
+            }
+
+            fn try_get_page_info<PageInfoType>(
+                &self,
+                metadata: &PaginationMetadata<CursorT>,
+                items: Option<&[Option<ItemT>]>,
+            ) -> Result<PageInfoType, CursorError>
+            where
+                PageInfoType: PageInfoFactory<CursorT>,
+            {
+                Ok((**self).dyn_try_page_info(metadata, items)?.into_page_info())
+            }
+        }
+    )+};
+}
+
+dyn_cursor_provider!(
+    &dyn DynCursorProvider<ItemT, CursorT>,
+    Box<dyn DynCursorProvider<ItemT, CursorT>>
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CursorByKey, CursorError, DynCursorProvider, FnCursorProvider, KeyedCursorProvider,
+        OffsetCursor, OffsetCursorProvider, PageRequest, PaginationMetadata, RelayConnection,
+        StringCursor,
+    };
+    use juniper::GraphQLObject;
+
+    #[derive(Debug, Clone, GraphQLObject, RelayConnection)]
+    #[relay(cursor = OffsetCursor)]
+    pub struct Canvas {
+        name: String,
+    }
+
+    #[derive(Debug, Clone, GraphQLObject, RelayConnection)]
+    pub struct Chroma {
+        id: String,
+    }
+    impl CursorByKey for Chroma {
+        fn cursor_key(&self) -> String {
+            self.id.clone()
+        }
+    }
+
+    fn canvases() -> Vec<Option<Canvas>> {
+        ["Lumière", "Old Lumière", "Monolith"]
+            .into_iter()
+            .map(|name| {
+                Some(Canvas {
+                    name: name.to_string(),
+                })
+            })
+            .collect()
+    }
+
+    fn provider(spaced: bool) -> Box<dyn DynCursorProvider<Canvas, OffsetCursor>> {
+        if spaced {
+            Box::new(FnCursorProvider::new(|_, idx, _: &Canvas| {
+                OffsetCursor::new(idx * 100)
+            }))
+        } else {
+            Box::new(OffsetCursorProvider::new())
+        }
+    }
+
+    #[test]
+    fn test_provider_chosen_at_runtime() {
+        let request = Some(PageRequest::new(Some(3), None, None));
+
+        let conn = CanvasRelayConnection::new(
+            Some(&canvases()),
+            Some(3),
+            provider(false),
+            request.clone(),
+        );
+        assert_eq!(conn.page_info.end_cursor, Some(OffsetCursor::new(2)));
+        assert!(!conn.page_info.has_next_page);
+
+        let spaced = provider(true);
+        let conn = CanvasRelayConnection::new(Some(&canvases()), Some(3), &*spaced, request);
+        assert_eq!(conn.page_info.end_cursor, Some(OffsetCursor::new(200)));
+    }
+
+    #[test]
+    fn test_try_methods_forwarded() {
+        let provider: &dyn DynCursorProvider<Chroma, StringCursor> = &KeyedCursorProvider;
+        let items = vec![Some(Chroma { id: String::new() })];
+
+        let conn = ChromaRelayConnection::try_new(Some(&items), None, provider, None);
+        assert_eq!(conn.err(), Some(CursorError::MissingKey));
+    }
+
+    #[test]
+    fn test_erased_cursor() {
+        let metadata = PaginationMetadata {
+            total_count: None,
+            page_request: None,
+            more_available: None,
+        };
+        let canvas = Canvas {
+            name: "Lumière".to_string(),
+        };
+
+        let erased = provider(false).erased_cursor_for_item(&metadata, 4, Some(&canvas));
+        assert_eq!(
+            erased.to_encoded_string(),
+            crate::Cursor::to_encoded_string(&OffsetCursor::new(4))
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::Cursor;
+use std::convert::Infallible;
 
 /// Trait encapsulating common parts of a Relay Edge.
 pub trait RelayEdge {
@@ -7,4 +8,61 @@ pub trait RelayEdge {
 
     /// New type taking a Cursor implementation
     fn new(node: Option<Self::NodeType>, cursor: Self::CursorType) -> Self;
+
+    /// The node at this edge, if any.
+    fn node(&self) -> Option<&Self::NodeType>;
+
+    /// The cursor for this edge, if any.
+    fn cursor(&self) -> Option<&Self::CursorType>;
+
+    /// Builds an edge from its node and cursor as they are, including a missing cursor. Returns `None`
+    /// if the cursor is missing and the edge can't be without one, as with `non_null_cursor`.
+    fn try_from_parts(
+        node: Option<Self::NodeType>,
+        cursor: Option<Self::CursorType>,
+    ) -> Option<Self>
+    where
+        Self: Sized;
+
+    /// Takes the edge apart into its node and cursor.
+    fn into_parts(self) -> (Option<Self::NodeType>, Option<Self::CursorType>);
+}
+
+/// Converts edges from one node type to another with `f`, keeping each edge's cursor. Useful for
+/// turning edges of database rows into edges of the DTOs returned to the client.
+///
+/// Missing edges and missing nodes stay missing, and `f` isn't called for them. An edge without a
+/// cursor becomes a missing edge if the target edge type needs one (see `RelayEdge::try_from_parts`).
+pub fn map_edges<FromT, ToT, F>(edges: Vec<Option<FromT>>, mut f: F) -> Vec<Option<ToT>>
+where
+    FromT: RelayEdge,
+    ToT: RelayEdge<CursorType = FromT::CursorType>,
+    F: FnMut(FromT::NodeType) -> ToT::NodeType,
+{
+    match try_map_edges(edges, |node| Ok::<_, Infallible>(f(node))) {
+        Ok(edges) => edges,
+        Err(never) => match never {},
+    }
+}
+
+/// As `map_edges`, for conversions that can fail. Stops at the first error and returns it.
+pub fn try_map_edges<FromT, ToT, F, ErrorT>(
+    edges: Vec<Option<FromT>>,
+    mut f: F,
+) -> Result<Vec<Option<ToT>>, ErrorT>
+where
+    FromT: RelayEdge,
+    ToT: RelayEdge<CursorType = FromT::CursorType>,
+    F: FnMut(FromT::NodeType) -> Result<ToT::NodeType, ErrorT>,
+{
+    edges
+        .into_iter()
+        .map(|edge| match edge {
+            Some(edge) => {
+                let (node, cursor) = edge.into_parts();
+                Ok(ToT::try_from_parts(node.map(&mut f).transpose()?, cursor))
+            }
+            None => Ok(None),
+        })
+        .collect()
 }
@@ -0,0 +1,724 @@
+use crate::page_info_factory::PageInfoParts;
+use crate::{
+    CURSOR_SEGMENT_DELIMITER, Cursor, CursorError, CursorProvider, PageInfoFactory, PageRequest,
+    PaginationMetadata,
+};
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::prelude::*;
+use juniper::GraphQLScalar;
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+
+/// Length of the AES-GCM nonce prefixed to the ciphertext.
+const NONCE_LEN: usize = 12;
+
+/// Supplies the AES-256 key used to encrypt and decrypt an `EncryptedCursor`.
+///
+/// As with `CursorSigningKey`, the key is provided by a type since cursors are decoded without access
+/// to your app state.
+///
+/// ```
+/// use juniper_relay_helpers::CursorEncryptionKey;
+/// use std::sync::OnceLock;
+///
+/// static CURSOR_KEY: OnceLock<[u8; 32]> = OnceLock::new();
+///
+/// pub struct AppCursorKey;
+/// impl CursorEncryptionKey for AppCursorKey {
+///     fn key() -> &'static [u8; 32] {
+///         CURSOR_KEY.get_or_init(|| *b"load-me-from-config-32-bytes-lng")
+///     }
+/// }
+/// ```
+pub trait CursorEncryptionKey {
+    /// The 256 bit AES key.
+    fn key() -> &'static [u8; 32];
+}
+
+/// An AES-256 key held as a value, for keys that are only known at runtime. Encrypts cursors the
+/// same way as an `EncryptedCursor`, and is what an `EncryptedCursorProvider` is built with.
+///
+/// ```
+/// use juniper_relay_helpers::{CursorCipher, CursorError, OffsetCursor};
+///
+/// let cipher = CursorCipher::new(b"not-a-real-key-not-a-real-key-!!");
+/// let token = cipher.encrypt(&OffsetCursor::new(10));
+/// assert_eq!(cipher.decrypt::<OffsetCursor>(&token), Ok(OffsetCursor::new(10)));
+///
+/// let other = CursorCipher::new(b"another-key-another-key-another!");
+/// assert_eq!(
+///     other.decrypt::<OffsetCursor>(&token),
+///     Err(CursorError::DecryptionFailed)
+/// );
+/// ```
+#[derive(Clone)]
+pub struct CursorCipher {
+    cipher: Aes256Gcm,
+}
+
+impl CursorCipher {
+    pub fn new(key: &[u8; 32]) -> Self {
+        CursorCipher {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)),
+        }
+    }
+
+    /// Encrypts the cursor's raw string, returning the base64 encoded nonce and ciphertext.
+    pub fn encrypt<C: Cursor>(&self, cursor: &C) -> String {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, cursor.to_raw_string().as_bytes())
+            .expect("AES-GCM encryption of an in-memory cursor can't fail");
+
+        let mut bytes = nonce.to_vec();
+        bytes.extend(ciphertext);
+        BASE64_URL_SAFE.encode(bytes)
+    }
+
+    /// Decrypts a string from `encrypt` back into the cursor. Returns
+    /// `CursorError::DecryptionFailed` if it was tampered with or encrypted with another key.
+    pub fn decrypt<C: Cursor<CursorType = C>>(&self, encrypted: &str) -> Result<C, CursorError> {
+        let bytes = BASE64_URL_SAFE
+            .decode(encrypted)
+            .map_err(|_| CursorError::DecryptionFailed)?;
+        self.decrypt_bytes(&bytes)
+    }
+
+    fn decrypt_bytes<C: Cursor<CursorType = C>>(&self, bytes: &[u8]) -> Result<C, CursorError> {
+        if bytes.len() < NONCE_LEN {
+            return Err(CursorError::DecryptionFailed);
+        }
+        let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| CursorError::DecryptionFailed)?;
+
+        let inner_raw = String::from_utf8(plaintext)?;
+        C::new(
+            &inner_raw,
+            inner_raw.split(CURSOR_SEGMENT_DELIMITER).collect(),
+        )
+    }
+}
+
+impl Debug for CursorCipher {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CursorCipher").finish_non_exhaustive()
+    }
+}
+
+/// Wraps another cursor and encrypts it with AES-256-GCM, so clients can neither read nor alter its
+/// contents. Use it when cursors carry things like internal offsets or keys that shouldn't be
+/// visible, where a `SignedCursor` would only stop them being changed.
+///
+/// The encoded cursor is the base64 of a random nonce followed by the ciphertext of the inner
+/// cursor's raw string. A fresh nonce is used every time it's encoded, so the same cursor encodes
+/// differently each time. Decoding a cursor that's been tampered with, or was encrypted with another
+/// key, returns `CursorError::DecryptionFailed`.
+///
+/// ```
+/// use juniper_relay_helpers::{Cursor, CursorEncryptionKey, EncryptedCursor, OffsetCursor};
+///
+/// struct TestKey;
+/// impl CursorEncryptionKey for TestKey {
+///     fn key() -> &'static [u8; 32] {
+///         b"not-a-real-key-not-a-real-key-!!"
+///     }
+/// }
+///
+/// let cursor = EncryptedCursor::<OffsetCursor, TestKey>::new(OffsetCursor::new(10));
+/// let encoded = cursor.to_encoded_string();
+/// let decoded = EncryptedCursor::<OffsetCursor, TestKey>::from_encoded_string(&encoded);
+/// assert_eq!(decoded.unwrap().inner, OffsetCursor::new(10));
+/// ```
+///
+/// It's named after the inner cursor in the schema, so `EncryptedCursor<OffsetCursor, K>` is an
+/// `EncryptedOffsetCursor` scalar, and encrypting different cursors gives different scalars.
+///
+/// Requires the `encryption` feature.
+pub struct EncryptedCursor<C, K>
+where
+    C: Cursor<CursorType = C>,
+    K: CursorEncryptionKey,
+{
+    /// The cursor that was encrypted.
+    pub inner: C,
+
+    _key: PhantomData<fn() -> K>,
+}
+
+impl<C, K> EncryptedCursor<C, K>
+where
+    C: Cursor<CursorType = C>,
+    K: CursorEncryptionKey,
+{
+    pub fn new(inner: C) -> Self {
+        EncryptedCursor {
+            inner,
+            _key: PhantomData,
+        }
+    }
+
+    /// Unwraps the encrypted cursor into the inner one.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+
+    fn cipher() -> CursorCipher {
+        CursorCipher::new(K::key())
+    }
+}
+
+impl<C, K> Cursor for EncryptedCursor<C, K>
+where
+    C: Cursor<CursorType = C>,
+    K: CursorEncryptionKey,
+{
+    type CursorType = EncryptedCursor<C, K>;
+
+    /// The raw string is already opaque: the base64 encoded nonce and ciphertext.
+    fn to_raw_string(&self) -> String {
+        Self::cipher().encrypt(&self.inner)
+    }
+
+    fn new(raw: &str, _parts: Vec<&str>) -> Result<Self::CursorType, CursorError> {
+        Self::cipher().decrypt(raw).map(EncryptedCursor::new)
+    }
+
+    fn from_raw_bytes(bytes: &[u8]) -> Result<Self::CursorType, CursorError> {
+        Self::cipher()
+            .decrypt_bytes(bytes)
+            .map(EncryptedCursor::new)
+    }
+
+    fn compare_position(&self, other: &Self) -> Option<Ordering> {
+        self.inner.compare_position(&other.inner)
+    }
+
+    fn to_encoded_string(&self) -> String {
+        self.to_raw_string()
+    }
+}
+
+crate::wrapped_cursor_scalar!(
+    EncryptedCursor<C, K>,
+    prefix = "Encrypted",
+    description = "A cursor encrypted so clients can't read what it holds.",
+    where C: Cursor<CursorType = C>, K: CursorEncryptionKey
+);
+
+impl<C, K> Clone for EncryptedCursor<C, K>
+where
+    C: Cursor<CursorType = C>,
+    K: CursorEncryptionKey,
+{
+    fn clone(&self) -> Self {
+        EncryptedCursor::new(self.inner.clone())
+    }
+}
+
+impl<C, K> PartialEq for EncryptedCursor<C, K>
+where
+    C: Cursor<CursorType = C> + PartialEq,
+    K: CursorEncryptionKey,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<C, K> Eq for EncryptedCursor<C, K>
+where
+    C: Cursor<CursorType = C> + Eq,
+    K: CursorEncryptionKey,
+{
+}
+
+impl<C, K> Debug for EncryptedCursor<C, K>
+where
+    C: Cursor<CursorType = C> + Debug,
+    K: CursorEncryptionKey,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EncryptedCursor")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+// -------------- EncryptedCursorProvider ---------------
+
+/// A cursor built by an `EncryptedCursorProvider`: the inner cursor encrypted with the provider's
+/// key.
+///
+/// Decoding it needs the key, which a cursor read from GraphQL input doesn't have, so it's only
+/// checked to look like a token. Use `EncryptedCursorProvider::decrypt_page_request` to decrypt it.
+#[derive(Debug, GraphQLScalar, Clone, Eq, PartialEq, Hash)]
+#[graphql(
+    name = "EncryptedToken",
+    to_output_with = Self::to_output,
+    from_input_with = Self::from_input
+)]
+pub struct EncryptedToken {
+    /// The base64 encoded nonce and ciphertext.
+    pub token: String,
+}
+
+impl EncryptedToken {
+    pub fn new(token: String) -> Self {
+        EncryptedToken { token }
+    }
+}
+
+impl Cursor for EncryptedToken {
+    type CursorType = EncryptedToken;
+
+    fn to_raw_string(&self) -> String {
+        self.token.clone()
+    }
+
+    fn new(raw: &str, _parts: Vec<&str>) -> Result<EncryptedToken, CursorError> {
+        let is_token = !raw.is_empty()
+            && raw
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'='));
+        if is_token {
+            Ok(EncryptedToken::new(raw.to_string()))
+        } else {
+            Err(CursorError::InvalidCursor)
+        }
+    }
+
+    fn from_encoded_string(input: &str) -> Result<EncryptedToken, CursorError> {
+        <EncryptedToken as Cursor>::new(input, vec![input])
+    }
+
+    fn to_encoded_string(&self) -> String {
+        self.token.clone()
+    }
+}
+
+/// Wraps another cursor provider, encrypting every cursor it builds with a key given when it's
+/// created. Use it instead of `EncryptedCursor` when the key is only known at runtime, such as
+/// one loaded per tenant.
+///
+/// The cursors are `EncryptedToken`s, which can't be decrypted as they're read from GraphQL input.
+/// Resolvers decrypt the incoming ones with `decrypt_page_request` before querying, which rejects
+/// tampered tokens, or ones encrypted with another key, with `CursorError::DecryptionFailed`:
+///
+/// ```
+/// use juniper::GraphQLObject;
+/// use juniper_relay_helpers::{
+///     EncryptedCursorProvider, EncryptedToken, OffsetCursor, OffsetCursorProvider, PageRequest,
+///     RelayConnection,
+/// };
+///
+/// #[derive(Clone, GraphQLObject, RelayConnection)]
+/// #[relay(cursor = EncryptedToken)]
+/// pub struct Note {
+///     pub text: String,
+/// }
+///
+/// let key = *b"not-a-real-key-not-a-real-key-!!";
+/// let provider = EncryptedCursorProvider::new(OffsetCursorProvider::new(), &key);
+///
+/// let request = PageRequest::<EncryptedToken>::new(Some(1), None, None);
+/// let offsets: PageRequest<OffsetCursor> = provider.decrypt_page_request(&request).unwrap();
+/// // ... query with `offsets` ...
+/// let notes = vec![Some(Note { text: "Tomorrow comes".to_string() })];
+///
+/// let conn = NoteRelayConnection::new(Some(&notes), Some(3), provider, Some(request));
+/// let token = conn.page_info.end_cursor.unwrap();
+///
+/// let provider = EncryptedCursorProvider::new(OffsetCursorProvider::new(), &key);
+/// assert_eq!(provider.decrypt::<OffsetCursor>(&token), Ok(OffsetCursor::new(0)));
+/// ```
+pub struct EncryptedCursorProvider<P> {
+    inner: P,
+    cipher: CursorCipher,
+}
+
+impl<P> EncryptedCursorProvider<P> {
+    pub fn new(inner: P, key: &[u8; 32]) -> Self {
+        Self::with_cipher(inner, CursorCipher::new(key))
+    }
+
+    /// Builds the provider from a `CursorCipher`, to share one between providers.
+    pub fn with_cipher(inner: P, cipher: CursorCipher) -> Self {
+        EncryptedCursorProvider { inner, cipher }
+    }
+
+    /// Decrypts a token this provider built back into the inner provider's cursor.
+    pub fn decrypt<C: Cursor<CursorType = C>>(
+        &self,
+        token: &EncryptedToken,
+    ) -> Result<C, CursorError> {
+        self.cipher.decrypt(&token.token)
+    }
+
+    /// Decrypts the `after` and `before` tokens of a request, for querying with. Returns
+    /// `CursorError::DecryptionFailed` if either was tampered with or encrypted with another key.
+    pub fn decrypt_page_request<C: Cursor<CursorType = C>>(
+        &self,
+        page_request: &PageRequest<EncryptedToken>,
+    ) -> Result<PageRequest<C>, CursorError> {
+        let decrypt = |token: &Option<EncryptedToken>| {
+            token.as_ref().map(|token| self.decrypt(token)).transpose()
+        };
+
+        Ok(PageRequest {
+            first: page_request.first,
+            after: decrypt(&page_request.after)?,
+            before: decrypt(&page_request.before)?,
+            last: page_request.last,
+        })
+    }
+
+    fn encrypt<C: Cursor>(&self, cursor: &C) -> EncryptedToken {
+        EncryptedToken::new(self.cipher.encrypt(cursor))
+    }
+
+    fn encrypted_page_info<C, PageInfoType>(&self, inner: PageInfoParts<C>) -> PageInfoType
+    where
+        C: Cursor,
+        PageInfoType: PageInfoFactory<EncryptedToken>,
+    {
+        PageInfoType::new(
+            inner.has_prev_page,
+            inner.has_next_page,
+            inner.start_cursor.map(|cursor| self.encrypt(&cursor)),
+            inner.end_cursor.map(|cursor| self.encrypt(&cursor)),
+        )
+    }
+
+    /// The metadata for the inner provider. Tokens that can't be decrypted are dropped, so
+    /// resolvers should check them with `decrypt_page_request` first.
+    fn decrypted_metadata<C: Cursor<CursorType = C>>(
+        &self,
+        metadata: &PaginationMetadata<EncryptedToken>,
+    ) -> PaginationMetadata<C> {
+        let decrypt = |token: &Option<EncryptedToken>| {
+            token.as_ref().and_then(|token| self.decrypt(token).ok())
+        };
+
+        PaginationMetadata {
+            total_count: metadata.total_count,
+            page_request: metadata.page_request.as_ref().map(|pr| PageRequest {
+                first: pr.first,
+                after: decrypt(&pr.after),
+                before: decrypt(&pr.before),
+                last: pr.last,
+            }),
+            more_available: metadata.more_available,
+        }
+    }
+}
+
+impl<ItemT, P> CursorProvider<ItemT> for EncryptedCursorProvider<P>
+where
+    P: CursorProvider<ItemT>,
+    P::CursorType: Cursor<CursorType = P::CursorType>,
+{
+    type CursorType = EncryptedToken;
+
+    fn get_cursor_for_item(
+        &self,
+        metadata: &PaginationMetadata<EncryptedToken>,
+        item_idx: i32,
+        item: Option<&ItemT>,
+    ) -> EncryptedToken {
+        self.encrypt(&self.inner.get_cursor_for_item(
+            &self.decrypted_metadata(metadata),
+            item_idx,
+            item,
+        ))
+    }
+
+    fn get_cursors_for_items(
+        &self,
+        metadata: &PaginationMetadata<EncryptedToken>,
+        items: &[Option<ItemT>],
+    ) -> Vec<EncryptedToken> {
+        self.inner
+            .get_cursors_for_items(&self.decrypted_metadata(metadata), items)
+            .iter()
+            .map(|cursor| self.encrypt(cursor))
+            .collect()
+    }
+
+    fn total_count(&self, metadata: &PaginationMetadata<EncryptedToken>) -> Option<i32> {
+        self.inner.total_count(&self.decrypted_metadata(metadata))
+    }
+
+    fn try_get_cursor_for_item(
+        &self,
+        metadata: &PaginationMetadata<EncryptedToken>,
+        item_idx: i32,
+        item: Option<&ItemT>,
+    ) -> Result<EncryptedToken, CursorError> {
+        self.inner
+            .try_get_cursor_for_item(&self.decrypted_metadata(metadata), item_idx, item)
+            .map(|cursor| self.encrypt(&cursor))
+    }
+
+    fn get_page_info<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<EncryptedToken>,
+        items: Option<&[Option<ItemT>]>,
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<EncryptedToken>,
+    {
+        let inner: PageInfoParts<P::CursorType> = self
+            .inner
+            .get_page_info(&self.decrypted_metadata(metadata), items);
+
+        self.encrypted_page_info(inner)
+    }
+
+    fn get_page_info_for_cursors<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<EncryptedToken>,
+        items: Option<&[Option<ItemT>]>,
+        cursors: &[EncryptedToken],
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<EncryptedToken>,
+    {
+        // The edge tokens were all encrypted by this provider, so they decrypt.
+        let inner_cursors: Vec<P::CursorType> = cursors
+            .iter()
+            .filter_map(|token| self.decrypt(token).ok())
+            .collect();
+        let inner: PageInfoParts<P::CursorType> = self.inner.get_page_info_for_cursors(
+            &self.decrypted_metadata(metadata),
+            items,
+            &inner_cursors,
+        );
+
+        // The start and end cursors are those of the first and last edges, so they reuse their
+        // tokens rather than being encrypted again under a different nonce.
+        let reuse = |cursor: Option<P::CursorType>, edge: Option<&EncryptedToken>| {
+            cursor.map(|cursor| match edge {
+                Some(edge) => edge.clone(),
+                None => self.encrypt(&cursor),
+            })
+        };
+        PageInfoType::new(
+            inner.has_prev_page,
+            inner.has_next_page,
+            reuse(inner.start_cursor, cursors.first()),
+            reuse(inner.end_cursor, cursors.last()),
+        )
+    }
+
+    fn try_get_page_info<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<EncryptedToken>,
+        items: Option<&[Option<ItemT>]>,
+    ) -> Result<PageInfoType, CursorError>
+    where
+        PageInfoType: PageInfoFactory<EncryptedToken>,
+    {
+        let inner: PageInfoParts<P::CursorType> = self
+            .inner
+            .try_get_page_info(&self.decrypted_metadata(metadata), items)?;
+        Ok(self.encrypted_page_info(inner))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Cursor, CursorCipher, CursorEncryptionKey, CursorError, EncryptedCursor,
+        EncryptedCursorProvider, EncryptedToken, OffsetCursor, OffsetCursorProvider, PageRequest,
+        RelayConnection, StringCursor,
+    };
+    use base64::prelude::*;
+    use juniper::{DefaultScalarValue, GraphQLObject, GraphQLType};
+
+    struct TestKey;
+    impl CursorEncryptionKey for TestKey {
+        fn key() -> &'static [u8; 32] {
+            b"0123456789abcdef0123456789abcdef"
+        }
+    }
+
+    struct OtherKey;
+    impl CursorEncryptionKey for OtherKey {
+        fn key() -> &'static [u8; 32] {
+            b"fedcba9876543210fedcba9876543210"
+        }
+    }
+
+    type EncryptedOffset = EncryptedCursor<OffsetCursor, TestKey>;
+
+    #[test]
+    fn test_round_trip() {
+        let cursor = EncryptedOffset::new(OffsetCursor::new(10).with_first(Some(5)));
+        let decoded = EncryptedOffset::from_encoded_string(&cursor.to_encoded_string()).unwrap();
+        assert_eq!(decoded, cursor);
+
+        let cursor = EncryptedCursor::<StringCursor, TestKey>::new(StringCursor::new(
+            "internal-key-42".to_string(),
+        ));
+        let encoded = cursor.to_encoded_string();
+        let decoded = EncryptedCursor::<StringCursor, TestKey>::from_encoded_string(&encoded);
+        assert_eq!(decoded.unwrap().inner.value, "internal-key-42");
+    }
+
+    #[test]
+    fn test_contents_unreadable() {
+        let cursor = EncryptedCursor::<StringCursor, TestKey>::new(StringCursor::new(
+            "internal-key-42".to_string(),
+        ));
+        let bytes = BASE64_URL_SAFE.decode(cursor.to_encoded_string()).unwrap();
+        assert!(
+            !bytes
+                .windows("internal-key-42".len())
+                .any(|window| window == b"internal-key-42")
+        );
+
+        // A fresh nonce each time, so the same cursor doesn't encode the same way twice.
+        assert_ne!(cursor.to_encoded_string(), cursor.to_encoded_string());
+    }
+
+    #[test]
+    fn test_wrong_key_rejected() {
+        let cursor = EncryptedCursor::<OffsetCursor, OtherKey>::new(OffsetCursor::new(10));
+        assert_eq!(
+            EncryptedOffset::from_encoded_string(&cursor.to_encoded_string()).unwrap_err(),
+            CursorError::DecryptionFailed
+        );
+    }
+
+    #[test]
+    fn test_tampered_rejected() {
+        let cursor = EncryptedOffset::new(OffsetCursor::new(10));
+        let mut bytes = BASE64_URL_SAFE.decode(cursor.to_encoded_string()).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0x01;
+
+        for encoded in [
+            BASE64_URL_SAFE.encode(&bytes),
+            BASE64_URL_SAFE.encode(&bytes[..4]),
+            OffsetCursor::new(10).to_encoded_string(),
+        ] {
+            assert_eq!(
+                EncryptedOffset::from_encoded_string(&encoded).unwrap_err(),
+                CursorError::DecryptionFailed
+            );
+        }
+    }
+
+    #[test]
+    fn test_named_after_inner_cursor() {
+        assert_eq!(
+            <EncryptedOffset as GraphQLType<DefaultScalarValue>>::name(&()).as_deref(),
+            Some("EncryptedOffsetCursor")
+        );
+        assert_eq!(
+            <EncryptedCursor<StringCursor, TestKey> as GraphQLType<DefaultScalarValue>>::name(&())
+                .as_deref(),
+            Some("EncryptedStringCursor")
+        );
+    }
+
+    #[test]
+    fn test_cipher_matches_key_type() {
+        let cipher = CursorCipher::new(TestKey::key());
+        let cursor = EncryptedOffset::new(OffsetCursor::new(10));
+        assert_eq!(
+            cipher.decrypt::<OffsetCursor>(&cursor.to_encoded_string()),
+            Ok(OffsetCursor::new(10))
+        );
+
+        let encrypted = cipher.encrypt(&OffsetCursor::new(10));
+        assert_eq!(
+            EncryptedOffset::from_encoded_string(&encrypted).unwrap(),
+            cursor
+        );
+    }
+
+    #[derive(Debug, Clone, GraphQLObject, RelayConnection)]
+    #[relay(cursor = EncryptedToken)]
+    pub struct Ledger {
+        pub id: i32,
+    }
+
+    fn provider(key: &[u8; 32]) -> EncryptedCursorProvider<OffsetCursorProvider> {
+        EncryptedCursorProvider::new(OffsetCursorProvider::new(), key)
+    }
+
+    #[test]
+    fn test_provider_round_trip() {
+        let items: Vec<Option<Ledger>> = (0..2).map(|id| Some(Ledger { id })).collect();
+        let conn = LedgerRelayConnection::new(
+            Some(&items),
+            Some(5),
+            provider(TestKey::key()),
+            Some(PageRequest::new(Some(2), None, None)),
+        );
+        assert!(conn.page_info.has_next_page);
+        assert_eq!(
+            conn.page_info.end_cursor.as_ref(),
+            conn.edges
+                .as_ref()
+                .and_then(|edges| edges.last()?.as_ref())
+                .and_then(|edge| edge.cursor.as_ref())
+        );
+
+        let end = conn.page_info.end_cursor.unwrap();
+        assert!(!end.token.contains("offset"));
+        let end = EncryptedToken::from_input(&end.to_output()).unwrap();
+
+        let request = PageRequest::new(Some(2), Some(end), None);
+        let offsets: PageRequest<OffsetCursor> = provider(TestKey::key())
+            .decrypt_page_request(&request)
+            .unwrap();
+        assert_eq!(offsets.after, Some(OffsetCursor::new(1)));
+
+        let conn = LedgerRelayConnection::new(
+            Some(&items),
+            Some(5),
+            provider(TestKey::key()),
+            Some(request),
+        );
+        let start = conn.page_info.start_cursor.unwrap();
+        assert_eq!(
+            provider(TestKey::key()).decrypt::<OffsetCursor>(&start),
+            Ok(OffsetCursor::new(2))
+        );
+    }
+
+    #[test]
+    fn test_provider_rejects_tampered_tokens() {
+        let token = provider(TestKey::key()).encrypt(&OffsetCursor::new(10));
+        let mut bytes = BASE64_URL_SAFE.decode(&token.token).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0x01;
+
+        for token in [
+            EncryptedToken::new(BASE64_URL_SAFE.encode(&bytes)),
+            provider(OtherKey::key()).encrypt(&OffsetCursor::new(10)),
+            EncryptedToken::new(OffsetCursor::new(10).to_encoded_string()),
+        ] {
+            let request = PageRequest::new(Some(2), Some(token), None);
+            assert_eq!(
+                provider(TestKey::key())
+                    .decrypt_page_request::<OffsetCursor>(&request)
+                    .unwrap_err(),
+                CursorError::DecryptionFailed
+            );
+        }
+
+        assert_eq!(
+            EncryptedToken::from_encoded_string("not a token!").unwrap_err(),
+            CursorError::InvalidCursor
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{PageRequest, RelayConnection, RelayEdge, RelayPageInfo};
+use serde::Serialize;
+use std::fmt::{Debug, Display, Formatter};
+use std::future::Future;
+use std::io::Write;
+
+/// Errors from `export_all`.
+#[derive(Debug)]
+pub enum ExportError<E> {
+    /// The page fetcher returned an error.
+    Fetch(E),
+
+    /// A node couldn't be serialized, or the line couldn't be written.
+    Write(serde_json::Error),
+}
+
+impl<E: Display> Display for ExportError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportError::Fetch(err) => write!(f, "Failed to fetch page: {}", err),
+            ExportError::Write(err) => write!(f, "Failed to write node: {}", err),
+        }
+    }
+}
+
+impl<E: Debug + Display> std::error::Error for ExportError<E> {}
+
+/// Pages through an entire connection set and writes each node to `writer` as a line of JSON
+/// (NDJSON), returning the number of nodes written.
+///
+/// `fetch_page` is called with a `PageRequest` for `page_size` items, starting after the previous
+/// page's `end_cursor`, until a page comes back without `has_next_page`. Only one page is held in
+/// memory at a time. Edges without a node are skipped.
+///
+/// ```
+/// use juniper::GraphQLObject;
+/// use juniper_relay_helpers::{
+///     export_all, OffsetCursor, OffsetCursorProvider, PageRequest, RelayConnection, RelayPageExt,
+/// };
+/// use serde::Serialize;
+///
+/// #[derive(Debug, Clone, GraphQLObject, RelayConnection, Serialize)]
+/// #[relay(cursor = OffsetCursor)]
+/// pub struct Pictos {
+///     name: String,
+/// }
+///
+/// async fn fetch(page_request: PageRequest<OffsetCursor>) -> Result<PictosRelayConnection, String> {
+///     let page = ["Augmented Attack", "Dodger", "Energising Start"]
+///         .into_iter()
+///         .map(|name| Pictos { name: name.to_string() })
+///         .relay_page(&page_request);
+///     Ok(PictosRelayConnection::from_metadata(
+///         Some(&page.items),
+///         page.metadata,
+///         OffsetCursorProvider::new(),
+///     ))
+/// }
+///
+/// # async fn export() -> Result<(), juniper_relay_helpers::ExportError<String>> {
+/// let mut out = vec![];
+/// let written = export_all(2, fetch, &mut out).await?;
+/// assert_eq!(written, 3);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Requires the `export` feature.
+pub async fn export_all<ConnectionT, FetchT, FutureT, ErrorT, WriterT>(
+    page_size: i32,
+    fetch_page: FetchT,
+    writer: &mut WriterT,
+) -> Result<usize, ExportError<ErrorT>>
+where
+    ConnectionT: RelayConnection,
+    ConnectionT::NodeType: Serialize,
+    ConnectionT::EdgeType: RelayEdge<NodeType = ConnectionT::NodeType>,
+    FetchT: Fn(PageRequest<ConnectionT::CursorType>) -> FutureT,
+    FutureT: Future<Output = Result<ConnectionT, ErrorT>>,
+    WriterT: Write,
+{
+    let mut after: Option<ConnectionT::CursorType> = None;
+    let mut written = 0;
+
+    loop {
+        let connection = fetch_page(PageRequest::new(Some(page_size), after.take(), None))
+            .await
+            .map_err(ExportError::Fetch)?;
+
+        for node in connection
+            .edges()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|edge| edge.as_ref()?.node())
+        {
+            serde_json::to_writer(&mut *writer, node).map_err(ExportError::Write)?;
+            writer
+                .write_all(b"\n")
+                .map_err(|err| ExportError::Write(serde_json::Error::io(err)))?;
+            written += 1;
+        }
+
+        let page_info = connection.page_info();
+        // Without an end cursor there's no way to ask for the next page, so stop rather than loop.
+        match page_info.end_cursor() {
+            Some(end_cursor) if page_info.has_next_page() => after = Some(end_cursor.clone()),
+            _ => break,
+        }
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ExportError, OffsetCursor, OffsetCursorProvider, PageRequest, RelayConnection,
+        RelayPageExt, export_all,
+    };
+    use juniper::GraphQLObject;
+    use serde::Serialize;
+    use std::cell::Cell;
+
+    #[derive(Debug, Clone, GraphQLObject, RelayConnection, Serialize)]
+    #[relay(cursor = OffsetCursor)]
+    pub struct Journal {
+        pub id: i32,
+    }
+
+    #[tokio::test]
+    async fn test_export_all_pages() {
+        let calls = Cell::new(0);
+        let fetch = |page_request: PageRequest<OffsetCursor>| {
+            calls.set(calls.get() + 1);
+            async move {
+                let page = (1..=7).map(|id| Journal { id }).relay_page(&page_request);
+                Ok::<_, String>(JournalRelayConnection::from_metadata(
+                    Some(&page.items),
+                    page.metadata,
+                    OffsetCursorProvider::new(),
+                ))
+            }
+        };
+
+        let mut out = vec![];
+        let written = export_all(3, fetch, &mut out).await.unwrap();
+
+        assert_eq!(written, 7);
+        assert_eq!(calls.get(), 3);
+        let lines: Vec<&str> = std::str::from_utf8(&out).unwrap().lines().collect();
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[0], r#"{"id":1}"#);
+        assert_eq!(lines[6], r#"{"id":7}"#);
+    }
+
+    #[tokio::test]
+    async fn test_export_all_fetch_error() {
+        let fetch = |_: PageRequest<OffsetCursor>| async {
+            Err::<JournalRelayConnection, _>("database unavailable".to_string())
+        };
+
+        let mut out = vec![];
+        let err = export_all(3, fetch, &mut out).await.unwrap_err();
+        assert!(matches!(err, ExportError::Fetch(_)));
+        assert_eq!(
+            err.to_string(),
+            "Failed to fetch page: database unavailable"
+        );
+        assert!(out.is_empty());
+    }
+}
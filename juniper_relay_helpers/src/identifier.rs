@@ -1,10 +1,75 @@
+use crate::IdentifierError;
 use base64::prelude::*;
 use juniper::{GraphQLScalar, ParseScalarResult, ParseScalarValue, ScalarToken, ScalarValue};
-use std::fmt::Display;
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::marker::PhantomData;
 use std::str::FromStr;
 
 const SEGMENT_DELIMITER: &str = "::";
 
+/// How a `RelayIdentifier` is written out in GraphQL responses.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum IdentifierOutputMode {
+    /// The base64 encoded form, such as `Y2hhcmFjdGVyOjoxMjM=`. This is what clients should treat
+    /// as opaque, and is the default.
+    #[default]
+    Opaque,
+
+    /// The plain `type::id` form, such as `character::123`, which is easier to read while developing.
+    Readable,
+}
+
+/// Picks the `IdentifierOutputMode` of a `RelayIdentifier`, as its third type parameter. Implemented
+/// by `OpaqueIds`, the default, and `ReadableIds`.
+pub trait IdentifierFormat: Debug + Clone + Eq + Hash {
+    /// The mode identifiers of this format are written out in.
+    const OUTPUT_MODE: IdentifierOutputMode;
+}
+
+/// Writes identifiers out in the base64 encoded form. The default format of a `RelayIdentifier`.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct OpaqueIds;
+
+impl IdentifierFormat for OpaqueIds {
+    const OUTPUT_MODE: IdentifierOutputMode = IdentifierOutputMode::Opaque;
+}
+
+/// Writes identifiers out in the readable `type::id` form, and accepts that form back as input as
+/// well as the encoded one, so clients can send back the IDs they were given.
+///
+/// The format is part of the identifier's type, so pick it per build with a type alias rather than
+/// switching at runtime, such as readable IDs for debug builds only:
+///
+/// ```
+/// use juniper_relay_helpers::{IdentifierTypeDiscriminator, RelayIdentifier, ReadableIds};
+///
+/// #[derive(IdentifierTypeDiscriminator, Debug, PartialEq)]
+/// enum EntityType {
+///     Character,
+/// }
+///
+/// #[cfg(debug_assertions)]
+/// type Id<T> = RelayIdentifier<T, EntityType, ReadableIds>;
+/// #[cfg(not(debug_assertions))]
+/// type Id<T> = RelayIdentifier<T, EntityType>;
+///
+/// let id = Id::with_format(123, EntityType::Character);
+/// # #[cfg(debug_assertions)]
+/// assert_eq!(id.to_output().to_string(), "character::123");
+/// # #[cfg(debug_assertions)]
+/// assert_eq!(Id::from_input("character::123"), Ok(id));
+///
+/// let opaque = RelayIdentifier::new(123, EntityType::Character);
+/// assert_eq!(opaque.to_output().to_string(), "Y2hhcmFjdGVyOjoxMjM=");
+/// ```
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct ReadableIds;
+
+impl IdentifierFormat for ReadableIds {
+    const OUTPUT_MODE: IdentifierOutputMode = IdentifierOutputMode::Readable;
+}
+
 /// Relay identifiers need to be globally unique. It's often useful to have a type delimiter in there
 /// too, so this is a struct to help with that!
 ///
@@ -17,24 +82,27 @@ const SEGMENT_DELIMITER: &str = "::";
     from_input_with = Self::from_input,
     parse_token_with = Self::parse_token
 )]
-pub struct RelayIdentifier<T, TD>
+pub struct RelayIdentifier<T, TD, F = OpaqueIds>
 where
     T: Display,
     T: FromStr,
     TD: Display,
     TD: FromStr,
+    F: IdentifierFormat,
 {
     pub id: T,
     pub type_discriminator: TD,
+    _format: PhantomData<fn() -> F>,
 }
 
 /// Implement Display
-impl<T, TD> Display for RelayIdentifier<T, TD>
+impl<T, TD, F> Display for RelayIdentifier<T, TD, F>
 where
     T: Display,
     T: FromStr,
     TD: Display,
     TD: FromStr,
+    F: IdentifierFormat,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -45,6 +113,30 @@ where
     }
 }
 
+/// Orders by type discriminator first and then by id, so identifiers of the same type sort together
+/// in something like a `BTreeMap`.
+impl<T, TD, F> PartialOrd for RelayIdentifier<T, TD, F>
+where
+    T: Display + FromStr + Ord,
+    TD: Display + FromStr + Ord,
+    F: IdentifierFormat,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T, TD, F> Ord for RelayIdentifier<T, TD, F>
+where
+    T: Display + FromStr + Ord,
+    TD: Display + FromStr + Ord,
+    F: IdentifierFormat,
+{
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (&self.type_discriminator, &self.id).cmp(&(&other.type_discriminator, &other.id))
+    }
+}
+
 /// Common implementation for all identifiers
 impl<T, TD> RelayIdentifier<T, TD>
 where
@@ -53,11 +145,26 @@ where
     TD: Display,
     TD: FromStr,
 {
-    /// General constructor
+    /// General constructor, for identifiers in the default `OpaqueIds` format.
     pub fn new(id: T, type_delimiter: TD) -> Self {
+        Self::with_format(id, type_delimiter)
+    }
+}
+
+impl<T, TD, F> RelayIdentifier<T, TD, F>
+where
+    T: Display,
+    T: FromStr,
+    TD: Display,
+    TD: FromStr,
+    F: IdentifierFormat,
+{
+    /// Constructor for identifiers in any format, such as `ReadableIds`, picked by the type.
+    pub fn with_format(id: T, type_discriminator: TD) -> Self {
         Self {
             id,
-            type_discriminator: type_delimiter,
+            type_discriminator,
+            _format: PhantomData,
         }
     }
 
@@ -65,34 +172,84 @@ where
         BASE64_URL_SAFE.encode(self.to_string())
     }
 
-    // ---------- GraphQLScalar implementation ----------
+    /// Writes the identifier out in the given mode, whatever its format is.
+    pub fn render(&self, mode: IdentifierOutputMode) -> String {
+        match mode {
+            IdentifierOutputMode::Opaque => self.to_encoded_string(),
+            IdentifierOutputMode::Readable => self.to_string(),
+        }
+    }
 
-    pub fn to_output(&self) -> juniper::ID {
-        juniper::ID::from(self.to_encoded_string())
+    /// Decodes an identifier from its base64 encoded form, as it arrives from a client.
+    ///
+    /// ```
+    /// use juniper_relay_helpers::{IdentifierError, IdentifierTypeDiscriminator, RelayIdentifier};
+    ///
+    /// #[derive(IdentifierTypeDiscriminator, Debug, PartialEq)]
+    /// enum EntityType {
+    ///     Character,
+    ///     Weapon,
+    /// }
+    ///
+    /// let encoded = RelayIdentifier::new(12, EntityType::Weapon).to_encoded_string();
+    /// let id = RelayIdentifier::<i32, EntityType>::parse(&encoded).unwrap();
+    /// assert_eq!(id.expect_type(EntityType::Weapon), Ok(&12));
+    /// assert!(matches!(
+    ///     id.expect_type(EntityType::Character),
+    ///     Err(IdentifierError::UnexpectedType { .. })
+    /// ));
+    /// ```
+    pub fn parse(encoded: &str) -> Result<Self, IdentifierError> {
+        let decoded_string = String::from_utf8(BASE64_URL_SAFE.decode(encoded)?)?;
+        Self::parse_readable(&decoded_string)
     }
 
-    pub fn from_input(input: &str) -> Result<Self, Box<str>> {
-        // Input is a base64 encoded string, so we need to decode it first
-        let decoded_bytes = BASE64_URL_SAFE
-            .decode(input)
-            .map_err(|err| format!("Invalid base64 encoding: {}", err))?;
+    /// Parses the readable `type::id` form, as written in `IdentifierOutputMode::Readable`.
+    pub fn parse_readable(readable: &str) -> Result<Self, IdentifierError> {
+        let parts = readable.split(SEGMENT_DELIMITER).collect::<Vec<&str>>();
+
+        if parts.len() != 2 {
+            return Err(IdentifierError::MissingDelimiter);
+        }
 
-        let decoded_string = String::from_utf8(decoded_bytes)
-            .map_err(|err| format!("Invalid UTF-8 encoding: {}", err))?;
+        let identifier_part = T::from_str(parts[1]).map_err(|_| IdentifierError::InvalidId)?;
 
-        let parts = decoded_string
-            .split(SEGMENT_DELIMITER)
-            .collect::<Vec<&str>>();
+        let type_delimiter_part = TD::from_str(parts[0])
+            .map_err(|_| IdentifierError::UnknownDiscriminator(parts[0].to_string()))?;
 
-        if parts.len() != 2 {
-            return Err("Invalid Relay identifier".into());
+        Ok(Self::with_format(identifier_part, type_delimiter_part))
+    }
+
+    /// Returns the id if the identifier is for the `expected` type, so a resolver can check it's been
+    /// handed the right kind of ID. Returns `IdentifierError::UnexpectedType` otherwise.
+    pub fn expect_type(&self, expected: TD) -> Result<&T, IdentifierError>
+    where
+        TD: PartialEq,
+    {
+        if self.type_discriminator == expected {
+            Ok(&self.id)
+        } else {
+            Err(IdentifierError::UnexpectedType {
+                expected: expected.to_string(),
+                got: self.type_discriminator.to_string(),
+            })
         }
+    }
 
-        let identifier_part = T::from_str(parts[1]).map_err(|_| "Invalid identifier")?;
+    // ---------- GraphQLScalar implementation ----------
 
-        let type_delimiter_part = TD::from_str(parts[0]).map_err(|_| "Invalid type delimiter")?;
+    pub fn to_output(&self) -> juniper::ID {
+        juniper::ID::from(self.render(F::OUTPUT_MODE))
+    }
 
-        Ok(Self::new(identifier_part, type_delimiter_part))
+    pub fn from_input(input: &str) -> Result<Self, Box<str>> {
+        let parsed = match F::OUTPUT_MODE {
+            IdentifierOutputMode::Readable if input.contains(SEGMENT_DELIMITER) => {
+                Self::parse_readable(input)
+            }
+            _ => Self::parse(input),
+        };
+        parsed.map_err(|err| err.to_string().into_boxed_str())
     }
 
     fn parse_token<S: ScalarValue>(value: ScalarToken<'_>) -> ParseScalarResult<S> {
@@ -100,26 +257,277 @@ where
     }
 }
 
+/// The encoded form, for fields typed as a plain `juniper::ID`. Always the base64 encoded form,
+/// whatever its format is.
+impl<T, TD, F> From<RelayIdentifier<T, TD, F>> for juniper::ID
+where
+    T: Display,
+    T: FromStr,
+    TD: Display,
+    TD: FromStr,
+    F: IdentifierFormat,
+{
+    fn from(identifier: RelayIdentifier<T, TD, F>) -> Self {
+        juniper::ID::new(identifier.to_encoded_string())
+    }
+}
+
+/// The encoded form, as `RelayIdentifier::to_encoded_string`.
+impl<T, TD, F> From<RelayIdentifier<T, TD, F>> for String
+where
+    T: Display,
+    T: FromStr,
+    TD: Display,
+    TD: FromStr,
+    F: IdentifierFormat,
+{
+    fn from(identifier: RelayIdentifier<T, TD, F>) -> Self {
+        identifier.to_encoded_string()
+    }
+}
+
+/// Decodes a `juniper::ID` holding the encoded form, as `RelayIdentifier::parse`. Fails with the
+/// `IdentifierError` describing why it couldn't be decoded.
+impl<T, TD, F> TryFrom<juniper::ID> for RelayIdentifier<T, TD, F>
+where
+    T: Display,
+    T: FromStr,
+    TD: Display,
+    TD: FromStr,
+    F: IdentifierFormat,
+{
+    type Error = IdentifierError;
+
+    fn try_from(id: juniper::ID) -> Result<Self, Self::Error> {
+        Self::parse(&id)
+    }
+}
+
+/// Decodes the encoded form, as `RelayIdentifier::parse`. Fails with the `IdentifierError`
+/// describing why it couldn't be decoded.
+impl<T, TD, F> TryFrom<String> for RelayIdentifier<T, TD, F>
+where
+    T: Display,
+    T: FromStr,
+    TD: Display,
+    TD: FromStr,
+    F: IdentifierFormat,
+{
+    type Error = IdentifierError;
+
+    fn try_from(encoded: String) -> Result<Self, Self::Error> {
+        Self::parse(&encoded)
+    }
+}
+
+/// The `uuid` crate's `Uuid`, re-exported so ids are built with the same version the library uses.
+#[cfg(feature = "uuid")]
+pub use uuid::Uuid;
+
+/// A `RelayIdentifier` with a `Uuid` id. Parsing fails with `IdentifierError::InvalidId` if the id
+/// part isn't a valid UUID.
+///
+/// ```
+/// use juniper_relay_helpers::{
+///     IdentifierError, IdentifierTypeDiscriminator, RelayIdentifier, UuidRelayIdentifier, Uuid,
+/// };
+///
+/// #[derive(IdentifierTypeDiscriminator, Debug, PartialEq)]
+/// enum EntityType {
+///     Character,
+/// }
+///
+/// let uuid = Uuid::parse_str("7396aa2f-03df-42d6-aae1-cf0e9182f0d2").unwrap();
+/// let encoded = UuidRelayIdentifier::new(uuid, EntityType::Character).to_encoded_string();
+///
+/// let id = UuidRelayIdentifier::<EntityType>::parse(&encoded).unwrap();
+/// assert_eq!(id.expect_type(EntityType::Character), Ok(&uuid));
+///
+/// let not_a_uuid = RelayIdentifier::new("123".to_string(), EntityType::Character).to_encoded_string();
+/// assert_eq!(
+///     UuidRelayIdentifier::<EntityType>::parse(&not_a_uuid),
+///     Err(IdentifierError::InvalidId)
+/// );
+/// ```
+#[cfg(feature = "uuid")]
+pub type UuidRelayIdentifier<TD> = RelayIdentifier<Uuid, TD>;
+
+/// Parses the base64 encoded form, as `RelayIdentifier::parse`.
+impl<T, TD, F> FromStr for RelayIdentifier<T, TD, F>
+where
+    T: Display,
+    T: FromStr,
+    TD: Display,
+    TD: FromStr,
+    F: IdentifierFormat,
+{
+    type Err = IdentifierError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Decodes an encoded `RelayIdentifier` and dispatches on its type discriminator, for building the
+/// `node(id: ID!): Node` field from Relay's global object identification.
+///
+/// Give it the encoded ID, the id and discriminator types, and an arm per discriminator binding the
+/// decoded id. Arms are checked like any `match`, so adding a variant to the discriminator without a
+/// loader for it is a compile error. The arm bodies run in the calling function, so `?` and `.await`
+/// work in them. Evaluates to `Result<T, IdentifierError>`, where `T` is what the arms return.
+///
+/// ```
+/// use juniper_relay_helpers::{
+///     relay_node_resolver, IdentifierError, IdentifierTypeDiscriminator, RelayIdentifier,
+/// };
+///
+/// #[derive(IdentifierTypeDiscriminator)]
+/// enum EntityType {
+///     Character,
+///     Weapon,
+/// }
+///
+/// enum Node {
+///     Character(String),
+///     Weapon(String),
+/// }
+///
+/// fn node(id: &str) -> Result<Node, IdentifierError> {
+///     relay_node_resolver!(id, i32, EntityType, {
+///         EntityType::Character => |id| Node::Character(format!("character {id}")),
+///         EntityType::Weapon => |id| Node::Weapon(format!("weapon {id}")),
+///     })
+/// }
+///
+/// let encoded = RelayIdentifier::new(7, EntityType::Weapon).to_encoded_string();
+/// assert!(matches!(node(&encoded), Ok(Node::Weapon(name)) if name == "weapon 7"));
+/// ```
+#[macro_export]
+macro_rules! relay_node_resolver {
+    ($encoded:expr, $id_type:ty, $discriminator_type:ty, {
+        $($variant:pat => |$id:ident| $load:expr),+ $(,)?
+    }) => {
+        match $crate::RelayIdentifier::<$id_type, $discriminator_type>::parse($encoded) {
+            ::std::result::Result::Ok(identifier) => ::std::result::Result::Ok(
+                match identifier.type_discriminator {
+                    $($variant => {
+                        let $id = identifier.id;
+                        $load
+                    })+
+                },
+            ),
+            ::std::result::Result::Err(err) => ::std::result::Result::Err(err),
+        }
+    };
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::identifier::RelayIdentifier;
+    use crate::identifier::{ReadableIds, RelayIdentifier};
+    use crate::{IdentifierError, IdentifierOutputMode};
     use base64::Engine;
     use base64::prelude::BASE64_URL_SAFE;
     use juniper_relay_helpers_codegen::IdentifierTypeDiscriminator;
+    use std::collections::{BTreeSet, HashSet};
     use uuid::Uuid;
 
-    #[derive(IdentifierTypeDiscriminator, PartialEq, Eq, Debug)]
+    #[derive(IdentifierTypeDiscriminator, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
     enum TestTypeDiscriminator {
         Character,
         Weapon,
     }
 
+    #[derive(IdentifierTypeDiscriminator, PartialEq, Eq, Debug)]
+    enum RenamedTypeDiscriminator {
+        #[discriminator("char")]
+        PlayableCharacter,
+        Weapon,
+    }
+
+    #[derive(IdentifierTypeDiscriminator, PartialEq, Eq, Debug)]
+    enum BracedTypeDiscriminator {
+        #[discriminator("{char}")]
+        Character,
+    }
+
+    #[test]
+    fn test_discriminator_with_format_braces() {
+        use std::str::FromStr;
+
+        assert_eq!(BracedTypeDiscriminator::Character.to_string(), "{char}");
+        assert_eq!(
+            BracedTypeDiscriminator::from_str("{char}"),
+            Ok(BracedTypeDiscriminator::Character)
+        );
+    }
+
+    #[derive(IdentifierTypeDiscriminator, PartialEq, Eq, Debug)]
+    #[relay(case_insensitive)]
+    enum LegacyTypeDiscriminator {
+        Character,
+        #[discriminator("wpn")]
+        Weapon,
+    }
+
+    #[test]
+    fn test_case_insensitive_discriminator() {
+        use std::str::FromStr;
+
+        for value in ["character", "Character", "CHARACTER"] {
+            assert_eq!(
+                LegacyTypeDiscriminator::from_str(value),
+                Ok(LegacyTypeDiscriminator::Character)
+            );
+        }
+        assert_eq!(
+            LegacyTypeDiscriminator::from_str("WPN"),
+            Ok(LegacyTypeDiscriminator::Weapon)
+        );
+        assert_eq!(LegacyTypeDiscriminator::Character.to_string(), "character");
+
+        // Case sensitive by default.
+        assert_eq!(
+            TestTypeDiscriminator::from_str("Character"),
+            Err(IdentifierError::UnknownDiscriminator(
+                "Character".to_string()
+            ))
+        );
+
+        let id = RelayIdentifier::<i32, LegacyTypeDiscriminator>::parse(
+            &BASE64_URL_SAFE.encode("Character::8"),
+        );
+        assert_eq!(
+            id,
+            Ok(RelayIdentifier::new(8, LegacyTypeDiscriminator::Character))
+        );
+    }
+
+    #[test]
+    fn test_custom_discriminator_values() {
+        use std::str::FromStr;
+
+        assert_eq!(
+            RenamedTypeDiscriminator::PlayableCharacter.to_string(),
+            "char"
+        );
+        assert_eq!(RenamedTypeDiscriminator::Weapon.to_string(), "weapon");
+        assert_eq!(
+            RenamedTypeDiscriminator::from_str("char"),
+            Ok(RenamedTypeDiscriminator::PlayableCharacter)
+        );
+        assert!(RenamedTypeDiscriminator::from_str("playablecharacter").is_err());
+
+        let id = RelayIdentifier::new(5, RenamedTypeDiscriminator::PlayableCharacter);
+        assert_eq!(id.to_string(), "char::5");
+        assert_eq!(
+            RelayIdentifier::<i32, RenamedTypeDiscriminator>::parse(&id.to_encoded_string()),
+            Ok(id)
+        );
+    }
+
     #[test]
     fn test_string_identifiers() {
-        let id = RelayIdentifier {
-            id: "123".to_string(),
-            type_discriminator: TestTypeDiscriminator::Character,
-        };
+        let id = RelayIdentifier::new("123".to_string(), TestTypeDiscriminator::Character);
         assert_eq!(id.to_string(), "character::123");
     }
 
@@ -143,6 +551,43 @@ mod tests {
         assert_eq!(output.to_string(), "Y2hhcmFjdGVyOjoxMjM=");
     }
 
+    #[test]
+    fn test_render_modes() {
+        let id = RelayIdentifier::new(123, TestTypeDiscriminator::Character);
+        assert_eq!(
+            id.render(IdentifierOutputMode::Opaque),
+            "Y2hhcmFjdGVyOjoxMjM="
+        );
+        assert_eq!(id.render(IdentifierOutputMode::Readable), "character::123");
+
+        assert_eq!(
+            RelayIdentifier::parse(&id.render(IdentifierOutputMode::Opaque)),
+            Ok(RelayIdentifier::new(123, TestTypeDiscriminator::Character))
+        );
+        assert_eq!(
+            RelayIdentifier::parse_readable(&id.render(IdentifierOutputMode::Readable)),
+            Ok(id)
+        );
+    }
+
+    #[test]
+    fn test_output_follows_format() {
+        type ReadableId = RelayIdentifier<i32, TestTypeDiscriminator, ReadableIds>;
+        let readable = || ReadableId::with_format(123, TestTypeDiscriminator::Character);
+        let opaque = RelayIdentifier::new(123, TestTypeDiscriminator::Character);
+        assert_eq!(opaque.to_output().to_string(), "Y2hhcmFjdGVyOjoxMjM=");
+        assert_eq!(readable().to_output().to_string(), "character::123");
+
+        assert_eq!(ReadableId::from_input("character::123"), Ok(readable()));
+        assert_eq!(
+            ReadableId::from_input("Y2hhcmFjdGVyOjoxMjM="),
+            Ok(readable())
+        );
+        assert!(
+            RelayIdentifier::<i32, TestTypeDiscriminator>::from_input("character::123").is_err()
+        );
+    }
+
     #[test]
     fn test_from_input_string() {
         let input = "Y2hhcmFjdGVyOjoxMjM=";
@@ -177,6 +622,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_identifiers_as_keys() {
+        let ids: HashSet<_> = [
+            RelayIdentifier::new(1, TestTypeDiscriminator::Weapon),
+            RelayIdentifier::new(1, TestTypeDiscriminator::Weapon),
+            RelayIdentifier::new(1, TestTypeDiscriminator::Character),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(ids.len(), 2);
+
+        let sorted: Vec<_> = [
+            RelayIdentifier::new(1, TestTypeDiscriminator::Weapon),
+            RelayIdentifier::new(2, TestTypeDiscriminator::Character),
+            RelayIdentifier::new(1, TestTypeDiscriminator::Character),
+        ]
+        .into_iter()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|id| id.to_string())
+        .collect();
+        assert_eq!(sorted, vec!["character::1", "character::2", "weapon::1"]);
+    }
+
+    #[test]
+    fn test_id_conversions() {
+        let id = || RelayIdentifier::new(123, TestTypeDiscriminator::Weapon);
+        let encoded = id().to_encoded_string();
+
+        let graphql_id = juniper::ID::from(id());
+        assert_eq!(graphql_id.to_string(), encoded);
+        assert_eq!(RelayIdentifier::try_from(graphql_id), Ok(id()));
+
+        let string = String::from(id());
+        assert_eq!(string, encoded);
+        assert_eq!(RelayIdentifier::try_from(string), Ok(id()));
+
+        assert_eq!(
+            RelayIdentifier::<i32, TestTypeDiscriminator>::try_from(juniper::ID::new(
+                BASE64_URL_SAFE.encode("weapon::sword")
+            )),
+            Err(IdentifierError::InvalidId)
+        );
+        // The readable form isn't accepted, only the encoded one.
+        assert!(matches!(
+            RelayIdentifier::<i32, TestTypeDiscriminator>::try_from("weapon::1".to_string()),
+            Err(IdentifierError::Base64(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_invalid_uuid() {
+        let input = BASE64_URL_SAFE.encode("character::7396aa2f-03df");
+        assert_eq!(
+            RelayIdentifier::<Uuid, TestTypeDiscriminator>::parse(&input),
+            Err(IdentifierError::InvalidId)
+        );
+    }
+
     #[test]
     fn test_from_invalid_base64() {
         let input = "Y2hhcmFjdGVyOjo3Mzk2YWEyZi0wM2RmLTQyZDYtYWFlMS1jZjBlOTE4MmYwZDI";
@@ -206,4 +710,66 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(result.unwrap_err().to_string(), "Invalid Relay identifier");
     }
+
+    #[test]
+    fn test_parse_errors() {
+        type Id = RelayIdentifier<i32, TestTypeDiscriminator>;
+
+        assert!(matches!(
+            Id::parse("not base64!"),
+            Err(IdentifierError::Base64(_))
+        ));
+        assert_eq!(
+            Id::parse(&BASE64_URL_SAFE.encode("weapon/123")),
+            Err(IdentifierError::MissingDelimiter)
+        );
+        assert_eq!(
+            Id::parse(&BASE64_URL_SAFE.encode("pictos::123")),
+            Err(IdentifierError::UnknownDiscriminator("pictos".to_string()))
+        );
+        assert_eq!(
+            Id::parse(&BASE64_URL_SAFE.encode("weapon::abc")),
+            Err(IdentifierError::InvalidId)
+        );
+    }
+
+    #[test]
+    fn test_parse_and_expect_type() {
+        let encoded = RelayIdentifier::new(33, TestTypeDiscriminator::Weapon).to_encoded_string();
+        let id: RelayIdentifier<i32, TestTypeDiscriminator> = encoded.parse().unwrap();
+
+        assert_eq!(id.expect_type(TestTypeDiscriminator::Weapon), Ok(&33));
+        assert_eq!(
+            id.expect_type(TestTypeDiscriminator::Character),
+            Err(IdentifierError::UnexpectedType {
+                expected: "character".to_string(),
+                got: "weapon".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_node_resolver_dispatches_on_type() {
+        fn resolve(encoded: &str) -> Result<String, IdentifierError> {
+            crate::relay_node_resolver!(encoded, i32, TestTypeDiscriminator, {
+                TestTypeDiscriminator::Character => |id| format!("character {id}"),
+                TestTypeDiscriminator::Weapon => |id| format!("weapon {id}"),
+            })
+        }
+
+        let character = RelayIdentifier::new(1, TestTypeDiscriminator::Character);
+        let weapon = RelayIdentifier::new(2, TestTypeDiscriminator::Weapon);
+        assert_eq!(
+            resolve(&character.to_encoded_string()),
+            Ok("character 1".to_string())
+        );
+        assert_eq!(
+            resolve(&weapon.to_encoded_string()),
+            Ok("weapon 2".to_string())
+        );
+        assert_eq!(
+            resolve(&BASE64_URL_SAFE.encode("weapon")),
+            Err(IdentifierError::MissingDelimiter)
+        );
+    }
 }
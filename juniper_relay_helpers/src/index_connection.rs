@@ -0,0 +1,262 @@
+use crate::{
+    CursorProvider, OffsetCursor, PageInfoFactory, PageRequest, PaginationMetadata, Paginator,
+    RelayPageInfo,
+};
+use std::ops::Range;
+
+/// An edge of an `IndexConnection`, holding the position of the node in the source rather than the
+/// node itself.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct IndexEdge {
+    /// Index of the node in the source slice.
+    pub index: usize,
+
+    /// The cursor for this edge.
+    pub cursor: OffsetCursor,
+}
+
+impl IndexEdge {
+    /// Looks the node up in the source the connection was built over.
+    pub fn node<'a, ItemT>(&self, source: &'a [ItemT]) -> Option<&'a ItemT> {
+        source.get(self.index)
+    }
+}
+
+/// PageInfo for an `IndexConnection`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct IndexPageInfo {
+    pub has_next_page: bool,
+    pub has_previous_page: bool,
+    pub start_cursor: Option<OffsetCursor>,
+    pub end_cursor: Option<OffsetCursor>,
+}
+
+impl PageInfoFactory<OffsetCursor> for IndexPageInfo {
+    fn new(
+        has_prev_page: bool,
+        has_next_page: bool,
+        start_cursor: Option<OffsetCursor>,
+        end_cursor: Option<OffsetCursor>,
+    ) -> Self {
+        IndexPageInfo {
+            has_next_page,
+            has_previous_page: has_prev_page,
+            start_cursor,
+            end_cursor,
+        }
+    }
+}
+
+impl RelayPageInfo for IndexPageInfo {
+    type CursorType = OffsetCursor;
+
+    fn has_next_page(&self) -> bool {
+        self.has_next_page
+    }
+
+    fn has_previous_page(&self) -> bool {
+        self.has_previous_page
+    }
+
+    fn start_cursor(&self) -> Option<&OffsetCursor> {
+        self.start_cursor.as_ref()
+    }
+
+    fn end_cursor(&self) -> Option<&OffsetCursor> {
+        self.end_cursor.as_ref()
+    }
+}
+
+/// A page over a slice that holds indices into it instead of the nodes, so nothing is cloned. Useful
+/// for large sources, or nodes that aren't `Clone`. Resolvers look the nodes up in the source as
+/// they're asked for, with `IndexEdge::node` or `IndexConnection::nodes`.
+///
+/// Only the page's window is walked, so building it costs the same however big the source is.
+///
+/// ```
+/// use juniper_relay_helpers::{IndexConnection, OffsetCursor, OffsetCursorProvider, PageRequest};
+///
+/// // Not Clone, so can't go in a generated connection.
+/// struct Frame(Vec<u8>);
+/// let frames: Vec<Frame> = (0..100).map(|n| Frame(vec![n])).collect();
+///
+/// let conn = IndexConnection::new(
+///     frames.len(),
+///     Some(PageRequest::new(Some(2), Some(OffsetCursor::new(9)), None)),
+///     OffsetCursorProvider::new(),
+/// );
+///
+/// let firsts: Vec<u8> = conn.nodes(&frames).map(|(frame, _cursor)| frame.0[0]).collect();
+/// assert_eq!(firsts, vec![10, 11]);
+/// ```
+///
+/// As with `Paginator::paginate`, pages run forwards from the `after` cursor. `last` and `before`
+/// are ignored, so a request with only `last` gets the whole source.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct IndexConnection {
+    /// The total number of items in the source.
+    pub count: Option<i32>,
+
+    /// The edges within the requested window.
+    pub edges: Vec<IndexEdge>,
+
+    pub page_info: IndexPageInfo,
+}
+
+impl IndexConnection {
+    /// Builds the edges for the window of a source of `len` items described by the page request.
+    pub fn new<ProviderT>(
+        len: usize,
+        page_request: Option<PageRequest<OffsetCursor>>,
+        cursor_provider: ProviderT,
+    ) -> Self
+    where
+        ProviderT: CursorProvider<usize, CursorType = OffsetCursor>,
+    {
+        // Only forward pages are supported, so drop the backward arguments rather than let the
+        // provider build its cursors from the end of the set.
+        let page_request = page_request.map(|pr| PageRequest {
+            last: None,
+            before: None,
+            ..pr
+        });
+        let Range { start, end } = page_request
+            .as_ref()
+            .map_or(0..len, |pr| Paginator::window(len, pr));
+
+        let metadata = PaginationMetadata {
+            total_count: Some(i32::try_from(len).unwrap_or(i32::MAX)),
+            page_request,
+            more_available: Some(end < len),
+        };
+        let metadata = PaginationMetadata {
+            total_count: cursor_provider
+                .total_count(&metadata)
+                .or(metadata.total_count),
+            ..metadata
+        };
+
+        let indices: Vec<Option<usize>> = (start..end).map(Some).collect();
+        let edges = (start..end)
+            .enumerate()
+            .map(|(idx, index)| IndexEdge {
+                index,
+                cursor: cursor_provider.get_cursor_for_item(&metadata, idx as i32, Some(&index)),
+            })
+            .collect();
+
+        IndexConnection {
+            count: metadata.known_total_count(),
+            edges,
+            page_info: cursor_provider.get_page_info(&metadata, Some(&indices)),
+        }
+    }
+
+    /// The nodes for each edge, looked up in `source`, along with their cursors.
+    pub fn nodes<'a, ItemT>(
+        &'a self,
+        source: &'a [ItemT],
+    ) -> impl Iterator<Item = (&'a ItemT, &'a OffsetCursor)> {
+        self.edges
+            .iter()
+            .filter_map(move |edge| Some((edge.node(source)?, &edge.cursor)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{IndexConnection, OffsetCursor, OffsetCursorProvider, PageRequest};
+
+    /// Deliberately not `Clone`.
+    struct Brush {
+        stroke: usize,
+    }
+
+    fn source() -> Vec<Brush> {
+        (0..1000).map(|stroke| Brush { stroke }).collect()
+    }
+
+    #[test]
+    fn test_indices_and_cursors() {
+        let source = source();
+        let conn = IndexConnection::new(
+            source.len(),
+            Some(PageRequest::new(
+                Some(3),
+                Some(OffsetCursor::new(499)),
+                None,
+            )),
+            OffsetCursorProvider::new(),
+        );
+
+        assert_eq!(conn.count, Some(1000));
+        let indices: Vec<usize> = conn.edges.iter().map(|edge| edge.index).collect();
+        assert_eq!(indices, vec![500, 501, 502]);
+        let cursors: Vec<i32> = conn.edges.iter().map(|edge| edge.cursor.offset).collect();
+        assert_eq!(cursors, vec![500, 501, 502]);
+
+        assert!(conn.page_info.has_previous_page);
+        assert!(conn.page_info.has_next_page);
+        assert_eq!(conn.page_info.start_cursor, Some(OffsetCursor::new(500)));
+        assert_eq!(conn.page_info.end_cursor, Some(OffsetCursor::new(502)));
+
+        let strokes: Vec<usize> = conn.nodes(&source).map(|(b, _)| b.stroke).collect();
+        assert_eq!(strokes, vec![500, 501, 502]);
+    }
+
+    #[test]
+    fn test_last_page_and_no_request() {
+        let source = source();
+        let conn = IndexConnection::new(
+            source.len(),
+            Some(PageRequest::new(
+                Some(10),
+                Some(OffsetCursor::new(994)),
+                None,
+            )),
+            OffsetCursorProvider::new(),
+        );
+        assert_eq!(conn.edges.len(), 5);
+        assert_eq!(conn.edges.last().unwrap().index, 999);
+        assert!(!conn.page_info.has_next_page);
+
+        let conn = IndexConnection::new(3, None, OffsetCursorProvider::new());
+        assert_eq!(conn.edges.len(), 3);
+        assert!(!conn.page_info.has_previous_page);
+        assert!(!conn.page_info.has_next_page);
+
+        let conn = IndexConnection::new(
+            3,
+            Some(PageRequest::new(Some(2), Some(OffsetCursor::new(40)), None)),
+            OffsetCursorProvider::new(),
+        );
+        assert!(conn.edges.is_empty());
+        assert_eq!(conn.page_info.start_cursor, None);
+    }
+
+    #[test]
+    fn test_backward_arguments_ignored() {
+        let conn = IndexConnection::new(
+            10,
+            Some(PageRequest::new_backward(Some(3), None)),
+            OffsetCursorProvider::new(),
+        );
+        let indices: Vec<usize> = conn.edges.iter().map(|edge| edge.index).collect();
+        assert_eq!(indices, (0..10).collect::<Vec<_>>());
+        let cursors: Vec<i32> = conn.edges.iter().map(|edge| edge.cursor.offset).collect();
+        assert_eq!(cursors, (0..10).collect::<Vec<_>>());
+        assert!(!conn.page_info.has_previous_page);
+        assert!(!conn.page_info.has_next_page);
+        assert_eq!(conn.page_info.start_cursor, Some(OffsetCursor::new(0)));
+        assert_eq!(conn.page_info.end_cursor, Some(OffsetCursor::new(9)));
+
+        // A `before` isn't mistaken for the `after` the page starts from.
+        let mut page_request = PageRequest::new(Some(2), None, Some(OffsetCursor::new(6)));
+        page_request.last = Some(2);
+        let conn = IndexConnection::new(10, Some(page_request), OffsetCursorProvider::new());
+        let cursors: Vec<i32> = conn.edges.iter().map(|edge| edge.cursor.offset).collect();
+        assert_eq!(cursors, vec![0, 1]);
+        assert_eq!(conn.edges[0].index, 0);
+        assert!(conn.page_info.has_next_page);
+    }
+}
@@ -0,0 +1,240 @@
+use crate::cursor_provider::{built_cursor, full_page_info};
+use crate::{CompositeCursor, CursorProvider, PageInfoFactory, PaginationMetadata};
+use std::marker::PhantomData;
+
+/// Implemented by items paged with the `KeysetCursorProvider`, returning the values of the columns
+/// the set is sorted by.
+pub trait KeysetKey {
+    /// The sort column name/value pairs for this item, in the order they're compared, for instance
+    /// `[("created_at", ...), ("id", ...)]` for `ORDER BY created_at, id`.
+    fn keyset_key(&self) -> Vec<(String, String)>;
+}
+
+/// Cursor provider for SQL keyset pagination. Each edge gets a `CompositeCursor` of the item's
+/// `KeysetKey`, so the next page starts from the last row seen rather than an offset. That keeps
+/// queries fast deep into the set, and pages don't shift when rows are inserted ahead of them.
+///
+/// The resolver turns the `after` cursor back into a row comparison, fetching one extra row to find
+/// out if there's another page:
+///
+/// ```
+/// use juniper::GraphQLObject;
+/// use juniper_relay_helpers::{
+///     CompositeCursor, CursorError, KeysetCursorProvider, KeysetKey, OverfetchPaginator,
+///     PageRequest, RelayConnection,
+/// };
+///
+/// #[derive(Clone, GraphQLObject, RelayConnection)]
+/// #[relay(cursor = CompositeCursor)]
+/// pub struct Journal {
+///     pub id: i32,
+///     pub written_at: String,
+/// }
+///
+/// impl KeysetKey for Journal {
+///     fn keyset_key(&self) -> Vec<(String, String)> {
+///         vec![
+///             ("written_at".to_string(), self.written_at.clone()),
+///             ("id".to_string(), self.id.to_string()),
+///         ]
+///     }
+/// }
+///
+/// fn journals(
+///     page_request: PageRequest<CompositeCursor>,
+/// ) -> Result<JournalRelayConnection, CursorError> {
+///     // ORDER BY written_at, id, so the cursor compares the same columns in the same order.
+///     let mut sql = "SELECT id, written_at FROM journals".to_string();
+///     let mut params: Vec<String> = vec![];
+///     if let Some(after) = &page_request.after {
+///         // Only the columns we sort by, and nothing else the client might have added.
+///         let (Some(written_at), Some(id), 2) =
+///             (after.get("written_at"), after.get("id"), after.columns.len())
+///         else {
+///             return Err(CursorError::InvalidCursor);
+///         };
+///         sql.push_str(" WHERE (written_at, id) > (?, ?)");
+///         params.extend([written_at.to_string(), id.to_string()]);
+///     }
+///
+///     // Pages of 20 unless asked otherwise, fetching one row more to see if another page follows.
+///     let first = page_request.first.unwrap_or(20);
+///     let paginator = OverfetchPaginator::new(PageRequest { first: Some(first), ..page_request });
+///     let limit = paginator.fetch_limit().unwrap_or_default();
+///     sql.push_str(&format!(" ORDER BY written_at, id LIMIT {limit}"));
+///
+///     // ... run the query ...
+///     let rows = vec![Journal { id: 8, written_at: "1905-01-02".to_string() }];
+///
+///     let page = paginator.paginate(rows, None);
+///     Ok(JournalRelayConnection::from_metadata(
+///         Some(&page.items),
+///         page.metadata,
+///         KeysetCursorProvider::new(),
+///     ))
+/// }
+/// ```
+///
+/// The column names in a decoded cursor come from the client, so never put them into the query.
+/// Write the comparison for the columns you sort by and bind the cursor's values for them.
+///
+/// `has_next_page` comes from `PaginationMetadata::more_available`, which the `OverfetchPaginator`
+/// sets from the extra row. Without it, a full page (as many items as `first`) is taken to mean more may
+/// follow. If an `after` is provided, it's assumed there is a previous page.
+pub struct KeysetCursorProvider<ItemT> {
+    _item: PhantomData<fn(&ItemT)>,
+}
+
+impl<ItemT> KeysetCursorProvider<ItemT> {
+    pub fn new() -> Self {
+        KeysetCursorProvider { _item: PhantomData }
+    }
+}
+
+impl<ItemT> Default for KeysetCursorProvider<ItemT> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<ItemT> CursorProvider<ItemT> for KeysetCursorProvider<ItemT>
+where
+    ItemT: KeysetKey,
+{
+    type CursorType = CompositeCursor;
+
+    fn get_cursor_for_item(
+        &self,
+        _metadata: &PaginationMetadata<CompositeCursor>,
+        _item_idx: i32,
+        item: Option<&ItemT>,
+    ) -> CompositeCursor {
+        CompositeCursor::new(item.map(KeysetKey::keyset_key).unwrap_or_default())
+    }
+
+    fn get_page_info<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<CompositeCursor>,
+        items: Option<&[Option<ItemT>]>,
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<CompositeCursor>,
+    {
+        full_page_info(metadata, items, |idx, item: &Option<ItemT>| {
+            self.get_cursor_for_item(metadata, idx, item.as_ref())
+        })
+    }
+
+    fn get_page_info_for_cursors<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<CompositeCursor>,
+        items: Option<&[Option<ItemT>]>,
+        cursors: &[CompositeCursor],
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<CompositeCursor>,
+    {
+        full_page_info(metadata, items, |idx, item: &Option<ItemT>| {
+            built_cursor(cursors, idx, || {
+                self.get_cursor_for_item(metadata, idx, item.as_ref())
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CompositeCursor, Cursor, KeysetCursorProvider, KeysetKey, OverfetchPaginator, PageRequest,
+        RelayConnection,
+    };
+    use juniper::GraphQLObject;
+
+    #[derive(Debug, Clone, GraphQLObject, RelayConnection)]
+    #[relay(cursor = CompositeCursor)]
+    pub struct Entry {
+        id: i32,
+        act: i32,
+    }
+
+    impl KeysetKey for Entry {
+        fn keyset_key(&self) -> Vec<(String, String)> {
+            vec![
+                ("act".to_string(), self.act.to_string()),
+                ("id".to_string(), self.id.to_string()),
+            ]
+        }
+    }
+
+    fn rows(ids: &[(i32, i32)]) -> Vec<Entry> {
+        ids.iter().map(|&(act, id)| Entry { id, act }).collect()
+    }
+
+    fn entries(ids: &[(i32, i32)]) -> Vec<Option<Entry>> {
+        rows(ids).into_iter().map(Some).collect()
+    }
+
+    fn key(act: &str, id: &str) -> CompositeCursor {
+        CompositeCursor::new(vec![
+            ("act".to_string(), act.to_string()),
+            ("id".to_string(), id.to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_cursors_from_keyset() {
+        let items = entries(&[(1, 4), (2, 1)]);
+        let conn = EntryRelayConnection::new(Some(&items), None, KeysetCursorProvider::new(), None);
+
+        let cursors: Vec<Option<CompositeCursor>> = conn
+            .edges
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .map(|edge| edge.cursor)
+            .collect();
+        assert_eq!(cursors, vec![Some(key("1", "4")), Some(key("2", "1"))]);
+        assert_eq!(conn.page_info.end_cursor, Some(key("2", "1")));
+
+        // Round-trips, so the resolver can compare against it.
+        let after = CompositeCursor::from_encoded_string(
+            &conn.page_info.end_cursor.unwrap().to_encoded_string(),
+        )
+        .unwrap();
+        assert_eq!(after.get("act"), Some("2"));
+        assert_eq!(after.get("id"), Some("1"));
+    }
+
+    #[test]
+    fn test_overfetch() {
+        let paginator =
+            OverfetchPaginator::new(PageRequest::new(Some(2), Some(key("1", "1")), None));
+
+        let page = paginator.paginate(rows(&[(1, 2), (1, 3), (2, 1)]), None);
+        let conn = EntryRelayConnection::from_metadata(
+            Some(&page.items),
+            page.metadata,
+            KeysetCursorProvider::new(),
+        );
+        assert!(conn.page_info.has_previous_page);
+        assert!(conn.page_info.has_next_page);
+        assert_eq!(conn.page_info.end_cursor, Some(key("1", "3")));
+
+        let page = paginator.paginate(rows(&[(2, 1), (2, 2)]), None);
+        let conn = EntryRelayConnection::from_metadata(
+            Some(&page.items),
+            page.metadata,
+            KeysetCursorProvider::new(),
+        );
+        assert!(!conn.page_info.has_next_page);
+
+        // No overfetch: a full page may have more after it.
+        let conn = EntryRelayConnection::new(
+            Some(&entries(&[(2, 1), (2, 2)])),
+            None,
+            KeysetCursorProvider::new(),
+            Some(PageRequest::new(Some(2), Some(key("1", "1")), None)),
+        );
+        assert!(conn.page_info.has_next_page);
+    }
+}
@@ -72,6 +72,57 @@
 //! **Notes**:
 //! - The struct has `RelayConnection` and `RelayEdge` as the suffix to help avoid collisions with your code.
 //! - GraphQL types have `Connection` and `Edge` as the suffix to conform to the spec.
+//! - The connection and edge descriptions in the schema include the node's `///` doc comment, if it
+//!   has one, after the generic "Connection type for Foo." text.
+//! - Generic nodes get generic connections and edges, with the same parameters and bounds, so a
+//!   `Reading<T>` gets a `ReadingRelayConnection<T>`. The PageInfo isn't generic. As with any generic
+//!   `GraphQLObject`, each GraphQL type name can only be used with one set of parameters per schema.
+//! - Nodes can't have lifetime parameters, as the connection owns its nodes. Borrowing fields need to
+//!   be owned, or `&'static` references (which need no parameter), and the derive fails at the
+//!   lifetime otherwise:
+//!
+//! ```compile_fail
+//! use juniper::GraphQLObject;
+//! use juniper_relay_helpers::RelayConnection;
+//!
+//! // error: RelayConnection can't be derived for nodes with lifetime parameters, ...
+//! #[derive(Clone, GraphQLObject, RelayConnection)]
+//! pub struct Pictos<'a> {
+//!     pub name: &'a str,
+//! }
+//! ```
+//!
+//! - The generated types have the same visibility as the node, so a `pub(crate)` node gets
+//!   `pub(crate)` connection, edge and PageInfo types, and a private node's stay private:
+//!
+//! ```compile_fail,E0603
+//! mod canvas {
+//!     use juniper::GraphQLObject;
+//!     use juniper_relay_helpers::RelayConnection;
+//!
+//!     #[derive(Clone, GraphQLObject, RelayConnection)]
+//!     struct Sketch {
+//!         pub title: String,
+//!     }
+//! }
+//!
+//! // error: struct `SketchRelayConnection` is private
+//! let _ = canvas::SketchRelayConnection::empty;
+//! ```
+//!
+//! - The node must be a GraphQL object, as well as `Clone`. The order of the derives doesn't matter,
+//!   but `GraphQLObject` (or a `#[graphql_object]` impl) needs to be there. Without it, the derive
+//!   fails with an error pointing at the node:
+//!
+//! ```compile_fail,E0277
+//! use juniper_relay_helpers::RelayConnection;
+//!
+//! // error: `Gestral` must be a GraphQL object to derive `RelayConnection`
+//! #[derive(Clone, RelayConnection)]
+//! pub struct Gestral {
+//!     pub name: String,
+//! }
+//! ```
 //!
 //! ## Building Connection responses
 //!
@@ -141,6 +192,9 @@
 //! You can either manually build this object up yourself or if you use an implementation of `CursorProvider`
 //! it can build this information for you.
 //!
+//! To build it yourself, the generated PageInfo type has a `builder()` for setting only the fields you
+//! need, and `empty()` for a page with no results.
+//!
 //! ## Page Request
 //!
 //! Pagination requests in Relay usually are specified by a ``first``, ``after`` and ``before`` arguments.
@@ -157,6 +211,34 @@
 //! Usage of this is optional for the most part, but if you want to use the `RelayConnection::new` method
 //! of building responses, it expects a `PageRequest` to be passed in.
 //!
+//! Resolvers can also take all four arguments as a single `ConnectionArgs<MyCursor>` input object
+//! and turn it into a `PageRequest` with `into_page_request`.
+//!
+//! ## Resolvers
+//!
+//! To skip the argument handling altogether, put `#[relay_resolvers]` above `#[graphql_object]` and
+//! mark connection fields with `#[relay_resolver]`. Each marked method gets `first` and `after`
+//! arguments and a `page_request` built from them, and its body only returns `(nodes, total)`:
+//!
+//! ```
+//! # use juniper::{FieldResult, GraphQLObject};
+//! # use juniper_relay_helpers::{relay_resolvers, OffsetCursor, RelayConnection};
+//! # #[derive(Clone, GraphQLObject, RelayConnection)]
+//! # #[relay(cursor = OffsetCursor)]
+//! # pub struct Location { pub name: String }
+//! # pub struct QueryRoot;
+//! #[relay_resolvers]
+//! #[juniper::graphql_object]
+//! impl QueryRoot {
+//!     #[relay_resolver]
+//!     fn locations() -> FieldResult<LocationRelayConnection> {
+//!         let take = page_request.first.unwrap_or(10);
+//!         let locations = (0..take).map(|n| Location { name: format!("Location {n}") });
+//!         (locations, Some(100))
+//!     }
+//! }
+//! ```
+//!
 //! ## Cursors
 //!
 //! Relay requires edges and pagination info to contain opaque strings called "cursors".
@@ -184,7 +266,32 @@
 //! # }
 //! ```
 //!
-//! Implementing your own cursor is as simple as implementing the `Cursor` trait.
+//! Tables with more rows than fit in an `i32` can use the `OffsetCursor64` instead, which encodes
+//! the same way so existing `OffsetCursor` values still decode.
+//!
+//! For keyset pagination over several columns, such as `(created_at, id)`, the `CompositeCursor`
+//! holds the column name/value pairs of the last row seen. Time-ordered feeds can use the
+//! `TimestampCursor` from the `chrono` feature instead, which holds a `DateTime<Utc>` and an id to
+//! break ties.
+//!
+//! Implementing your own cursor is as simple as implementing the `Cursor` trait. If its layout is
+//! likely to change, implement `VersionedCursorFormat` too and wrap it in a `VersionedCursor`, which
+//! writes a `v{n}` version segment ahead of it so cursors from older layouts can still be decoded.
+//! It's named after the inner cursor in the schema, so `VersionedCursor<FeedCursor>` becomes a
+//! `VersionedFeedCursor` scalar.
+//!
+//! Offset cursors are trivial for clients to forge. With the `signing` feature enabled, wrap any
+//! cursor in a `SignedCursor` to have it signed with HMAC-SHA256, and wrap the cursor provider in a
+//! `SignedCursorProvider` to sign the cursors it builds. The secret comes from a type implementing
+//! `CursorSigningKey`. Signed cursors are named after the cursor they wrap, so a
+//! `SignedCursor<OffsetCursor, K>` is a `SignedOffsetCursor` scalar.
+//!
+//! Signing stops cursors being changed, but anyone can still decode them. If their contents need to
+//! stay private, enable the `encryption` feature and wrap the cursor in an `EncryptedCursor`, which
+//! encrypts it with AES-256-GCM using the key from a `CursorEncryptionKey`. It's named after the
+//! wrapped cursor in the same way, as `EncryptedOffsetCursor` and so on. Where the key is only known
+//! at runtime, an `EncryptedCursorProvider` built with it hands out `EncryptedToken` cursors
+//! instead, and decrypts them again with `decrypt_page_request`.
 //!
 //! ## Cursor providers
 //!
@@ -194,13 +301,43 @@
 //! `CursorProvider` is a trait that allows you to easily generate cursors for each of the items
 //! in the result set.
 //!
-//! For a reference implementation, see the `OffsetCursorProvider` struct.
+//! For a reference implementation, see the `OffsetCursorProvider` struct. The
+//! `OffsetCursorProvider64` does the same for `OffsetCursor64`, taking an `i64` total count.
+//!
+//! For NoSQL use cases, there is also the `KeyedCursorProvider`. If an item's key depends on the
+//! request (such as a per-user sort position), use the `KeyedCursorProviderWithCtx` with items
+//! implementing `CursorByKeyWithCtx` instead.
+//!
+//! For SQL keyset pagination, the `KeysetCursorProvider` builds a `CompositeCursor` from each item's
+//! sort columns (via `KeysetKey`), for the resolver to turn back into a `WHERE (a, b) > (?, ?)`.
+//!
+//! With the `chrono` feature, the `TimestampCursorProvider` builds a `TimestampCursor` from each
+//! item's `CursorByTimestamp` implementation.
 //!
-//! For NoSQL use cases, there is also the `KeyedCursorProvider`.
+//! For a one-off cursor rule, `FnCursorProvider::new` builds a provider from a closure instead.
+//!
+//! Cursors that need I/O to build, such as tokens looked up in a cache, can come from an
+//! `AsyncCursorProvider` instead, with the connection built by `RelayConnection::new_async`.
+//!
+//! To pick a provider at runtime, box it as a `dyn DynCursorProvider`, the object safe version of
+//! `CursorProvider` that every provider implements.
+//!
+//! To page over a large slice without cloning the nodes (or when they aren't `Clone`), build an
+//! `IndexConnection`. Its edges hold indices into the slice, for resolvers to look the nodes up by.
+//!
+//! For immutable data, the `ContentHashCursorProvider` (behind the `content-hash` feature) builds each
+//! cursor from a hash of the node's content, so cursors don't change when the ordering does.
+//!
+//! Providers that can also page backwards with `last` and `before` implement
+//! `BidirectionalCursorProvider`. The `OffsetCursorProvider` does this, treating the `before` offset
+//! as the exclusive end of the page. Build these requests with `PageRequest::new_backward`.
 //!
 //! **Note**: remember that offset cursors are massively prone to off-by-one errors. The cursor provided
 //! to the `after` argument **means** after - if you're using database offsets or memory slices, you need to
-//! add `+ 1` to the provided offset to get the _actual_ starting point.
+//! add `+ 1` to the provided offset to get the _actual_ starting point. `OffsetCursor::to_sql_limit_offset`
+//! does this for you, turning the `after` cursor and the page's `first` into the `LIMIT` and
+//! `OFFSET` to bind into a query. `PageRequest::to_sql_limit_offset` does the same for a whole
+//! request, with or without an `after`.
 //!
 //! See the example app for a more detailed example of how to handle this.
 //!
@@ -234,6 +371,22 @@
 //! implemented as a `GraphQLScalar` for use directly in Juniper, so you can return it directly from
 //! your DTO object or field resolver.
 //!
+//! To decode an ID string yourself, such as in a `node(id:)` resolver, use `RelayIdentifier::parse`
+//! (or `str::parse`). `expect_type` then checks it's the type of ID you were expecting, returning an
+//! `IdentifierError` if not.
+//!
+//! For the `node(id: ID!): Node` field, `relay_node_resolver!` does both: it decodes the ID and
+//! matches on its discriminator, running the loader you give for each type.
+//!
+//! Any id type that implements `Display` and `FromStr` works. With the `uuid` feature, the
+//! `UuidRelayIdentifier` alias covers the common case of `Uuid` ids.
+//!
+//! IDs are opaque in production, but reading `character::123` is easier than decoding base64 while
+//! developing. A `RelayIdentifier<T, TD, ReadableIds>` writes itself out in that form instead, and
+//! accepts it back as input too. The format is part of the type, so a `#[cfg(debug_assertions)]`
+//! type alias picking `ReadableIds` for debug builds and the default `OpaqueIds` otherwise switches
+//! a whole schema over.
+//!
 //! ## IdentifierTypeDiscriminator
 //!
 //! To be able to use an `enum` as your identifier discriminator, you need to implement a couple of traits.
@@ -252,6 +405,22 @@
 //! let id = RelayIdentifier::new("123".to_string(), MyEntityTypes::CHARACTER);
 //! ```
 //!
+//! Values are the lowercased variant names, matched exactly when decoding. `#[discriminator("...")]`
+//! on a variant picks its value, and `#[relay(case_insensitive)]` on the enum accepts any casing.
+//! Each value can only be used once:
+//!
+//! ```compile_fail
+//! use juniper_relay_helpers::IdentifierTypeDiscriminator;
+//!
+//! #[derive(IdentifierTypeDiscriminator)]
+//! enum MyEntityTypes {
+//!     Character,
+//!     // error: duplicate discriminator value `character`
+//!     #[discriminator("character")]
+//!     PlayableCharacter,
+//! }
+//! ```
+//!
 //! The use of `RelayIdentifier` is entirely optional - you can use your own identifiers or the `juniper::ID` type
 //! and still make use of the `RelayConnection` derive macro. It's just here if you want it.
 //!
@@ -298,6 +467,156 @@
 //! ```
 //!
 //!
+//! # Connection options
+//!
+//! Alongside `context`, the `#[relay(...)]` attribute accepts a few more options to tweak the
+//! generated types:
+//!
+//! - `cursor = MyCursor`: the cursor type used in the edges and `PageInfo`. Defaults to `StringCursor`.
+//! - `expose_page_size`: adds a `pageSize` field to the connection, reporting the `first` (or `last`)
+//!   from the `PageRequest` it was built with.
+//! - `page_args`: generates a `FooPageArgs` input object (`FooRelayPageArgs` in Rust) holding
+//!   `first`, `after`, `last` and `before`. Its `into_page_request` rejects arguments that mix forward
+//!   and backward paging, since there's no `@oneOf` support to express that in the schema.
+//! - `rust_suffix = "Conn"`: the suffix for the Rust connection type name, so `Foo` gets a
+//!   `FooConn` and `FooConnPageInfo`. Defaults to `RelayConnection`. Useful if a `FooRelayConnection`
+//!   already exists.
+//! - `gql_suffix = "List"`: the suffix for the GraphQL connection type name, so `Foo` gets a `FooList`
+//!   and `FooListPageInfo` in the schema. Defaults to `Connection`.
+//! - `edge_rust_suffix = "ListEdge"` and `edge_gql_suffix = "ListEdge"`: the same for the edge type
+//!   names, which the suffixes above leave alone. Default to `RelayEdge` and `Edge`. Set them along
+//!   with the connection suffixes when two nodes of the same name (from different modules, say) are
+//!   in one schema, as their edges would otherwise collide.
+//! - `with_nodes`: adds a flattened `nodes: [Foo!]!` field alongside `edges`, for clients that don't
+//!   need the cursors. Edges without a node are left out of it. The nodes are cloned into it, including
+//!   from `RelayConnection::from_iter`.
+//! - `partial_errors`: adds an `errors: [String!]!` field to the connection, and a `new_partial`
+//!   constructor taking the errors alongside the nodes. Useful when some nodes fail to load (say from
+//!   another service) and you'd rather return the rest than fail the whole query. The other
+//!   constructors leave `errors` empty.
+//! - `rc_node`: the edges hold an `Arc<Foo>` rather than a `Foo`, and the constructors take
+//!   `Arc<Foo>` nodes, so the same node can appear in several connections (a "recent" and a
+//!   "featured" list, say) without being cloned into each. The schema is unchanged. `Rc` isn't
+//!   supported, as Juniper needs the nodes to be `Send + Sync`.
+//! - `non_null_cursor`: makes the edge's `cursor` field non-null (`cursor: Foo!`), as the Relay spec
+//!   has it, for clients that rely on every edge having one. The Rust field is then a plain cursor
+//!   rather than an `Option`. `RelayEdge::try_from_parts` returns `None` without a cursor, so
+//!   `map_edges` leaves out edges that didn't have one. Leave it off to keep cursors nullable, such
+//!   as for the "last page is empty" style of the `KeyedCursorProvider`.
+//! - `no_count`: leaves the `count` field off the connection, as the Relay spec doesn't require it
+//!   and an exact total is expensive on some backends. Pass `None` for `total_items` when building
+//!   it, and the `OffsetCursorProvider` falls back to treating a full page as having a next page.
+//!   Overfetching by one with an `OverfetchPaginator` gives an exact answer instead.
+//! - `count_name = "totalCount"`: renames the `count` field in the schema, as many Relay clients
+//!   expect `totalCount`. The Rust field is still `count`. Defaults to `count`. It can't be combined
+//!   with `no_count`, as there's no field left to rename:
+//!
+//! ```compile_fail
+//! use juniper::GraphQLObject;
+//! use juniper_relay_helpers::RelayConnection;
+//!
+//! #[derive(Clone, GraphQLObject, RelayConnection)]
+//! // error: count_name has no effect with no_count, as there's no count field to rename
+//! #[relay(no_count, count_name = "totalCount")]
+//! pub struct Esquie {
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! - `extra_field(name = "total_unread", ty = "i32")`: adds a public field to the connection, such
+//!   as an aggregate for the whole set, exposed in the schema as `totalUnread`. Repeat it for more
+//!   fields. The constructors fill these in with `Default::default()`, so set them yourself once the
+//!   connection is built. They sit alongside `count`, which is still filled in from the total count
+//!   and is never changed by them. The names `count`, `edges`, `errors`, `nodes`, `page_info` and
+//!   `page_size` are taken.
+//! - `serde`: derives serde's `Serialize` and `Deserialize` for the generated PageInfo. Requires the
+//!   `serde` feature, and the cursor type to implement them too. The built-in cursors and
+//!   `PageRequest` do when the feature is enabled.
+//! - `visibility = "pub(crate)"`: the visibility of the generated types, in place of the node's own.
+//!   Useful for keeping the connection of a `pub` node out of the crate's public API.
+//! - `scalar = MyScalarValue`: for nodes using a custom Juniper `ScalarValue`, such as one adding
+//!   64-bit integers. The generated types are built for that scalar rather than being generic over
+//!   it. The built-in cursors are generic, so they work with any scalar.
+//! - `interface(connection = ConnectionValue, edge = EdgeValue, page_info = PageInfoValue)`: has the
+//!   generated types implement your own GraphQL interfaces. Each key is optional. See below.
+//!
+//! ## Interfaces
+//!
+//! Some schemas share `Connection`, `Edge` and `PageInfo` interfaces between their connections, so
+//! clients can write one fragment for all of them. Juniper needs an interface to list the types that
+//! implement it, so the interfaces are declared in your schema with the generated Rust types in
+//! `for = [...]`, and `interface(...)` points each generated type at the `...Value` type Juniper
+//! generates for its interface:
+//!
+//! ```
+//! use juniper::{graphql_interface, GraphQLObject};
+//! use juniper_relay_helpers::{OffsetCursor, RelayConnection};
+//!
+//! #[graphql_interface(for = [CanvasRelayConnection, BrushRelayConnection])]
+//! trait Connection {
+//!     fn count(&self) -> Option<i32>;
+//!     fn edges(&self) -> Option<Vec<Option<EdgeValue>>>;
+//!     fn page_info(&self) -> PageInfoValue;
+//! }
+//!
+//! #[graphql_interface(for = [CanvasRelayEdge, BrushRelayEdge])]
+//! trait Edge {
+//!     fn cursor(&self) -> Option<OffsetCursor>;
+//! }
+//!
+//! #[graphql_interface(for = [CanvasRelayConnectionPageInfo, BrushRelayConnectionPageInfo])]
+//! trait PageInfo {
+//!     fn has_next_page(&self) -> bool;
+//!     fn end_cursor(&self) -> Option<OffsetCursor>;
+//! }
+//!
+//! #[derive(Clone, GraphQLObject, RelayConnection)]
+//! #[relay(
+//!     cursor = OffsetCursor,
+//!     interface(connection = ConnectionValue, edge = EdgeValue, page_info = PageInfoValue)
+//! )]
+//! pub struct Canvas {
+//!     pub name: String,
+//! }
+//!
+//! #[derive(Clone, GraphQLObject, RelayConnection)]
+//! #[relay(
+//!     cursor = OffsetCursor,
+//!     interface(connection = ConnectionValue, edge = EdgeValue, page_info = PageInfoValue)
+//! )]
+//! pub struct Brush {
+//!     pub size: i32,
+//! }
+//! ```
+//!
+//! The interface fields can return the other interfaces, as `edges` and `page_info` do here, since
+//! every connection has its own edge and PageInfo types. Fields that return a cursor only line up
+//! when all the implementing connections use the same cursor type.
+//!
+//! # Exporting
+//!
+//! Enabling the `export` feature adds `export_all`, which pages through a whole connection set with
+//! an async page fetcher and writes each node as a line of JSON, without holding more than one page
+//! in memory. It's built on the `RelayConnection::edges`, `RelayConnection::page_info` and
+//! `RelayPageInfo` accessors, which you can also use to walk connections yourself.
+//!
+//! # Testing utilities
+//!
+//! Enabling the `test-util` feature adds the `CursorStabilityTester`, which pages through a dataset
+//! with a given `CursorProvider` while simulating inserts between pages, and reports any nodes that
+//! were skipped or duplicated. It's a quick way to see why offset cursors drift under concurrent
+//! writes while keyed cursors don't.
+//!
+//! It also adds the `MockCursorProvider`, which numbers items `"0"`, `"1"`, `"2"` and so on, and
+//! `assert_connection_page`, which checks a connection's edge cursors and PageInfo in one go. Together
+//! they save hand-building `PaginationMetadata` in resolver tests. The `testing` feature enables
+//! `test-util` too, so either one adds them, typically as a dev-dependency:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! juniper_relay_helpers = { version = "0.7", features = ["testing"] }
+//! ```
+//!
 //! # Example App
 //!
 //! You can see the library in action in the example app in `/juniper_relay_helpers_test`.
@@ -310,26 +629,94 @@
 
 extern crate self as juniper_relay_helpers;
 
+mod async_cursor_provider;
+mod connection_args;
 mod connections;
+#[cfg(feature = "content-hash")]
+mod content_hash;
 mod cursor_errors;
 mod cursor_provider;
 mod cursors;
+mod dyn_cursor_provider;
 mod edges;
+#[cfg(feature = "encryption")]
+mod encrypted_cursor;
+#[cfg(feature = "export")]
+mod export;
 mod identifier;
+mod index_connection;
+mod keyset_cursor_provider;
+mod offset_cursor_provider_64;
 mod page_info_factory;
 mod page_request;
+mod pagination;
 mod pagination_metadata;
+#[cfg(feature = "signing")]
+mod signed_cursor;
+mod stored_cursor;
+#[cfg(any(test, feature = "test-util"))]
+mod test_util;
+#[cfg(feature = "chrono")]
+mod timestamp_cursor_provider;
 
 // From other crates in the workspace:
-pub use juniper_relay_helpers_codegen::{IdentifierTypeDiscriminator, RelayConnection};
+pub use juniper_relay_helpers_codegen::{
+    IdentifierTypeDiscriminator, RelayConnection, relay_resolver, relay_resolvers,
+};
 
 // From this crate:
+pub use async_cursor_provider::*;
+pub use connection_args::*;
 pub use connections::*;
+#[cfg(feature = "content-hash")]
+pub use content_hash::*;
 pub use cursor_errors::*;
 pub use cursor_provider::*;
 pub use cursors::*;
+pub use dyn_cursor_provider::*;
 pub use edges::*;
+#[cfg(feature = "encryption")]
+pub use encrypted_cursor::*;
+#[cfg(feature = "export")]
+pub use export::*;
 pub use identifier::*;
+pub use index_connection::*;
+pub use keyset_cursor_provider::*;
+pub use offset_cursor_provider_64::*;
 pub use page_info_factory::*;
 pub use page_request::*;
+pub use pagination::*;
 pub use pagination_metadata::*;
+#[cfg(feature = "signing")]
+pub use signed_cursor::*;
+pub use stored_cursor::*;
+#[cfg(any(test, feature = "test-util"))]
+pub use test_util::*;
+#[cfg(feature = "chrono")]
+pub use timestamp_cursor_provider::*;
+
+/// Re-exports used by the generated code. Not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "serde")]
+    pub use serde;
+
+    /// Bound the derive checks the node against, so forgetting `GraphQLObject` points at the cause
+    /// rather than somewhere deep in the generated edge.
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` must be a GraphQL object to derive `RelayConnection`",
+        label = "`{Self}` is not a GraphQL object",
+        note = "add `#[derive(juniper::GraphQLObject)]` to `{Self}`, or implement it with `#[juniper::graphql_object]`",
+        note = "if `{Self}` uses a custom `ScalarValue`, pass it to the derive as well with `#[relay(scalar = ...)]`"
+    )]
+    pub trait RelayNode<S> {}
+
+    impl<T, S> RelayNode<S> for T
+    where
+        T: juniper::GraphQLType<S> + ?Sized,
+        S: juniper::ScalarValue,
+    {
+    }
+
+    pub fn assert_relay_node<S, T: RelayNode<S> + ?Sized>() {}
+}
@@ -0,0 +1,413 @@
+use crate::cursor_provider::built_cursor;
+use crate::{
+    BidirectionalCursorProvider, CursorProvider, OffsetCursor64, PageInfoFactory,
+    PaginationMetadata,
+};
+
+/// Cursor provider for `OffsetCursor64`, for result sets with more items than fit in an `i32`.
+/// Pages the same way as the `OffsetCursorProvider`, forwards with `first`/`after` and backwards
+/// with `last`/`before`.
+///
+/// `PaginationMetadata::total_count` (and the connection's `count`) is an `i32` to match GraphQL's
+/// `Int`, so a larger total is given to the provider with `with_total_count` instead. When set it
+/// takes precedence over the metadata's total.
+///
+/// ```
+/// use juniper::GraphQLObject;
+/// use juniper_relay_helpers::{
+///     OffsetCursor64, OffsetCursorProvider64, PageRequest, RelayConnection,
+/// };
+///
+/// #[derive(Clone, GraphQLObject, RelayConnection)]
+/// #[relay(cursor = OffsetCursor64)]
+/// pub struct Reading {
+///     pub value: f64,
+/// }
+///
+/// let readings = vec![Some(Reading { value: 1.5 }), Some(Reading { value: 2.5 })];
+/// let conn = ReadingRelayConnection::new(
+///     Some(&readings),
+///     None,
+///     OffsetCursorProvider64::new().with_total_count(10_000_000_000),
+///     Some(PageRequest::new(Some(2), Some(OffsetCursor64::new(5_000_000_000)), None)),
+/// );
+/// assert_eq!(conn.page_info.end_cursor, Some(OffsetCursor64::new(5_000_000_002)));
+/// assert!(conn.page_info.has_next_page);
+/// ```
+pub struct OffsetCursorProvider64 {
+    total_count: Option<i64>,
+    include_first: bool,
+    include_total: bool,
+}
+
+impl<ItemT> CursorProvider<ItemT> for OffsetCursorProvider64 {
+    type CursorType = OffsetCursor64;
+
+    fn get_cursor_for_item(
+        &self,
+        metadata: &PaginationMetadata<OffsetCursor64>,
+        item_idx: i32,
+        _item: Option<&ItemT>,
+    ) -> OffsetCursor64 {
+        let start = match self.backward_window_start(metadata) {
+            Some(start) => start,
+            None => Self::forward_window_start(metadata),
+        };
+        self.cursor_at(metadata, start.saturating_add(item_idx.into()))
+    }
+
+    fn get_page_info<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<OffsetCursor64>,
+        items: Option<&[Option<ItemT>]>,
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<OffsetCursor64>,
+    {
+        // As with the `OffsetCursorProvider`, only a count of 0 with no items is an empty set.
+        if self.known_total_count(metadata) == Some(0) && items.is_none_or(<[_]>::is_empty) {
+            return PageInfoType::new(false, false, None, None);
+        }
+
+        if self.backward_window_start(metadata).is_some() {
+            return self.reverse_page_info(metadata, items);
+        }
+
+        let start = Self::forward_window_start(metadata);
+        self.forward_page_info(metadata, items, |idx| {
+            self.cursor_at(metadata, start.saturating_add(idx))
+        })
+    }
+
+    fn get_page_info_for_cursors<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<OffsetCursor64>,
+        items: Option<&[Option<ItemT>]>,
+        cursors: &[OffsetCursor64],
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<OffsetCursor64>,
+    {
+        if (self.known_total_count(metadata) == Some(0) && items.is_none_or(<[_]>::is_empty))
+            || self.backward_window_start(metadata).is_some()
+        {
+            return self.get_page_info(metadata, items);
+        }
+
+        let start = Self::forward_window_start(metadata);
+        self.forward_page_info(metadata, items, |idx| {
+            built_cursor(cursors, idx as i32, || {
+                self.cursor_at(metadata, start.saturating_add(idx))
+            })
+        })
+    }
+}
+
+/// Pages backwards the same way as the `OffsetCursorProvider`: `last` items back from the `before`
+/// offset (or the end of the set), clamped to the start of the set.
+impl<ItemT> BidirectionalCursorProvider<ItemT> for OffsetCursorProvider64 {
+    fn reverse_page_info<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<OffsetCursor64>,
+        items: Option<&[Option<ItemT>]>,
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<OffsetCursor64>,
+    {
+        let before = metadata
+            .page_request
+            .as_ref()
+            .and_then(|pr| pr.before.as_ref());
+        let start = self.window_start_before(metadata);
+
+        let has_next_page = metadata.more_available.unwrap_or_else(|| {
+            before.is_some_and(|b| {
+                self.known_total_count(metadata)
+                    .is_none_or(|total| b.offset < total)
+            })
+        });
+
+        let item_count = items.map(|i| i.len()).unwrap_or(0) as i64;
+        PageInfoType::new(
+            start > 0,
+            has_next_page,
+            (item_count > 0).then(|| self.cursor_at(metadata, start)),
+            (item_count > 0)
+                .then(|| self.cursor_at(metadata, start.saturating_add(item_count - 1))),
+        )
+    }
+}
+
+impl Default for OffsetCursorProvider64 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OffsetCursorProvider64 {
+    pub fn new() -> Self {
+        OffsetCursorProvider64 {
+            total_count: None,
+            include_first: false,
+            include_total: false,
+        }
+    }
+
+    /// Sets the total number of items in the result set. Negative values mean "unknown", as with
+    /// `PaginationMetadata::total_count`.
+    pub fn with_total_count(mut self, total_count: i64) -> Self {
+        self.total_count = Some(total_count);
+        self
+    }
+
+    /// Embeds the requested `first` into each cursor. See
+    /// `OffsetCursorProvider::with_page_size_in_cursors`.
+    pub fn with_page_size_in_cursors(mut self) -> Self {
+        self.include_first = true;
+        self
+    }
+
+    /// Embeds the total count into each cursor. See `OffsetCursorProvider::with_total_in_cursors`.
+    pub fn with_total_in_cursors(mut self) -> Self {
+        self.include_total = true;
+        self
+    }
+
+    /// The total from `with_total_count` if it was given, otherwise the one in the metadata.
+    fn known_total_count(&self, metadata: &PaginationMetadata<OffsetCursor64>) -> Option<i64> {
+        match self.total_count {
+            Some(total) => Some(total).filter(|total| *total >= 0),
+            None => metadata.known_total_count().map(i64::from),
+        }
+    }
+
+    fn cursor_at(
+        &self,
+        metadata: &PaginationMetadata<OffsetCursor64>,
+        offset: i64,
+    ) -> OffsetCursor64 {
+        let mut cursor = OffsetCursor64::new(offset);
+        if self.include_first {
+            cursor = cursor.with_first(
+                metadata
+                    .page_request
+                    .as_ref()
+                    .and_then(|pr| pr.first)
+                    .map(i64::from),
+            );
+        }
+        if self.include_total {
+            cursor = cursor.with_total(self.known_total_count(metadata));
+        }
+        cursor
+    }
+
+    /// PageInfo for a forward page, with `cursor_for` building the cursor for the item at an index.
+    fn forward_page_info<ItemT, PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<OffsetCursor64>,
+        items: Option<&[Option<ItemT>]>,
+        cursor_for: impl Fn(i64) -> OffsetCursor64,
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<OffsetCursor64>,
+    {
+        let start = Self::forward_window_start(metadata);
+        let item_count = items.map(|i| i.len()).unwrap_or(0) as i64;
+
+        let has_next_page = metadata.more_available.unwrap_or_else(|| {
+            let first = metadata.page_request.as_ref().and_then(|pr| pr.first);
+            match (first, self.known_total_count(metadata)) {
+                (Some(first), Some(total)) => start.saturating_add(first.into()) < total,
+                (Some(first), None) => first > 0 && item_count >= first.into(),
+                // No `first` means the entire result set was requested.
+                (None, _) => false,
+            }
+        });
+
+        PageInfoType::new(
+            start > 0,
+            has_next_page,
+            (item_count > 0).then(|| cursor_for(0)),
+            (item_count > 0).then(|| cursor_for(item_count - 1)),
+        )
+    }
+
+    /// Offset of the first item in a forward page: the item following the `after` cursor, or the
+    /// start of the set.
+    fn forward_window_start(metadata: &PaginationMetadata<OffsetCursor64>) -> i64 {
+        metadata
+            .page_request
+            .as_ref()
+            .and_then(|pr| pr.after.as_ref())
+            .map_or(0, |after| after.offset.saturating_add(1))
+    }
+
+    fn window_start_before(&self, metadata: &PaginationMetadata<OffsetCursor64>) -> i64 {
+        let pr = metadata.page_request.as_ref();
+        let end = pr
+            .and_then(|pr| pr.before.as_ref())
+            .map(|before| before.offset)
+            .or(self.known_total_count(metadata))
+            .unwrap_or(0);
+
+        match pr.and_then(|pr| pr.last) {
+            Some(last) => end.saturating_sub(last.into()).max(0),
+            None => 0,
+        }
+    }
+
+    /// Returns the start of the window if this is a backwards request (`last` without `after`).
+    fn backward_window_start(&self, metadata: &PaginationMetadata<OffsetCursor64>) -> Option<i64> {
+        let pr = metadata.page_request.as_ref()?;
+        if pr.last.is_some() && pr.after.is_none() {
+            Some(self.window_start_before(metadata))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CursorProvider, OffsetCursor64, OffsetCursorProvider64, PageRequest, PaginationMetadata,
+        RelayConnection,
+    };
+    use juniper::GraphQLObject;
+
+    #[derive(Debug, Clone, GraphQLObject, RelayConnection)]
+    #[relay(cursor = OffsetCursor64)]
+    pub struct Star {
+        name: String,
+    }
+
+    fn stars(count: usize) -> Vec<Option<Star>> {
+        (0..count)
+            .map(|i| {
+                Some(Star {
+                    name: format!("Star {i}"),
+                })
+            })
+            .collect()
+    }
+
+    fn page_info(
+        provider: &OffsetCursorProvider64,
+        total_count: Option<i32>,
+        page_request: PageRequest<OffsetCursor64>,
+        items: &[Option<Star>],
+    ) -> StarRelayConnectionPageInfo {
+        provider.get_page_info(
+            &PaginationMetadata {
+                total_count,
+                page_request: Some(page_request),
+                more_available: None,
+            },
+            Some(items),
+        )
+    }
+
+    #[test]
+    fn test_cursors_beyond_i32() {
+        let items = stars(3);
+        let after = i64::from(i32::MAX);
+        let conn = StarRelayConnection::new(
+            Some(&items),
+            None,
+            OffsetCursorProvider64::new().with_total_count(after + 4),
+            Some(PageRequest::new(
+                Some(3),
+                Some(OffsetCursor64::new(after)),
+                None,
+            )),
+        );
+
+        let offsets: Vec<i64> = conn
+            .edges
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .map(|edge| edge.cursor.unwrap().offset)
+            .collect();
+        assert_eq!(offsets, vec![after + 1, after + 2, after + 3]);
+        assert!(conn.page_info.has_previous_page);
+        assert!(!conn.page_info.has_next_page);
+    }
+
+    #[test]
+    fn test_forward_page_info() {
+        let p = OffsetCursorProvider64::new();
+        let items = stars(5);
+
+        let first = page_info(&p, Some(13), PageRequest::new(Some(5), None, None), &items);
+        assert!(!first.has_previous_page);
+        assert!(first.has_next_page);
+        assert_eq!(first.start_cursor, Some(OffsetCursor64::new(0)));
+        assert_eq!(first.end_cursor, Some(OffsetCursor64::new(4)));
+
+        let last = page_info(
+            &p,
+            Some(10),
+            PageRequest::new(Some(5), first.end_cursor, None),
+            &items,
+        );
+        assert!(last.has_previous_page);
+        assert!(!last.has_next_page);
+        assert_eq!(last.start_cursor, Some(OffsetCursor64::new(5)));
+        assert_eq!(last.end_cursor, Some(OffsetCursor64::new(9)));
+
+        // Without a total, a full page may have more following it.
+        let unknown = page_info(&p, None, PageRequest::new(Some(5), None, None), &items);
+        assert!(unknown.has_next_page);
+    }
+
+    #[test]
+    fn test_provider_total_takes_precedence() {
+        let items = stars(2);
+        let request = || PageRequest::new(Some(2), Some(OffsetCursor64::new(9)), None);
+
+        let from_metadata = page_info(&OffsetCursorProvider64::new(), Some(12), request(), &items);
+        assert!(!from_metadata.has_next_page);
+
+        let p = OffsetCursorProvider64::new()
+            .with_total_count(6_000_000_000)
+            .with_total_in_cursors();
+        let from_provider = page_info(&p, Some(12), request(), &items);
+        assert!(from_provider.has_next_page);
+        assert_eq!(
+            from_provider.end_cursor,
+            Some(OffsetCursor64::new(11).with_total(Some(6_000_000_000)))
+        );
+
+        let empty = page_info(
+            &OffsetCursorProvider64::new().with_total_count(0),
+            Some(12),
+            request(),
+            &[],
+        );
+        assert!(!empty.has_previous_page);
+        assert!(!empty.has_next_page);
+    }
+
+    #[test]
+    fn test_backward_page_info() {
+        let p = OffsetCursorProvider64::new().with_total_count(5_000_000_000);
+        let items = stars(2);
+
+        let pi = page_info(
+            &p,
+            None,
+            PageRequest::new_backward(Some(2), Some(OffsetCursor64::new(4_000_000_000))),
+            &items,
+        );
+        assert!(pi.has_previous_page);
+        assert!(pi.has_next_page);
+        assert_eq!(pi.start_cursor, Some(OffsetCursor64::new(3_999_999_998)));
+        assert_eq!(pi.end_cursor, Some(OffsetCursor64::new(3_999_999_999)));
+
+        let tail = page_info(&p, None, PageRequest::new_backward(Some(2), None), &items);
+        assert!(!tail.has_next_page);
+        assert_eq!(tail.end_cursor, Some(OffsetCursor64::new(4_999_999_999)));
+    }
+}
@@ -14,3 +14,271 @@ where
         end_cursor: Option<CursorT>,
     ) -> Self;
 }
+
+/// Read access to the generated PageInfo structs, for code that works with any connection type.
+///
+/// Implemented for you by the codegen.
+pub trait RelayPageInfo {
+    type CursorType: Cursor;
+
+    /// Whether there is a page following this one.
+    fn has_next_page(&self) -> bool;
+
+    /// Whether there is a page preceding this one.
+    fn has_previous_page(&self) -> bool;
+
+    /// The cursor of the first item in the page.
+    fn start_cursor(&self) -> Option<&Self::CursorType>;
+
+    /// The cursor of the last item in the page, to pass as `after` to fetch the next page.
+    fn end_cursor(&self) -> Option<&Self::CursorType>;
+
+    /// Builds HTTP `Link` header values for the pages either side of this one, for REST endpoints
+    /// backed by the same pagination. The `next` link passes the encoded `end_cursor` as an `after`
+    /// query param and the `prev` link passes the `start_cursor` as `before`. Links are only built
+    /// when the respective `has_*_page` is true and the cursor is present.
+    ///
+    /// ```
+    /// use juniper_relay_helpers::{OffsetCursor, OffsetCursorProvider, RelayConnection, RelayPageInfo};
+    /// # use juniper::GraphQLObject;
+    /// # #[derive(Debug, Clone, GraphQLObject, RelayConnection)]
+    /// # #[relay(cursor = OffsetCursor)]
+    /// # pub struct Weapon { name: String }
+    ///
+    /// let weapons = vec![Some(Weapon { name: "Lighterim".to_string() })];
+    /// let conn = WeaponRelayConnection::new(Some(&weapons), Some(3), OffsetCursorProvider::new(), None);
+    ///
+    /// // The whole set was requested, so there are no pages either side.
+    /// assert!(conn.page_info.to_link_headers("https://example.com/weapons").is_empty());
+    /// ```
+    fn to_link_headers(&self, base_url: &str) -> Vec<String> {
+        let separator = if base_url.contains('?') { '&' } else { '?' };
+        let link = |param: &str, cursor: &Self::CursorType, rel: &str| {
+            // The url safe alphabet only needs the `=` padding escaping to sit in a query string.
+            let cursor = cursor.to_encoded_string().replace('=', "%3D");
+            format!("<{base_url}{separator}{param}={cursor}>; rel=\"{rel}\"")
+        };
+
+        let mut links = vec![];
+        if self.has_next_page()
+            && let Some(end_cursor) = self.end_cursor()
+        {
+            links.push(link("after", end_cursor, "next"));
+        }
+        if self.has_previous_page()
+            && let Some(start_cursor) = self.start_cursor()
+        {
+            links.push(link("before", start_cursor, "prev"));
+        }
+        links
+    }
+}
+
+/// Plain `PageInfo` values, for when a provider needs to inspect or adjust the page info built by
+/// another before handing it on to the generated type.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PageInfoParts<CursorT> {
+    pub has_prev_page: bool,
+    pub has_next_page: bool,
+    pub start_cursor: Option<CursorT>,
+    pub end_cursor: Option<CursorT>,
+}
+
+impl<CursorT: Cursor> PageInfoParts<CursorT> {
+    /// Builds another `PageInfo` type from these values.
+    pub fn into_page_info<PageInfoType>(self) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<CursorT>,
+    {
+        PageInfoType::new(
+            self.has_prev_page,
+            self.has_next_page,
+            self.start_cursor,
+            self.end_cursor,
+        )
+    }
+}
+
+impl<CursorT: Cursor> PageInfoFactory<CursorT> for PageInfoParts<CursorT> {
+    fn new(
+        has_prev_page: bool,
+        has_next_page: bool,
+        start_cursor: Option<CursorT>,
+        end_cursor: Option<CursorT>,
+    ) -> Self {
+        PageInfoParts {
+            has_prev_page,
+            has_next_page,
+            start_cursor,
+            end_cursor,
+        }
+    }
+}
+
+/// Builds a generated PageInfo one field at a time, rather than spelling out the whole struct. Get
+/// one from the generated type's `builder()`. Anything left unset is `false` or `None`.
+///
+/// ```
+/// use juniper_relay_helpers::{OffsetCursor, RelayConnection};
+/// # use juniper::GraphQLObject;
+/// # #[derive(Debug, Clone, GraphQLObject, RelayConnection)]
+/// # #[relay(cursor = OffsetCursor)]
+/// # pub struct Weapon { name: String }
+///
+/// let page_info: WeaponRelayConnectionPageInfo = WeaponRelayConnectionPageInfo::builder()
+///     .has_next_page(true)
+///     .start_cursor(OffsetCursor::new(0))
+///     .end_cursor(OffsetCursor::new(9))
+///     .build();
+/// assert!(!page_info.has_previous_page);
+/// assert_eq!(page_info.end_cursor, Some(OffsetCursor::new(9)));
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PageInfoBuilder<CursorT> {
+    parts: PageInfoParts<CursorT>,
+}
+
+impl<CursorT: Cursor> PageInfoBuilder<CursorT> {
+    pub fn new() -> Self {
+        PageInfoBuilder {
+            parts: PageInfoParts {
+                has_prev_page: false,
+                has_next_page: false,
+                start_cursor: None,
+                end_cursor: None,
+            },
+        }
+    }
+
+    pub fn has_next_page(mut self, has_next_page: bool) -> Self {
+        self.parts.has_next_page = has_next_page;
+        self
+    }
+
+    pub fn has_prev_page(mut self, has_prev_page: bool) -> Self {
+        self.parts.has_prev_page = has_prev_page;
+        self
+    }
+
+    /// The cursor of the first item in the page. It's encoded when the response is written.
+    pub fn start_cursor(mut self, cursor: CursorT) -> Self {
+        self.parts.start_cursor = Some(cursor);
+        self
+    }
+
+    /// The cursor of the last item in the page.
+    pub fn end_cursor(mut self, cursor: CursorT) -> Self {
+        self.parts.end_cursor = Some(cursor);
+        self
+    }
+
+    pub fn build<PageInfoType>(self) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<CursorT>,
+    {
+        self.parts.into_page_info()
+    }
+}
+
+impl<CursorT: Cursor> Default for PageInfoBuilder<CursorT> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        OffsetCursor, OffsetCursorProvider, PageInfoBuilder, PageRequest, RelayConnection,
+        RelayPageInfo,
+    };
+    use juniper::GraphQLObject;
+
+    #[derive(Debug, Clone, GraphQLObject, RelayConnection)]
+    #[relay(cursor = OffsetCursor)]
+    pub struct Outfit {
+        name: String,
+    }
+
+    fn page_info(has_previous_page: bool, has_next_page: bool) -> OutfitRelayConnectionPageInfo {
+        OutfitRelayConnectionPageInfo {
+            has_next_page,
+            has_previous_page,
+            start_cursor: Some(OffsetCursor::new(10)),
+            end_cursor: Some(OffsetCursor::new(19)),
+        }
+    }
+
+    #[test]
+    fn test_link_headers() {
+        assert_eq!(
+            page_info(true, true).to_link_headers("https://example.com/outfits"),
+            vec![
+                r#"<https://example.com/outfits?after=b2Zmc2V0fHwxOQ%3D%3D>; rel="next""#,
+                r#"<https://example.com/outfits?before=b2Zmc2V0fHwxMA%3D%3D>; rel="prev""#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_link_headers_next_only_when_has_next_page() {
+        let links = page_info(false, true).to_link_headers("/outfits?owner=maelle");
+        assert_eq!(
+            links,
+            vec![r#"</outfits?owner=maelle&after=b2Zmc2V0fHwxOQ%3D%3D>; rel="next""#]
+        );
+
+        assert!(
+            page_info(false, false)
+                .to_link_headers("/outfits")
+                .is_empty()
+        );
+        assert!(
+            !page_info(true, false)
+                .to_link_headers("/outfits")
+                .iter()
+                .any(|link| link.ends_with(r#"rel="next""#))
+        );
+    }
+
+    #[test]
+    fn test_link_headers_from_connection() {
+        let outfits = vec![Some(Outfit {
+            name: "Clair Obscur".to_string(),
+        })];
+        let conn = OutfitRelayConnection::new(
+            Some(&outfits),
+            Some(2),
+            OffsetCursorProvider::new(),
+            Some(PageRequest::new(Some(1), None, None)),
+        );
+        assert_eq!(
+            conn.page_info().to_link_headers("/outfits"),
+            vec![r#"</outfits?after=b2Zmc2V0fHww>; rel="next""#]
+        );
+    }
+
+    #[test]
+    fn test_builder() {
+        let built: OutfitRelayConnectionPageInfo = OutfitRelayConnectionPageInfo::builder()
+            .has_prev_page(true)
+            .has_next_page(true)
+            .start_cursor(OffsetCursor::new(10))
+            .end_cursor(OffsetCursor::new(19))
+            .build();
+        let expected = page_info(true, true);
+        assert_eq!(built.has_previous_page, expected.has_previous_page);
+        assert_eq!(built.has_next_page, expected.has_next_page);
+        assert_eq!(built.start_cursor, expected.start_cursor);
+        assert_eq!(built.end_cursor, expected.end_cursor);
+
+        let unset: OutfitRelayConnectionPageInfo = PageInfoBuilder::new().build();
+        let empty = OutfitRelayConnectionPageInfo::empty();
+        for pi in [unset, empty] {
+            assert!(!pi.has_previous_page);
+            assert!(!pi.has_next_page);
+            assert_eq!(pi.start_cursor, None);
+            assert_eq!(pi.end_cursor, None);
+        }
+    }
+}
@@ -1,4 +1,5 @@
-use crate::Cursor;
+use crate::{Cursor, CursorError, PageRequestError};
+use std::cmp::Ordering;
 
 /// Represents a common Relay pagination request pattern. You'd usually build this from the arguments
 /// into the query resolver, and can then pass that into service calls etc.
@@ -14,9 +15,11 @@ use crate::Cursor;
 ///  }
 /// ```
 ///
-/// This struct can be used to represent the first and after arguments.
+/// This struct can be used to represent the first and after arguments, and the last and before
+/// arguments when paging backwards.
 ///
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PageRequest<CursorType>
 where
     CursorType: Cursor,
@@ -29,6 +32,22 @@ where
 
     /// A cursor to use as the pointer to the start of the page.
     pub after: Option<CursorType>,
+
+    /// The number of items to return from the end of the set, when paging backwards from `before`.
+    pub last: Option<i32>,
+}
+
+/// Which way a `PageRequest` pages through the set, from `PageRequest::direction`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Direction {
+    /// Paging forwards with `first` and `after`. Also used when no arguments are given at all.
+    Forward,
+
+    /// Paging backwards with `last` and `before`.
+    Backward,
+
+    /// Arguments for both directions were given, such as `first` with `before`.
+    Ambiguous,
 }
 
 impl<CursorT> PageRequest<CursorT>
@@ -41,7 +60,103 @@ where
             first,
             before,
             after,
+            last: None,
+        }
+    }
+
+    /// Helper method to build a backwards page request from the `last` and `before` arguments.
+    pub fn new_backward(last: Option<i32>, before: Option<CursorT>) -> Self {
+        PageRequest {
+            first: None,
+            before,
+            after: None,
+            last,
+        }
+    }
+
+    /// Builds a request from all four pagination arguments, where paging is either forwards
+    /// (`first`/`after`) or backwards (`last`/`before`) but never both. Fails with
+    /// `PageRequestError::MixedDirections` if arguments from both directions are given, and otherwise
+    /// validates the request as `validate` does.
+    pub fn from_args(
+        first: Option<i32>,
+        after: Option<CursorT>,
+        last: Option<i32>,
+        before: Option<CursorT>,
+    ) -> Result<Self, PageRequestError> {
+        let page_request = PageRequest {
+            first,
+            before,
+            after,
+            last,
+        };
+        if page_request.direction() == Direction::Ambiguous {
+            return Err(PageRequestError::MixedDirections);
+        }
+        page_request.validate()?;
+        Ok(page_request)
+    }
+
+    /// Validates the request against the Relay connection spec. Fails if both `first` and `last`
+    /// are given, if either is negative, or if the `after` cursor doesn't come before the `before`
+    /// cursor (where the cursor type can compare positions).
+    ///
+    /// The error converts into a `FieldError`, so resolvers can call this at the top with `?`.
+    pub fn validate(&self) -> Result<(), PageRequestError> {
+        if self.first.is_some() && self.last.is_some() {
+            return Err(PageRequestError::FirstAndLast);
+        }
+        if self.first.is_some_and(|first| first < 0) {
+            return Err(PageRequestError::NegativeFirst);
+        }
+        if self.last.is_some_and(|last| last < 0) {
+            return Err(PageRequestError::NegativeLast);
+        }
+        if let (Some(after), Some(before)) = (&self.after, &self.before)
+            && after
+                .compare_position(before)
+                .is_some_and(|ordering| ordering != Ordering::Less)
+        {
+            return Err(PageRequestError::ContradictoryCursors);
+        }
+        Ok(())
+    }
+
+    /// Works out which way the request pages from the arguments given. `first`/`after` page forwards
+    /// and `last`/`before` page backwards. A request with neither is treated as forwards, through the
+    /// whole set, and one with both is `Direction::Ambiguous`.
+    pub fn direction(&self) -> Direction {
+        let forward = self.first.is_some() || self.after.is_some();
+        let backward = self.last.is_some() || self.before.is_some();
+        match (forward, backward) {
+            (true, true) => Direction::Ambiguous,
+            (false, true) => Direction::Backward,
+            _ => Direction::Forward,
+        }
+    }
+
+    /// Whether the request pages forwards. See `direction`.
+    pub fn is_forward(&self) -> bool {
+        self.direction() == Direction::Forward
+    }
+
+    /// Whether the request pages backwards. See `direction`.
+    pub fn is_backward(&self) -> bool {
+        self.direction() == Direction::Backward
+    }
+
+    /// Returns the request with `first` capped at `max`, for resolvers that limit the page size.
+    /// A forward request with no `first` asks for the whole set, so it's given a `first` of `max`;
+    /// backward requests are returned unchanged.
+    ///
+    /// Use the clamped `first` as the `LIMIT` of the query, and give the provider the same maximum
+    /// (such as `OffsetCursorProvider::with_max_first`) so the page info agrees with it.
+    pub fn clamp_first(&self, max: i32) -> Self {
+        let mut page_request = self.clone();
+        if self.is_forward() {
+            page_request.first = Some(self.first.map_or(max, |first| first.min(max)));
         }
+        page_request
     }
 
     /// Checks after, and then before, to return the current cursor we're working with.
@@ -52,3 +167,253 @@ where
         }
     }
 }
+
+impl<CursorT> PageRequest<CursorT>
+where
+    CursorT: Cursor<CursorType = CursorT>,
+{
+    /// As `from_args`, but with the cursors still encoded, as they arrive when the resolver declares
+    /// them as `String` or `ID` arguments rather than as the cursor scalar.
+    ///
+    /// ```
+    /// use juniper_relay_helpers::{Cursor, CursorError, OffsetCursor, PageRequest, PageRequestError};
+    ///
+    /// let after = OffsetCursor::new(5).to_encoded_string();
+    /// let page_request = PageRequest::<OffsetCursor>::from_juniper_args(Some(10), Some(after), None, None);
+    /// assert_eq!(page_request, Ok(PageRequest::new(Some(10), Some(OffsetCursor::new(5)), None)));
+    ///
+    /// let page_request = PageRequest::<OffsetCursor>::from_juniper_args(Some(10), None, Some(10), None);
+    /// assert_eq!(
+    ///     page_request,
+    ///     Err(CursorError::InvalidPageRequest(PageRequestError::MixedDirections))
+    /// );
+    /// ```
+    ///
+    /// Fails if either cursor doesn't decode, or with `CursorError::InvalidPageRequest` if the
+    /// arguments don't pass `from_args`, such as when both `first` and `last` are given.
+    pub fn from_juniper_args(
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+    ) -> Result<Self, CursorError> {
+        let after = after
+            .map(|after| CursorT::from_encoded_string(&after))
+            .transpose()?;
+        let before = before
+            .map(|before| CursorT::from_encoded_string(&before))
+            .transpose()?;
+        Ok(Self::from_args(first, after, last, before)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Cursor, CursorError, Direction, OffsetCursor, PageRequest, PageRequestError, StringCursor,
+    };
+    use juniper::FieldResult;
+
+    #[test]
+    fn test_validate_ok() {
+        assert_eq!(
+            PageRequest::new(Some(10), Some(OffsetCursor::new(5)), None).validate(),
+            Ok(())
+        );
+        assert_eq!(
+            PageRequest::new_backward(Some(10), Some(OffsetCursor::new(5))).validate(),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_direction() {
+        let cursor = || Some(OffsetCursor::new(5));
+        let request = |first, after, last, before| PageRequest {
+            first,
+            after,
+            last,
+            before,
+        };
+
+        for (pr, direction) in [
+            (request(None, None, None, None), Direction::Forward),
+            (request(Some(10), None, None, None), Direction::Forward),
+            (request(None, cursor(), None, None), Direction::Forward),
+            (request(Some(10), cursor(), None, None), Direction::Forward),
+            (request(None, None, Some(10), None), Direction::Backward),
+            (request(None, None, None, cursor()), Direction::Backward),
+            (request(None, None, Some(10), cursor()), Direction::Backward),
+            (
+                request(Some(10), None, Some(10), None),
+                Direction::Ambiguous,
+            ),
+            (
+                request(Some(10), None, None, cursor()),
+                Direction::Ambiguous,
+            ),
+            (
+                request(None, cursor(), Some(10), None),
+                Direction::Ambiguous,
+            ),
+            (
+                request(None, cursor(), None, cursor()),
+                Direction::Ambiguous,
+            ),
+        ] {
+            assert_eq!(pr.direction(), direction, "{pr:?}");
+            assert_eq!(pr.is_forward(), direction == Direction::Forward);
+            assert_eq!(pr.is_backward(), direction == Direction::Backward);
+        }
+    }
+
+    #[test]
+    fn test_validate_first_and_last() {
+        let mut pr = PageRequest::<OffsetCursor>::new(Some(10), None, None);
+        pr.last = Some(10);
+        assert_eq!(pr.validate(), Err(PageRequestError::FirstAndLast));
+    }
+
+    #[test]
+    fn test_from_args() {
+        assert_eq!(
+            PageRequest::from_args(Some(10), Some(OffsetCursor::new(5)), None, None),
+            Ok(PageRequest::new(Some(10), Some(OffsetCursor::new(5)), None))
+        );
+        assert_eq!(
+            PageRequest::from_args(None, None, Some(10), Some(OffsetCursor::new(5))),
+            Ok(PageRequest::new_backward(
+                Some(10),
+                Some(OffsetCursor::new(5))
+            ))
+        );
+        assert_eq!(
+            PageRequest::from_args(Some(10), None, None, Some(OffsetCursor::new(5))),
+            Err(PageRequestError::MixedDirections)
+        );
+        assert_eq!(
+            PageRequest::<OffsetCursor>::from_args(None, None, Some(-1), None),
+            Err(PageRequestError::NegativeLast)
+        );
+    }
+
+    #[test]
+    fn test_from_juniper_args() {
+        let encoded = || Some(OffsetCursor::new(5).to_encoded_string());
+        assert_eq!(
+            PageRequest::from_juniper_args(Some(10), encoded(), None, None),
+            Ok(PageRequest::new(Some(10), Some(OffsetCursor::new(5)), None))
+        );
+        assert_eq!(
+            PageRequest::from_juniper_args(None, None, Some(10), encoded()),
+            Ok(PageRequest::new_backward(
+                Some(10),
+                Some(OffsetCursor::new(5))
+            ))
+        );
+        assert_eq!(
+            PageRequest::<OffsetCursor>::from_juniper_args(None, None, None, None),
+            Ok(PageRequest::new(None, None, None))
+        );
+    }
+
+    #[test]
+    fn test_from_juniper_args_errors() {
+        assert_eq!(
+            PageRequest::<OffsetCursor>::from_juniper_args(Some(10), None, Some(10), None),
+            Err(CursorError::InvalidPageRequest(
+                PageRequestError::MixedDirections
+            ))
+        );
+        assert_eq!(
+            PageRequest::<OffsetCursor>::from_juniper_args(
+                None,
+                Some(StringCursor::new("5".to_string()).to_encoded_string()),
+                None,
+                None
+            ),
+            Err(CursorError::InvalidCursor)
+        );
+        assert!(matches!(
+            PageRequest::<OffsetCursor>::from_juniper_args(
+                None,
+                None,
+                Some(1),
+                Some("not base64!".to_string())
+            ),
+            Err(CursorError::Base64(_))
+        ));
+    }
+
+    #[test]
+    fn test_clamp_first() {
+        let after = || Some(OffsetCursor::new(5));
+
+        assert_eq!(
+            PageRequest::new(Some(1_000_000), after(), None).clamp_first(50),
+            PageRequest::new(Some(50), after(), None)
+        );
+        assert_eq!(
+            PageRequest::new(Some(10), after(), None).clamp_first(50),
+            PageRequest::new(Some(10), after(), None)
+        );
+        assert_eq!(
+            PageRequest::<OffsetCursor>::new(None, None, None).clamp_first(50),
+            PageRequest::new(Some(50), None, None)
+        );
+        assert_eq!(
+            PageRequest::new_backward(Some(1_000_000), after()).clamp_first(50),
+            PageRequest::new_backward(Some(1_000_000), after())
+        );
+    }
+
+    #[test]
+    fn test_validate_negative() {
+        let pr = PageRequest::<OffsetCursor>::new(Some(-1), None, None);
+        assert_eq!(pr.validate(), Err(PageRequestError::NegativeFirst));
+
+        let pr = PageRequest::<OffsetCursor>::new_backward(Some(-1), None);
+        assert_eq!(pr.validate(), Err(PageRequestError::NegativeLast));
+    }
+
+    #[test]
+    fn test_validate_contradictory_cursors() {
+        let pr = PageRequest::new(
+            Some(10),
+            Some(OffsetCursor::new(8)),
+            Some(OffsetCursor::new(3)),
+        );
+        assert_eq!(pr.validate(), Err(PageRequestError::ContradictoryCursors));
+
+        let pr = PageRequest::new(
+            Some(10),
+            Some(OffsetCursor::new(3)),
+            Some(OffsetCursor::new(8)),
+        );
+        assert_eq!(pr.validate(), Ok(()));
+
+        // Opaque cursors can't be compared, so they're let through:
+        let pr = PageRequest::new(
+            Some(10),
+            Some(StringCursor::new("b".to_string())),
+            Some(StringCursor::new("a".to_string())),
+        );
+        assert_eq!(pr.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_into_field_error() {
+        fn resolver(pr: PageRequest<OffsetCursor>) -> FieldResult<i32> {
+            pr.validate()?;
+            Ok(1)
+        }
+
+        let mut pr = PageRequest::new(Some(10), None, None);
+        pr.last = Some(2);
+        let err = resolver(pr).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "Providing both first and last is not supported"
+        );
+    }
+}
@@ -0,0 +1,670 @@
+use crate::{
+    Cursor, CursorByKey, CursorProvider, KeyedCursorProvider, OffsetCursor, OffsetCursorProvider,
+    PageInfoFactory, PageRequest, PaginationMetadata, StringCursor,
+};
+#[cfg(feature = "indexmap")]
+use indexmap::IndexMap;
+#[cfg(feature = "indexmap")]
+use std::borrow::Borrow;
+use std::collections::BinaryHeap;
+#[cfg(feature = "indexmap")]
+use std::hash::Hash;
+use std::ops::Range;
+
+mod private {
+    pub trait Sealed {}
+    impl<I: Iterator> Sealed for I {}
+}
+
+/// A window of items taken from an iterator, along with the metadata needed to build the
+/// connection and `PageInfo` for it.
+#[derive(Debug, Clone)]
+pub struct RelayPage<ItemT, CursorT = OffsetCursor>
+where
+    CursorT: Cursor,
+{
+    /// The items within the requested window, ready to pass into `RelayConnection::from_metadata`.
+    pub items: Vec<Option<ItemT>>,
+
+    /// Metadata describing the window, with the total count and whether more items follow it.
+    pub metadata: PaginationMetadata<CursorT>,
+}
+
+impl<ItemT> RelayPage<ItemT, OffsetCursor> {
+    /// Builds the `PageInfo` for this window using offset cursors.
+    pub fn page_info<PageInfoType>(&self) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<OffsetCursor>,
+    {
+        OffsetCursorProvider::new().get_page_info(&self.metadata, Some(&self.items))
+    }
+}
+
+impl<ItemT> RelayPage<ItemT, StringCursor>
+where
+    ItemT: CursorByKey,
+{
+    /// Builds the `PageInfo` for this window using keyed cursors.
+    pub fn page_info<PageInfoType>(&self) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<StringCursor>,
+    {
+        KeyedCursorProvider.get_page_info(&self.metadata, Some(&self.items))
+    }
+}
+
+/// Extension trait adding offset-style Relay pagination to any iterator.
+///
+/// ```
+/// use juniper_relay_helpers::{OffsetCursor, PageRequest, RelayPageExt};
+///
+/// let page_request = PageRequest::new(Some(2), Some(OffsetCursor::new(1)), None);
+/// let page = (1..=10).filter(|n| n % 2 == 0).relay_page(&page_request);
+///
+/// // The item at offset 1 (`4`) was the `after` cursor, so the page starts after it:
+/// assert_eq!(page.items, vec![Some(6), Some(8)]);
+/// assert_eq!(page.metadata.total_count, Some(5));
+/// assert_eq!(page.metadata.more_available, Some(true));
+/// ```
+///
+/// The `after` cursor is applied as "after", so the window starts at `offset + 1`. The whole
+/// iterator is consumed to find the total count.
+pub trait RelayPageExt: Iterator + private::Sealed {
+    /// Takes the window described by the page request out of the iterator.
+    fn relay_page(self, page_request: &PageRequest<OffsetCursor>) -> RelayPage<Self::Item>
+    where
+        Self: Sized,
+    {
+        let start = page_request
+            .after
+            .as_ref()
+            .map(|after| after.offset.saturating_add(1).max(0) as usize)
+            .unwrap_or(0);
+
+        let mut iter = self;
+        let skipped = iter.by_ref().take(start).count();
+        let items: Vec<Option<Self::Item>> = match page_request.first {
+            Some(first) => iter
+                .by_ref()
+                .take(first.max(0) as usize)
+                .map(Some)
+                .collect(),
+            None => iter.by_ref().map(Some).collect(),
+        };
+        let remaining = iter.count();
+
+        RelayPage {
+            metadata: PaginationMetadata {
+                total_count: Some((skipped + items.len() + remaining) as i32),
+                page_request: Some(page_request.clone()),
+                more_available: Some(remaining > 0),
+            },
+            items,
+        }
+    }
+}
+
+impl<I: Iterator> RelayPageExt for I {}
+
+/// Paginates an `IndexMap` in insertion order, using the map keys as `StringCursor` values.
+///
+/// The `after` cursor is looked up with the map's own index, so resuming is a hash lookup rather than
+/// a scan. If the `after` key isn't in the map, the page starts from the beginning.
+///
+/// The values are cloned into the page. To build the connection with `KeyedCursorProvider`, have the
+/// value's `CursorByKey::cursor_key` return the same key it's stored under.
+///
+/// ```
+/// use indexmap::IndexMap;
+/// use juniper_relay_helpers::{paginate_indexmap, PageRequest, StringCursor};
+///
+/// let mut map = IndexMap::new();
+/// map.insert("lumiere".to_string(), "Lumiére");
+/// map.insert("old-lumiere".to_string(), "Old Lumiére");
+/// map.insert("flying-waters".to_string(), "Flying Waters");
+///
+/// let page = paginate_indexmap(
+///     &map,
+///     &PageRequest::new(Some(1), Some(StringCursor::new("lumiere".to_string())), None),
+/// );
+/// assert_eq!(page.items, vec![Some("Old Lumiére")]);
+/// ```
+#[cfg(feature = "indexmap")]
+pub fn paginate_indexmap<K, V>(
+    map: &IndexMap<K, V>,
+    page_request: &PageRequest<StringCursor>,
+) -> RelayPage<V, StringCursor>
+where
+    K: Hash + Eq + Borrow<str>,
+    V: Clone,
+{
+    let start = page_request
+        .after
+        .as_ref()
+        .and_then(|after| map.get_index_of(after.value.as_str()))
+        .map(|idx| idx + 1)
+        .unwrap_or(0)
+        .min(map.len());
+
+    let end = match page_request.first {
+        Some(first) => start.saturating_add(first.max(0) as usize).min(map.len()),
+        None => map.len(),
+    };
+
+    RelayPage {
+        items: map[start..end].values().cloned().map(Some).collect(),
+        metadata: PaginationMetadata {
+            total_count: Some(map.len() as i32),
+            page_request: Some(page_request.clone()),
+            more_available: Some(end < map.len()),
+        },
+    }
+}
+
+/// Paginates a snapshot of a `BinaryHeap` in the order it would be popped (greatest first), using
+/// keyed cursors from `CursorByKey`.
+///
+/// A heap isn't ordered for iteration, so every call clones it into a vec and sorts it, which is
+/// O(n log n) in the size of the heap. That's fine for top-N style heaps, but for large sets keep a
+/// sorted copy around instead.
+///
+/// The `after` cursor is found by scanning for the item with a matching key. If it isn't in the
+/// heap any more, the page starts from the top.
+///
+/// ```
+/// use juniper_relay_helpers::{paginate_heap, CursorByKey, PageRequest};
+/// use std::collections::BinaryHeap;
+///
+/// #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+/// struct Score(u32);
+/// impl CursorByKey for Score {
+///     fn cursor_key(&self) -> String {
+///         self.0.to_string()
+///     }
+/// }
+///
+/// let heap = BinaryHeap::from(vec![Score(30), Score(90), Score(60)]);
+/// let page = paginate_heap(&heap, &PageRequest::new(Some(2), None, None));
+/// assert_eq!(page.items, vec![Some(Score(90)), Some(Score(60))]);
+/// ```
+pub fn paginate_heap<ItemT>(
+    heap: &BinaryHeap<ItemT>,
+    page_request: &PageRequest<StringCursor>,
+) -> RelayPage<ItemT, StringCursor>
+where
+    ItemT: Ord + Clone + CursorByKey,
+{
+    let mut sorted = heap.clone().into_sorted_vec();
+    sorted.reverse();
+
+    let start = page_request
+        .after
+        .as_ref()
+        .and_then(|after| {
+            sorted
+                .iter()
+                .position(|item| item.cursor_key() == after.value)
+        })
+        .map(|idx| idx + 1)
+        .unwrap_or(0);
+
+    let end = match page_request.first {
+        Some(first) => start
+            .saturating_add(first.max(0) as usize)
+            .min(sorted.len()),
+        None => sorted.len(),
+    };
+
+    RelayPage {
+        metadata: PaginationMetadata {
+            total_count: Some(sorted.len() as i32),
+            page_request: Some(page_request.clone()),
+            more_available: Some(end < sorted.len()),
+        },
+        items: sorted.drain(start..end).map(Some).collect(),
+    }
+}
+
+/// What `Paginator::paginate` found out about the set while slicing it, for building the `PageInfo`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct PageInfoHints {
+    /// The number of items in the whole set.
+    pub total_count: i32,
+
+    /// Whether any items come before the page.
+    pub has_previous_page: bool,
+
+    /// Whether any items come after the page.
+    pub has_next_page: bool,
+}
+
+impl PageInfoHints {
+    /// Builds the metadata to pass to `RelayConnection::from_metadata` along with the page.
+    pub fn metadata(
+        &self,
+        page_request: &PageRequest<OffsetCursor>,
+    ) -> PaginationMetadata<OffsetCursor> {
+        PaginationMetadata {
+            total_count: Some(self.total_count),
+            page_request: Some(page_request.clone()),
+            more_available: Some(self.has_next_page),
+        }
+    }
+}
+
+/// Slices an in-memory set by an offset `PageRequest`, so resolvers don't each redo the
+/// `after.offset + 1` and `first` arithmetic.
+///
+/// ```
+/// use juniper_relay_helpers::{OffsetCursor, PageRequest, Paginator};
+///
+/// let items = vec!["Lune", "Maelle", "Sciel", "Verso", "Monoco"];
+/// let page_request = PageRequest::new(Some(2), Some(OffsetCursor::new(1)), None);
+///
+/// let (page, hints) = Paginator::paginate(&items, &page_request);
+/// assert_eq!(page, vec!["Sciel", "Verso"]);
+/// assert!(hints.has_previous_page);
+/// assert!(hints.has_next_page);
+/// ```
+///
+/// Unlike `RelayPageExt::relay_page`, only the page itself is visited, as the length of a slice is
+/// already known. As there, pages run forwards from the `after` cursor and `last` and `before` are
+/// ignored.
+pub struct Paginator;
+
+impl Paginator {
+    /// Clones the page described by the request out of `items`.
+    pub fn paginate<ItemT: Clone>(
+        items: &[ItemT],
+        page_request: &PageRequest<OffsetCursor>,
+    ) -> (Vec<ItemT>, PageInfoHints) {
+        let Range { start, end } = Self::window(items.len(), page_request);
+
+        (
+            items[start..end].to_vec(),
+            PageInfoHints {
+                total_count: i32::try_from(items.len()).unwrap_or(i32::MAX),
+                has_previous_page: start > 0,
+                has_next_page: end < items.len(),
+            },
+        )
+    }
+
+    /// The range of a set of `len` items covered by the request, running forwards from the `after`
+    /// cursor and clamped to the set.
+    pub(crate) fn window(len: usize, page_request: &PageRequest<OffsetCursor>) -> Range<usize> {
+        let start = page_request
+            .after
+            .as_ref()
+            .map(|after| after.offset.saturating_add(1).max(0) as usize)
+            .unwrap_or(0)
+            .min(len);
+        let end = match page_request.first {
+            Some(first) => start.saturating_add(first.max(0) as usize).min(len),
+            None => len,
+        };
+        start..end
+    }
+}
+
+/// Trims a page fetched with one item more than `first`, so the connection gets an exact
+/// `has_next_page` without needing a count.
+///
+/// Ask the backend for `fetch_limit()` items, then hand them to `paginate`. The extra item, if it
+/// came back, is dropped and recorded as `more_available`, which both `OffsetCursorProvider` and
+/// `KeyedCursorProvider` use as `has_next_page` in place of guessing from `total_count`.
+///
+/// ```
+/// use juniper_relay_helpers::{OffsetCursor, OverfetchPaginator, PageRequest};
+///
+/// let paginator = OverfetchPaginator::new(PageRequest::new(Some(2), None::<OffsetCursor>, None));
+/// assert_eq!(paginator.fetch_limit(), Some(3));
+///
+/// // Stands in for `SELECT ... LIMIT 3`:
+/// let fetched = vec!["Gustave", "Lune", "Maelle"];
+///
+/// let page = paginator.paginate(fetched, None);
+/// assert_eq!(page.items, vec![Some("Gustave"), Some("Lune")]);
+/// assert_eq!(page.metadata.more_available, Some(true));
+/// ```
+///
+/// Like `PaginationMetadata::from_overfetch`, which it's built on, this only works forwards. With no
+/// `first` there's nothing to overfetch, so every item is kept and `more_available` is left unset.
+#[derive(Debug, Clone)]
+pub struct OverfetchPaginator<CursorT>
+where
+    CursorT: Cursor,
+{
+    page_request: PageRequest<CursorT>,
+}
+
+impl<CursorT> OverfetchPaginator<CursorT>
+where
+    CursorT: Cursor,
+{
+    /// Creates a paginator for the given page request.
+    pub fn new(page_request: PageRequest<CursorT>) -> Self {
+        OverfetchPaginator { page_request }
+    }
+
+    /// How many items to fetch from the backend: one more than `first`, or `None` for no limit.
+    pub fn fetch_limit(&self) -> Option<i32> {
+        self.page_request
+            .first
+            .map(|first| first.max(0).saturating_add(1))
+    }
+
+    /// Trims the fetched items down to `first` and builds the metadata for them. `total_count` can
+    /// be `None` if the backend doesn't count.
+    pub fn paginate<ItemT>(
+        &self,
+        mut items: Vec<ItemT>,
+        total_count: Option<i32>,
+    ) -> RelayPage<ItemT, CursorT> {
+        let metadata = PaginationMetadata::from_overfetch(
+            &mut items,
+            total_count,
+            Some(self.page_request.clone()),
+        );
+
+        RelayPage {
+            items: items.into_iter().map(Some).collect(),
+            metadata,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        OffsetCursor, OverfetchPaginator, PageInfoHints, PageRequest, Paginator, RelayConnection,
+        RelayPageExt,
+    };
+    use juniper::GraphQLObject;
+
+    #[derive(Debug, Clone, GraphQLObject, RelayConnection, Eq, PartialEq)]
+    #[relay(cursor = OffsetCursor)]
+    pub struct Song {
+        pub id: i32,
+    }
+
+    #[test]
+    fn test_paginator() {
+        let items: Vec<i32> = (0..5).collect();
+        let page = |first, after: Option<i32>| {
+            Paginator::paginate(
+                &items,
+                &PageRequest::new(first, after.map(OffsetCursor::new), None),
+            )
+        };
+        let hints = |has_previous_page, has_next_page| PageInfoHints {
+            total_count: 5,
+            has_previous_page,
+            has_next_page,
+        };
+
+        assert_eq!(page(Some(2), None), (vec![0, 1], hints(false, true)));
+        assert_eq!(page(Some(2), Some(1)), (vec![2, 3], hints(true, true)));
+        assert_eq!(page(None, None), (items.clone(), hints(false, false)));
+
+        // First larger than what's left.
+        assert_eq!(page(Some(10), Some(2)), (vec![3, 4], hints(true, false)));
+
+        // After past the end.
+        assert_eq!(page(Some(2), Some(4)), (vec![], hints(true, false)));
+        assert_eq!(page(Some(2), Some(40)), (vec![], hints(true, false)));
+
+        let empty: Vec<i32> = vec![];
+        let (page, hints) = Paginator::paginate(
+            &empty,
+            &PageRequest::new(Some(2), Some(OffsetCursor::new(3)), None),
+        );
+        assert!(page.is_empty());
+        assert_eq!(hints.total_count, 0);
+        assert!(!hints.has_previous_page);
+        assert!(!hints.has_next_page);
+    }
+
+    #[test]
+    fn test_paginator_connection() {
+        let songs: Vec<Song> = (0..6).map(|id| Song { id }).collect();
+        let page_request = PageRequest::new(Some(2), Some(OffsetCursor::new(2)), None);
+
+        let (page, hints) = Paginator::paginate(&songs, &page_request);
+        let page: Vec<Option<Song>> = page.into_iter().map(Some).collect();
+        let conn = SongRelayConnection::from_metadata(
+            Some(&page),
+            hints.metadata(&page_request),
+            crate::OffsetCursorProvider::new(),
+        );
+
+        assert_eq!(conn.count, Some(6));
+        assert!(conn.page_info.has_previous_page);
+        assert!(conn.page_info.has_next_page);
+        assert_eq!(conn.page_info.start_cursor, Some(OffsetCursor::new(3)));
+        assert_eq!(conn.page_info.end_cursor, Some(OffsetCursor::new(4)));
+    }
+
+    #[test]
+    fn test_overfetch_paginator() {
+        let songs: Vec<Song> = (0..5).map(|id| Song { id }).collect();
+        let page_request = PageRequest::new(Some(2), Some(OffsetCursor::new(1)), None);
+        let paginator = OverfetchPaginator::new(page_request);
+
+        // The backend has no count, so it fetches `first + 1` after the cursor.
+        let limit = paginator.fetch_limit().unwrap() as usize;
+        assert_eq!(limit, 3);
+        let page = paginator.paginate(songs[2..2 + limit].to_vec(), None);
+        assert_eq!(page.items.len(), 2);
+
+        let conn = SongRelayConnection::from_metadata(
+            Some(&page.items),
+            page.metadata,
+            crate::OffsetCursorProvider::new(),
+        );
+        assert_eq!(conn.count, None);
+        assert!(conn.page_info.has_previous_page);
+        assert!(conn.page_info.has_next_page);
+        assert_eq!(conn.page_info.start_cursor, Some(OffsetCursor::new(2)));
+        assert_eq!(conn.page_info.end_cursor, Some(OffsetCursor::new(3)));
+
+        // The last page is exactly `first` long, so only the missing extra item says it's the end.
+        // Without it, `RelayConnection::new` has to guess from the full page.
+        let paginator =
+            OverfetchPaginator::new(PageRequest::new(Some(2), Some(OffsetCursor::new(2)), None));
+        let page = paginator.paginate(songs[3..].to_vec(), None);
+        let conn = SongRelayConnection::from_metadata(
+            Some(&page.items),
+            page.metadata,
+            crate::OffsetCursorProvider::new(),
+        );
+        assert_eq!(conn.edges.as_ref().map(|e| e.len()), Some(2));
+        assert!(!conn.page_info.has_next_page);
+
+        let guessed = SongRelayConnection::new(
+            Some(&page.items),
+            None,
+            crate::OffsetCursorProvider::new(),
+            Some(PageRequest::new(Some(2), Some(OffsetCursor::new(2)), None)),
+        );
+        assert!(guessed.page_info.has_next_page);
+    }
+
+    #[test]
+    fn test_overfetch_paginator_without_first() {
+        let paginator = OverfetchPaginator::new(PageRequest::<OffsetCursor>::new(None, None, None));
+        assert_eq!(paginator.fetch_limit(), None);
+
+        // The trimming itself is `PaginationMetadata::from_overfetch`'s, and tested there.
+        let page = paginator.paginate(vec![1, 2, 3], Some(3));
+        assert_eq!(page.items, vec![Some(1), Some(2), Some(3)]);
+    }
+
+    #[test]
+    fn test_relay_page_after_filter() {
+        let page_request = PageRequest::new(Some(3), Some(OffsetCursor::new(1)), None);
+        let page = (1..=20).filter(|n| n % 3 == 0).relay_page(&page_request);
+
+        assert_eq!(page.items, vec![Some(9), Some(12), Some(15)]);
+        assert_eq!(page.metadata.total_count, Some(6));
+        assert_eq!(page.metadata.more_available, Some(true));
+
+        let page_info: SongRelayConnectionPageInfo = page.page_info();
+        assert!(page_info.has_previous_page);
+        assert!(page_info.has_next_page);
+        assert_eq!(page_info.start_cursor, Some(OffsetCursor::new(2)));
+        assert_eq!(page_info.end_cursor, Some(OffsetCursor::new(4)));
+    }
+
+    #[test]
+    fn test_relay_page_last_page() {
+        let page_request = PageRequest::new(Some(3), Some(OffsetCursor::new(3)), None);
+        let page = (1..=6).relay_page(&page_request);
+
+        assert_eq!(page.items, vec![Some(5), Some(6)]);
+        assert_eq!(page.metadata.more_available, Some(false));
+    }
+
+    #[test]
+    fn test_relay_page_no_first_takes_everything() {
+        let page = (1..=4).relay_page(&PageRequest::new(None, None, None));
+        assert_eq!(page.items.len(), 4);
+        assert_eq!(page.metadata.more_available, Some(false));
+    }
+
+    #[cfg(feature = "indexmap")]
+    mod indexmap_pagination {
+        use crate::{
+            CursorByKey, KeyedCursorProvider, PageRequest, RelayConnection, StringCursor,
+            paginate_indexmap,
+        };
+        use indexmap::IndexMap;
+        use juniper::GraphQLObject;
+
+        #[derive(Debug, Clone, GraphQLObject, RelayConnection, Eq, PartialEq)]
+        #[relay(cursor = StringCursor)]
+        pub struct Track {
+            pub key: String,
+        }
+        impl CursorByKey for Track {
+            fn cursor_key(&self) -> String {
+                self.key.clone()
+            }
+        }
+
+        fn tracks() -> IndexMap<String, Track> {
+            // Deliberately not in alphabetical order, to check insertion order is kept.
+            ["une-vie-a-t-aimer", "lumiere", "gustave", "alicia", "verso"]
+                .into_iter()
+                .map(|key| {
+                    (
+                        key.to_string(),
+                        Track {
+                            key: key.to_string(),
+                        },
+                    )
+                })
+                .collect()
+        }
+
+        fn keys(items: &[Option<Track>]) -> Vec<String> {
+            items
+                .iter()
+                .map(|t| t.as_ref().unwrap().key.clone())
+                .collect()
+        }
+
+        #[test]
+        fn test_paginate_across_pages() {
+            let map = tracks();
+
+            let page1 = paginate_indexmap(&map, &PageRequest::new(Some(2), None, None));
+            assert_eq!(keys(&page1.items), vec!["une-vie-a-t-aimer", "lumiere"]);
+            let pi1: TrackRelayConnectionPageInfo = page1.page_info();
+            assert!(pi1.has_next_page);
+            assert!(!pi1.has_previous_page);
+
+            let page2 = paginate_indexmap(
+                &map,
+                &PageRequest::new(Some(2), pi1.end_cursor.clone(), None),
+            );
+            assert_eq!(keys(&page2.items), vec!["gustave", "alicia"]);
+            let pi2: TrackRelayConnectionPageInfo = page2.page_info();
+            assert!(pi2.has_next_page);
+            assert!(pi2.has_previous_page);
+
+            let page3 = paginate_indexmap(
+                &map,
+                &PageRequest::new(Some(2), pi2.end_cursor.clone(), None),
+            );
+            assert_eq!(keys(&page3.items), vec!["verso"]);
+
+            let conn = TrackRelayConnection::from_metadata(
+                Some(&page3.items),
+                page3.metadata,
+                KeyedCursorProvider,
+            );
+            assert_eq!(conn.count, Some(5));
+            assert!(!conn.page_info.has_next_page);
+            assert_eq!(
+                conn.page_info.end_cursor,
+                Some(StringCursor::new("verso".to_string()))
+            );
+        }
+
+        #[test]
+        fn test_paginate_unknown_after_starts_from_beginning() {
+            let map = tracks();
+            let page = paginate_indexmap(
+                &map,
+                &PageRequest::new(Some(1), Some(StringCursor::new("maelle".to_string())), None),
+            );
+            assert_eq!(keys(&page.items), vec!["une-vie-a-t-aimer"]);
+        }
+    }
+
+    #[test]
+    fn test_paginate_heap_across_pages() {
+        use crate::{CursorByKey, StringCursor, paginate_heap};
+        use std::collections::BinaryHeap;
+
+        #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+        struct Damage(u32);
+        impl CursorByKey for Damage {
+            fn cursor_key(&self) -> String {
+                self.0.to_string()
+            }
+        }
+
+        let heap: BinaryHeap<Damage> = [120, 9999, 45, 800, 3].into_iter().map(Damage).collect();
+
+        let page1 = paginate_heap(&heap, &PageRequest::new(Some(2), None, None));
+        assert_eq!(page1.items, vec![Some(Damage(9999)), Some(Damage(800))]);
+        assert_eq!(page1.metadata.more_available, Some(true));
+
+        let after = Some(StringCursor::new("800".to_string()));
+        let page2 = paginate_heap(&heap, &PageRequest::new(Some(2), after, None));
+        assert_eq!(page2.items, vec![Some(Damage(120)), Some(Damage(45))]);
+
+        let after = Some(StringCursor::new("45".to_string()));
+        let page3 = paginate_heap(&heap, &PageRequest::new(Some(2), after, None));
+        assert_eq!(page3.items, vec![Some(Damage(3))]);
+        assert_eq!(page3.metadata.more_available, Some(false));
+        assert_eq!(page3.metadata.total_count, Some(5));
+    }
+
+    #[test]
+    fn test_relay_page_into_connection() {
+        let page_request = PageRequest::new(Some(2), None, None);
+        let page = (1..=5).map(|id| Song { id }).relay_page(&page_request);
+
+        let conn = SongRelayConnection::from_metadata(
+            Some(&page.items),
+            page.metadata,
+            crate::OffsetCursorProvider::new(),
+        );
+        assert_eq!(conn.count, Some(5));
+        assert!(conn.page_info.has_next_page);
+        assert_eq!(conn.edges.unwrap().len(), 2);
+    }
+}
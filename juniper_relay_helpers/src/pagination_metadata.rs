@@ -6,9 +6,87 @@ pub struct PaginationMetadata<CursorType>
 where
     CursorType: Cursor,
 {
-    /// The total number of items in the result set:
+    /// The total number of items in the result set. Backends that can't cheaply count can pass a
+    /// negative value (usually `-1`) to mean "unknown", which is treated the same as `None`.
     pub total_count: Option<i32>,
 
     /// The current PageInfo, if any:
     pub page_request: Option<PageRequest<CursorType>>,
+
+    /// Whether the resolver knows there are more items after this page, usually because it fetched
+    /// `first + 1` items and saw the extra one. When set, this becomes `has_next_page` directly and
+    /// the providers skip their own heuristics.
+    pub more_available: Option<bool>,
+}
+
+impl<CursorType> PaginationMetadata<CursorType>
+where
+    CursorType: Cursor,
+{
+    /// The total count, if it's known. Negative counts are the "unknown" sentinel, so they come back
+    /// as `None`.
+    pub fn known_total_count(&self) -> Option<i32> {
+        self.total_count.filter(|total| *total >= 0)
+    }
+
+    /// Builds the metadata for a page fetched with one item more than `first`, trimming the extra
+    /// item off `items` and setting `more_available` if it was there. Without a `first` nothing is
+    /// trimmed and `more_available` is left unset.
+    ///
+    /// This is the trimming behind `OverfetchPaginator`, which also works out how many items to
+    /// fetch. Reach for it directly only when the items can't be handed over by value.
+    pub fn from_overfetch<ItemT>(
+        items: &mut Vec<ItemT>,
+        total_count: Option<i32>,
+        page_request: Option<PageRequest<CursorType>>,
+    ) -> Self {
+        let first = page_request
+            .as_ref()
+            .and_then(|pr| pr.first)
+            .map(|first| first.max(0) as usize);
+
+        let more_available = first.map(|first| {
+            let more = items.len() > first;
+            items.truncate(first);
+            more
+        });
+
+        PaginationMetadata {
+            total_count,
+            page_request,
+            more_available,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{OffsetCursor, PageRequest, PaginationMetadata};
+
+    #[test]
+    fn test_from_overfetch() {
+        let mut items = vec![1, 2, 3, 4];
+        let metadata = PaginationMetadata::<OffsetCursor>::from_overfetch(
+            &mut items,
+            None,
+            Some(PageRequest::new(Some(3), None, None)),
+        );
+        assert_eq!(items, vec![1, 2, 3]);
+        assert_eq!(metadata.more_available, Some(true));
+
+        let mut items = vec![1, 2, 3];
+        let metadata = PaginationMetadata::<OffsetCursor>::from_overfetch(
+            &mut items,
+            Some(3),
+            Some(PageRequest::new(Some(3), None, None)),
+        );
+        assert_eq!(items, vec![1, 2, 3]);
+        assert_eq!(metadata.more_available, Some(false));
+        assert_eq!(metadata.total_count, Some(3));
+
+        let mut items = vec![1, 2, 3];
+        let metadata = PaginationMetadata::<OffsetCursor>::from_overfetch(&mut items, None, None);
+        assert_eq!(items, vec![1, 2, 3]);
+        assert_eq!(metadata.more_available, None);
+    }
 }
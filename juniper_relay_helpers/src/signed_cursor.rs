@@ -0,0 +1,662 @@
+use crate::page_info_factory::PageInfoParts;
+use crate::{
+    CURSOR_SEGMENT_DELIMITER, Cursor, CursorError, CursorProvider, PageInfoFactory, PageRequest,
+    PaginationMetadata,
+};
+use base64::prelude::*;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+
+/// Tag for the segment carrying the signature, always the last segment of the raw string.
+const SIGNATURE_SEGMENT: &str = "sig=";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Supplies the secret used to sign and verify a `SignedCursor`.
+///
+/// Cursors are decoded from GraphQL input without any access to your app state, so the secret is
+/// provided by a type rather than a value. Load it however you like, for instance into a `OnceLock`
+/// at startup:
+///
+/// ```
+/// use juniper_relay_helpers::CursorSigningKey;
+/// use std::sync::OnceLock;
+///
+/// static CURSOR_SECRET: OnceLock<Vec<u8>> = OnceLock::new();
+///
+/// pub struct AppCursorKey;
+/// impl CursorSigningKey for AppCursorKey {
+///     fn secret() -> &'static [u8] {
+///         CURSOR_SECRET.get_or_init(|| b"load-me-from-config".to_vec())
+///     }
+/// }
+/// ```
+pub trait CursorSigningKey {
+    /// The secret used as the HMAC-SHA256 key.
+    fn secret() -> &'static [u8];
+}
+
+/// Wraps another cursor and signs it with HMAC-SHA256, so clients can't craft their own cursors
+/// (for instance to jump to an arbitrary offset).
+///
+/// The signature is appended to the inner cursor's raw string as a final `sig=` segment before base64
+/// encoding. Decoding a cursor with a missing or wrong signature returns `CursorError::SignatureMismatch`.
+///
+/// ```
+/// use juniper_relay_helpers::{Cursor, CursorError, CursorSigningKey, OffsetCursor, SignedCursor};
+///
+/// struct TestKey;
+/// impl CursorSigningKey for TestKey {
+///     fn secret() -> &'static [u8] {
+///         b"not-a-real-secret"
+///     }
+/// }
+///
+/// let cursor = SignedCursor::<OffsetCursor, TestKey>::new(OffsetCursor::new(10));
+/// let decoded = SignedCursor::<OffsetCursor, TestKey>::from_encoded_string(&cursor.to_encoded_string());
+/// assert_eq!(decoded.unwrap().inner, OffsetCursor::new(10));
+///
+/// // A plain offset cursor has no signature, so it's rejected:
+/// let forged = SignedCursor::<OffsetCursor, TestKey>::from_encoded_string(
+///     &OffsetCursor::new(5000).to_encoded_string(),
+/// );
+/// assert_eq!(forged.unwrap_err(), CursorError::SignatureMismatch);
+/// ```
+///
+/// It's named after the inner cursor in the schema, so `SignedCursor<OffsetCursor, K>` is a
+/// `SignedOffsetCursor` scalar, and signing different cursors gives different scalars.
+///
+/// Requires the `signing` feature.
+pub struct SignedCursor<C, K>
+where
+    C: Cursor<CursorType = C>,
+    K: CursorSigningKey,
+{
+    /// The cursor that was signed.
+    pub inner: C,
+
+    _key: PhantomData<fn() -> K>,
+}
+
+impl<C, K> SignedCursor<C, K>
+where
+    C: Cursor<CursorType = C>,
+    K: CursorSigningKey,
+{
+    pub fn new(inner: C) -> Self {
+        SignedCursor {
+            inner,
+            _key: PhantomData,
+        }
+    }
+
+    /// Unwraps the signed cursor into the inner one.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+
+    /// Decodes an encoded cursor, accepting a signature from any of `keys`.
+    ///
+    /// Use this during key rotation to keep accepting cursors handed out under the old key while
+    /// new ones are issued under the new key, then `re_sign` them before handing them back out.
+    ///
+    /// ```
+    /// use juniper_relay_helpers::{Cursor, CursorSigningKey, OffsetCursor, SignedCursor};
+    ///
+    /// struct OldKey;
+    /// impl CursorSigningKey for OldKey {
+    ///     fn secret() -> &'static [u8] {
+    ///         b"old-secret"
+    ///     }
+    /// }
+    ///
+    /// struct NewKey;
+    /// impl CursorSigningKey for NewKey {
+    ///     fn secret() -> &'static [u8] {
+    ///         b"new-secret"
+    ///     }
+    /// }
+    ///
+    /// let issued = SignedCursor::<OffsetCursor, OldKey>::new(OffsetCursor::new(10)).to_encoded_string();
+    ///
+    /// let inner = SignedCursor::<OffsetCursor, NewKey>::verify_with_any(
+    ///     &issued,
+    ///     &[NewKey::secret(), OldKey::secret()],
+    /// )
+    /// .unwrap();
+    /// assert_eq!(inner, OffsetCursor::new(10));
+    /// ```
+    pub fn verify_with_any(encoded: &str, keys: &[&[u8]]) -> Result<C, CursorError> {
+        let raw = String::from_utf8(BASE64_URL_SAFE.decode(encoded)?)?;
+        let (inner_raw, signature) = split_signature(&raw)?;
+
+        if !keys.iter().any(|key| verify(key, inner_raw, &signature)) {
+            return Err(CursorError::SignatureMismatch);
+        }
+
+        C::new(
+            inner_raw,
+            inner_raw.split(CURSOR_SEGMENT_DELIMITER).collect(),
+        )
+    }
+
+    /// Moves the cursor over to another signing key, so it's signed with that key when encoded.
+    pub fn re_sign<NewK: CursorSigningKey>(self) -> SignedCursor<C, NewK> {
+        SignedCursor::new(self.inner)
+    }
+}
+
+fn mac(key: &[u8]) -> HmacSha256 {
+    // HMAC accepts keys of any length, so this can't fail.
+    HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length")
+}
+
+fn sign(key: &[u8], inner_raw: &str) -> String {
+    let mut mac = mac(key);
+    mac.update(inner_raw.as_bytes());
+    BASE64_URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes())
+}
+
+fn verify(key: &[u8], inner_raw: &str, signature: &[u8]) -> bool {
+    let mut mac = mac(key);
+    mac.update(inner_raw.as_bytes());
+    mac.verify_slice(signature).is_ok()
+}
+
+/// Splits a raw signed cursor into the inner cursor's raw string and the decoded signature.
+fn split_signature(raw: &str) -> Result<(&str, Vec<u8>), CursorError> {
+    let (inner_raw, signature) = raw
+        .rsplit_once(CURSOR_SEGMENT_DELIMITER)
+        .and_then(|(inner_raw, last)| Some((inner_raw, last.strip_prefix(SIGNATURE_SEGMENT)?)))
+        .ok_or(CursorError::SignatureMismatch)?;
+    let signature = BASE64_URL_SAFE_NO_PAD
+        .decode(signature)
+        .map_err(|_| CursorError::SignatureMismatch)?;
+    Ok((inner_raw, signature))
+}
+
+impl<C, K> Cursor for SignedCursor<C, K>
+where
+    C: Cursor<CursorType = C>,
+    K: CursorSigningKey,
+{
+    type CursorType = SignedCursor<C, K>;
+
+    fn to_raw_string(&self) -> String {
+        let inner_raw = self.inner.to_raw_string();
+        let signature = sign(K::secret(), &inner_raw);
+        format!("{inner_raw}{CURSOR_SEGMENT_DELIMITER}{SIGNATURE_SEGMENT}{signature}")
+    }
+
+    fn new(raw: &str, _parts: Vec<&str>) -> Result<Self::CursorType, CursorError> {
+        let (inner_raw, signature) = split_signature(raw)?;
+        if !verify(K::secret(), inner_raw, &signature) {
+            return Err(CursorError::SignatureMismatch);
+        }
+
+        let inner = C::new(
+            inner_raw,
+            inner_raw.split(CURSOR_SEGMENT_DELIMITER).collect(),
+        )?;
+        Ok(SignedCursor::new(inner))
+    }
+
+    fn compare_position(&self, other: &Self) -> Option<Ordering> {
+        self.inner.compare_position(&other.inner)
+    }
+}
+
+crate::wrapped_cursor_scalar!(
+    SignedCursor<C, K>,
+    prefix = "Signed",
+    description = "A cursor signed so clients can't craft their own.",
+    where C: Cursor<CursorType = C>, K: CursorSigningKey
+);
+
+impl<C, K> Clone for SignedCursor<C, K>
+where
+    C: Cursor<CursorType = C>,
+    K: CursorSigningKey,
+{
+    fn clone(&self) -> Self {
+        SignedCursor::new(self.inner.clone())
+    }
+}
+
+impl<C, K> PartialEq for SignedCursor<C, K>
+where
+    C: Cursor<CursorType = C> + PartialEq,
+    K: CursorSigningKey,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<C, K> Eq for SignedCursor<C, K>
+where
+    C: Cursor<CursorType = C> + Eq,
+    K: CursorSigningKey,
+{
+}
+
+impl<C, K> Debug for SignedCursor<C, K>
+where
+    C: Cursor<CursorType = C> + Debug,
+    K: CursorSigningKey,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignedCursor")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+// -------------- SignedCursorProvider ---------------
+
+/// Wraps another cursor provider so every cursor it builds is a `SignedCursor`.
+///
+/// Incoming `after`/`before` cursors have already been verified by the time they're in the
+/// `PageRequest`, so they're unwrapped and handed to the inner provider as-is.
+///
+/// ```
+/// use juniper_relay_helpers::{CursorSigningKey, OffsetCursorProvider, SignedCursorProvider};
+///
+/// struct TestKey;
+/// impl CursorSigningKey for TestKey {
+///     fn secret() -> &'static [u8] {
+///         b"not-a-real-secret"
+///     }
+/// }
+///
+/// let provider = SignedCursorProvider::<_, TestKey>::new(OffsetCursorProvider::new());
+/// ```
+pub struct SignedCursorProvider<P, K> {
+    inner: P,
+    _key: PhantomData<fn() -> K>,
+}
+
+impl<P, K> SignedCursorProvider<P, K>
+where
+    K: CursorSigningKey,
+{
+    pub fn new(inner: P) -> Self {
+        SignedCursorProvider {
+            inner,
+            _key: PhantomData,
+        }
+    }
+
+    fn signed_page_info<C, PageInfoType>(unsigned: PageInfoParts<C>) -> PageInfoType
+    where
+        C: Cursor<CursorType = C>,
+        PageInfoType: PageInfoFactory<SignedCursor<C, K>>,
+    {
+        PageInfoType::new(
+            unsigned.has_prev_page,
+            unsigned.has_next_page,
+            unsigned.start_cursor.map(SignedCursor::new),
+            unsigned.end_cursor.map(SignedCursor::new),
+        )
+    }
+
+    fn unsigned_metadata<C>(
+        metadata: &PaginationMetadata<SignedCursor<C, K>>,
+    ) -> PaginationMetadata<C>
+    where
+        C: Cursor<CursorType = C>,
+    {
+        PaginationMetadata {
+            total_count: metadata.total_count,
+            page_request: metadata.page_request.as_ref().map(|pr| PageRequest {
+                first: pr.first,
+                before: pr.before.as_ref().map(|c| c.inner.clone()),
+                after: pr.after.as_ref().map(|c| c.inner.clone()),
+                last: pr.last,
+            }),
+            more_available: metadata.more_available,
+        }
+    }
+}
+
+impl<ItemT, P, K> CursorProvider<ItemT> for SignedCursorProvider<P, K>
+where
+    P: CursorProvider<ItemT>,
+    P::CursorType: Cursor<CursorType = P::CursorType>,
+    K: CursorSigningKey,
+{
+    type CursorType = SignedCursor<P::CursorType, K>;
+
+    fn get_cursor_for_item(
+        &self,
+        metadata: &PaginationMetadata<Self::CursorType>,
+        item_idx: i32,
+        item: Option<&ItemT>,
+    ) -> Self::CursorType {
+        SignedCursor::new(self.inner.get_cursor_for_item(
+            &Self::unsigned_metadata(metadata),
+            item_idx,
+            item,
+        ))
+    }
+
+    fn get_cursors_for_items(
+        &self,
+        metadata: &PaginationMetadata<Self::CursorType>,
+        items: &[Option<ItemT>],
+    ) -> Vec<Self::CursorType> {
+        self.inner
+            .get_cursors_for_items(&Self::unsigned_metadata(metadata), items)
+            .into_iter()
+            .map(SignedCursor::new)
+            .collect()
+    }
+
+    fn total_count(&self, metadata: &PaginationMetadata<Self::CursorType>) -> Option<i32> {
+        self.inner.total_count(&Self::unsigned_metadata(metadata))
+    }
+
+    fn try_get_cursor_for_item(
+        &self,
+        metadata: &PaginationMetadata<Self::CursorType>,
+        item_idx: i32,
+        item: Option<&ItemT>,
+    ) -> Result<Self::CursorType, CursorError> {
+        self.inner
+            .try_get_cursor_for_item(&Self::unsigned_metadata(metadata), item_idx, item)
+            .map(SignedCursor::new)
+    }
+
+    fn get_page_info<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<Self::CursorType>,
+        items: Option<&[Option<ItemT>]>,
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<Self::CursorType>,
+    {
+        let unsigned: PageInfoParts<P::CursorType> = self
+            .inner
+            .get_page_info(&Self::unsigned_metadata(metadata), items);
+
+        Self::signed_page_info(unsigned)
+    }
+
+    fn get_page_info_for_cursors<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<Self::CursorType>,
+        items: Option<&[Option<ItemT>]>,
+        cursors: &[Self::CursorType],
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<Self::CursorType>,
+    {
+        let unsigned_cursors: Vec<P::CursorType> =
+            cursors.iter().map(|cursor| cursor.inner.clone()).collect();
+        let unsigned: PageInfoParts<P::CursorType> = self.inner.get_page_info_for_cursors(
+            &Self::unsigned_metadata(metadata),
+            items,
+            &unsigned_cursors,
+        );
+
+        Self::signed_page_info(unsigned)
+    }
+
+    fn try_get_page_info<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<Self::CursorType>,
+        items: Option<&[Option<ItemT>]>,
+    ) -> Result<PageInfoType, CursorError>
+    where
+        PageInfoType: PageInfoFactory<Self::CursorType>,
+    {
+        let unsigned: PageInfoParts<P::CursorType> = self
+            .inner
+            .try_get_page_info(&Self::unsigned_metadata(metadata), items)?;
+        Ok(Self::signed_page_info(unsigned))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Cursor, CursorError, CursorSigningKey, FnCursorProvider, OffsetCursor,
+        OffsetCursorProvider, PageRequest, PaginationMetadata, RelayConnection, SignedCursor,
+        SignedCursorProvider, StringCursor,
+    };
+    use base64::prelude::*;
+    use juniper::{EmptyMutation, EmptySubscription, GraphQLObject, RootNode, graphql_object};
+    use std::cell::Cell;
+
+    pub struct TestKey;
+    impl CursorSigningKey for TestKey {
+        fn secret() -> &'static [u8] {
+            b"test-secret"
+        }
+    }
+
+    struct OtherKey;
+    impl CursorSigningKey for OtherKey {
+        fn secret() -> &'static [u8] {
+            b"some-other-secret"
+        }
+    }
+
+    type SignedOffset = SignedCursor<OffsetCursor, TestKey>;
+
+    #[test]
+    fn test_round_trip() {
+        let cursor = SignedOffset::new(OffsetCursor::new(10).with_first(Some(5)));
+        let decoded = SignedOffset::from_encoded_string(&cursor.to_encoded_string()).unwrap();
+        assert_eq!(decoded, cursor);
+
+        let cursor = SignedCursor::<StringCursor, TestKey>::new(StringCursor::new("key".into()));
+        let decoded =
+            SignedCursor::<StringCursor, TestKey>::from_encoded_string(&cursor.to_encoded_string())
+                .unwrap();
+        assert_eq!(decoded.inner.value, "key");
+    }
+
+    #[test]
+    fn test_raw_string_has_signature_segment() {
+        let raw = SignedOffset::new(OffsetCursor::new(3)).to_raw_string();
+        assert!(raw.starts_with("offset||3||sig="));
+    }
+
+    #[test]
+    fn test_tampered_offset_rejected() {
+        let cursor = SignedOffset::new(OffsetCursor::new(10));
+        let tampered = cursor
+            .to_raw_string()
+            .replacen("offset||10", "offset||5000", 1);
+        assert_eq!(
+            SignedOffset::from_encoded_string(&BASE64_URL_SAFE.encode(tampered)).unwrap_err(),
+            CursorError::SignatureMismatch
+        );
+    }
+
+    #[test]
+    fn test_unsigned_cursor_rejected() {
+        assert_eq!(
+            SignedOffset::from_encoded_string(&OffsetCursor::new(10).to_encoded_string())
+                .unwrap_err(),
+            CursorError::SignatureMismatch
+        );
+    }
+
+    #[test]
+    fn test_wrong_key_rejected() {
+        let cursor = SignedCursor::<OffsetCursor, OtherKey>::new(OffsetCursor::new(10));
+        assert_eq!(
+            SignedOffset::from_encoded_string(&cursor.to_encoded_string()).unwrap_err(),
+            CursorError::SignatureMismatch
+        );
+    }
+
+    #[test]
+    fn test_key_rotation() {
+        // Cursors handed out before the rotation are still signed with the old key.
+        let issued =
+            SignedCursor::<OffsetCursor, OtherKey>::new(OffsetCursor::new(10)).to_encoded_string();
+        assert_eq!(
+            SignedOffset::from_encoded_string(&issued).unwrap_err(),
+            CursorError::SignatureMismatch
+        );
+
+        let keys = [TestKey::secret(), OtherKey::secret()];
+        let inner = SignedOffset::verify_with_any(&issued, &keys).unwrap();
+        assert_eq!(inner, OffsetCursor::new(10));
+
+        // New cursors are signed with the new key only.
+        let reissued = SignedCursor::<OffsetCursor, OtherKey>::new(inner)
+            .re_sign::<TestKey>()
+            .to_encoded_string();
+        assert_eq!(
+            SignedOffset::from_encoded_string(&reissued).unwrap().inner,
+            OffsetCursor::new(10)
+        );
+        assert_eq!(
+            SignedOffset::verify_with_any(&reissued, &keys).unwrap(),
+            OffsetCursor::new(10)
+        );
+
+        assert_eq!(
+            SignedOffset::verify_with_any(&issued, &[TestKey::secret()]).unwrap_err(),
+            CursorError::SignatureMismatch
+        );
+        assert_eq!(
+            SignedOffset::verify_with_any(&issued, &[]).unwrap_err(),
+            CursorError::SignatureMismatch
+        );
+    }
+
+    #[derive(Debug, Clone, GraphQLObject, RelayConnection, Eq, PartialEq)]
+    #[relay(cursor = SignedOffset)]
+    pub struct Expedition {
+        pub id: i32,
+    }
+
+    #[test]
+    fn test_provider_signs_cursors() {
+        let items: Vec<Option<Expedition>> = (0..2).map(|id| Some(Expedition { id })).collect();
+        let conn = ExpeditionRelayConnection::from_metadata(
+            Some(&items),
+            PaginationMetadata {
+                total_count: Some(5),
+                page_request: Some(PageRequest::new(
+                    Some(2),
+                    Some(SignedOffset::new(OffsetCursor::new(0))),
+                    None,
+                )),
+                more_available: None,
+            },
+            SignedCursorProvider::<_, TestKey>::new(OffsetCursorProvider::new()),
+        );
+
+        assert!(conn.page_info.has_next_page);
+        assert_eq!(
+            conn.page_info.start_cursor,
+            Some(SignedOffset::new(OffsetCursor::new(1)))
+        );
+        let end = conn.page_info.end_cursor.unwrap();
+        assert_eq!(
+            SignedOffset::from_encoded_string(&end.to_encoded_string())
+                .unwrap()
+                .inner,
+            OffsetCursor::new(2)
+        );
+    }
+
+    type SignedString = SignedCursor<StringCursor, TestKey>;
+
+    #[derive(Debug, Clone, GraphQLObject, RelayConnection, Eq, PartialEq)]
+    #[relay(cursor = SignedString)]
+    pub struct Outpost {
+        pub id: i32,
+    }
+
+    #[test]
+    fn test_inner_provider_gets_its_cursors_back() {
+        let calls = Cell::new(0);
+        let inner = FnCursorProvider::new(|_, idx, _: &Outpost| {
+            calls.set(calls.get() + 1);
+            StringCursor::new(idx.to_string())
+        });
+
+        let items: Vec<Option<Outpost>> = (0..3).map(|id| Some(Outpost { id })).collect();
+        let conn = OutpostRelayConnection::new(
+            Some(&items),
+            None,
+            SignedCursorProvider::<_, TestKey>::new(inner),
+            None,
+        );
+        assert_eq!(calls.get(), 3);
+        assert_eq!(
+            conn.page_info.end_cursor,
+            Some(SignedString::new(StringCursor::new("2".to_string())))
+        );
+    }
+
+    struct Query;
+
+    #[graphql_object]
+    impl Query {
+        fn expeditions(after: Option<SignedOffset>) -> ExpeditionRelayConnection {
+            let items = vec![Some(Expedition { id: 1 })];
+            ExpeditionRelayConnection::new(
+                Some(&items),
+                None,
+                SignedCursorProvider::<_, TestKey>::new(OffsetCursorProvider::new()),
+                Some(PageRequest::new(Some(1), after, None)),
+            )
+        }
+
+        fn outposts(after: Option<SignedString>) -> OutpostRelayConnection {
+            let items = vec![Some(Outpost { id: 1 })];
+            let provider = FnCursorProvider::new(|_, _, outpost: &Outpost| {
+                StringCursor::new(outpost.id.to_string())
+            });
+            OutpostRelayConnection::new(
+                Some(&items),
+                None,
+                SignedCursorProvider::<_, TestKey>::new(provider),
+                Some(PageRequest::new(Some(1), after, None)),
+            )
+        }
+    }
+
+    #[test]
+    fn test_named_after_inner_cursor_in_schema() {
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        let after_offset = SignedOffset::new(OffsetCursor::new(0)).to_encoded_string();
+        let after_string =
+            SignedString::new(StringCursor::new("0".to_string())).to_encoded_string();
+        let query = format!(
+            r#"{{
+                offset: __type(name: "SignedOffsetCursor") {{ kind }}
+                string: __type(name: "SignedStringCursor") {{ kind }}
+                expeditions(after: "{after_offset}") {{ edges {{ cursor }} }}
+                outposts(after: "{after_string}") {{ edges {{ cursor }} }}
+            }}"#
+        );
+
+        let (result, errors) =
+            juniper::execute_sync(&query, None, &schema, &juniper::Variables::new(), &()).unwrap();
+        assert!(errors.is_empty(), "{errors:?}");
+
+        let result = result.to_string();
+        assert_eq!(result.matches(r#""kind": "SCALAR""#).count(), 2, "{result}");
+        let outpost_cursor = SignedString::new(StringCursor::new("1".to_string()));
+        assert!(
+            result.contains(&outpost_cursor.to_encoded_string()),
+            "{result}"
+        );
+    }
+}
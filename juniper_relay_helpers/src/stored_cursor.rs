@@ -0,0 +1,671 @@
+use crate::page_info_factory::PageInfoParts;
+use crate::{
+    Cursor, CursorError, CursorProvider, PageInfoFactory, PageRequest, PaginationMetadata,
+};
+use juniper::GraphQLScalar;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, RandomState};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Keeps the state behind `StoredCursor` tokens on the server, so the cursors clients see are
+/// random tokens that can't be decoded at all.
+///
+/// `InMemoryCursorStore` is enough for a single process. Implement this over something like Redis
+/// to share tokens between instances.
+pub trait CursorStore {
+    /// The state each token stands for, usually the cursor type of the wrapped provider.
+    type CursorState;
+
+    /// Stores the state and returns a new token for it.
+    fn put(&self, state: Self::CursorState) -> String;
+
+    /// The state for a token, or `None` if the token is unknown or has expired.
+    fn get(&self, token: &str) -> Option<Self::CursorState>;
+}
+
+impl<S> CursorStore for &S
+where
+    S: CursorStore + ?Sized,
+{
+    type CursorState = S::CursorState;
+
+    fn put(&self, state: Self::CursorState) -> String {
+        (**self).put(state)
+    }
+
+    fn get(&self, token: &str) -> Option<Self::CursorState> {
+        (**self).get(token)
+    }
+}
+
+impl<S> CursorStore for Arc<S>
+where
+    S: CursorStore + ?Sized,
+{
+    type CursorState = S::CursorState;
+
+    fn put(&self, state: Self::CursorState) -> String {
+        (**self).put(state)
+    }
+
+    fn get(&self, token: &str) -> Option<Self::CursorState> {
+        (**self).get(token)
+    }
+}
+
+/// A `CursorStore` holding the states in a `HashMap`, each for a fixed time after it's stored.
+///
+/// Tokens are 128 bits, hashed from a counter with a key picked at random when the store is
+/// created, so they can't be guessed from one another. Expired states are dropped as they're
+/// looked up, or all at once with `purge_expired`.
+///
+/// Tokens that are never looked up again would otherwise pile up, so `put` also purges the expired
+/// states whenever the store has doubled in size since it last did. The store holds at most around
+/// twice the states stored within the last `ttl`, and each purge is paid for by the puts before it.
+pub struct InMemoryCursorStore<CursorT> {
+    ttl: Duration,
+    states: Mutex<HashMap<String, (CursorT, Instant)>>,
+    tokens: RandomState,
+    minted: AtomicU64,
+    purge_at: AtomicUsize,
+}
+
+/// The size an `InMemoryCursorStore` grows to before `put` first purges it.
+const MIN_PURGE_AT: usize = 1024;
+
+impl<CursorT> InMemoryCursorStore<CursorT> {
+    /// A store keeping each state for `ttl` after it's stored.
+    pub fn new(ttl: Duration) -> Self {
+        InMemoryCursorStore {
+            ttl,
+            states: Mutex::new(HashMap::new()),
+            tokens: RandomState::new(),
+            minted: AtomicU64::new(0),
+            purge_at: AtomicUsize::new(MIN_PURGE_AT),
+        }
+    }
+
+    /// Drops every state that has expired.
+    pub fn purge_expired(&self) {
+        let ttl = self.ttl;
+        self.lock()
+            .retain(|_, (_, stored_at)| stored_at.elapsed() < ttl);
+    }
+
+    /// The number of states held, including any that have expired but not been dropped yet.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether the store holds no states.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, (CursorT, Instant)>> {
+        // The map is never left half-updated, so a panic elsewhere doesn't make it unusable.
+        self.states
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn mint_token(&self) -> String {
+        let n = self.minted.fetch_add(1, AtomicOrdering::Relaxed);
+        format!(
+            "{:016x}{:016x}",
+            self.tokens.hash_one((n, 0u8)),
+            self.tokens.hash_one((n, 1u8))
+        )
+    }
+}
+
+impl<CursorT: Clone> CursorStore for InMemoryCursorStore<CursorT> {
+    type CursorState = CursorT;
+
+    fn put(&self, state: CursorT) -> String {
+        let token = self.mint_token();
+        let mut states = self.lock();
+        if states.len() >= self.purge_at.load(AtomicOrdering::Relaxed) {
+            let ttl = self.ttl;
+            states.retain(|_, (_, stored_at)| stored_at.elapsed() < ttl);
+            self.purge_at.store(
+                states.len().saturating_mul(2).max(MIN_PURGE_AT),
+                AtomicOrdering::Relaxed,
+            );
+        }
+        states.insert(token.clone(), (state, Instant::now()));
+        token
+    }
+
+    fn get(&self, token: &str) -> Option<CursorT> {
+        let mut states = self.lock();
+        let (state, stored_at) = states.get(token)?;
+        if stored_at.elapsed() < self.ttl {
+            Some(state.clone())
+        } else {
+            states.remove(token);
+            None
+        }
+    }
+}
+
+/// A cursor that's only a token, with the state it stands for kept in a `CursorStore`. Built by the
+/// `StoredCursorProvider`.
+///
+/// The token is passed to and from clients as-is, rather than base64 encoded like other cursors.
+/// Decoding only checks it looks like a token; use `StoredCursorProvider::resolve_page_request` to
+/// look it up.
+#[derive(Debug, GraphQLScalar, Clone, Eq, PartialEq, Hash)]
+#[graphql(
+    name = "StoredCursor",
+    to_output_with = Self::to_output,
+    from_input_with = Self::from_input
+)]
+pub struct StoredCursor {
+    /// The token the state is stored under.
+    pub token: String,
+}
+
+impl StoredCursor {
+    pub fn new(token: String) -> Self {
+        StoredCursor { token }
+    }
+}
+
+impl Cursor for StoredCursor {
+    type CursorType = StoredCursor;
+
+    fn to_raw_string(&self) -> String {
+        self.token.clone()
+    }
+
+    fn new(raw: &str, _parts: Vec<&str>) -> Result<StoredCursor, CursorError> {
+        let is_token = !raw.is_empty()
+            && raw
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_');
+        if is_token {
+            Ok(StoredCursor::new(raw.to_string()))
+        } else {
+            Err(CursorError::InvalidCursor)
+        }
+    }
+
+    fn from_encoded_string(input: &str) -> Result<StoredCursor, CursorError> {
+        <StoredCursor as Cursor>::new(input, vec![input])
+    }
+
+    fn to_encoded_string(&self) -> String {
+        self.token.clone()
+    }
+}
+
+/// Wraps another cursor provider, storing each cursor it builds in a `CursorStore` and handing out
+/// a `StoredCursor` token in its place. Useful where cursors have to be fully opaque, which even an
+/// `EncryptedCursor` isn't quite: it can still be kept and replayed for as long as the key lives,
+/// where a token can be expired.
+///
+/// Resolvers look the incoming tokens up with `resolve_page_request` before querying, which
+/// rejects unknown or expired ones with `CursorError::UnknownToken`:
+///
+/// ```
+/// use juniper::GraphQLObject;
+/// use juniper_relay_helpers::{
+///     InMemoryCursorStore, OffsetCursor, OffsetCursorProvider, PageRequest, RelayConnection,
+///     StoredCursor, StoredCursorProvider,
+/// };
+/// use std::time::Duration;
+///
+/// #[derive(Clone, GraphQLObject, RelayConnection)]
+/// #[relay(cursor = StoredCursor)]
+/// pub struct Note {
+///     pub text: String,
+/// }
+///
+/// // Shared between requests, usually from the app's context.
+/// let store = InMemoryCursorStore::<OffsetCursor>::new(Duration::from_secs(15 * 60));
+/// let provider = StoredCursorProvider::new(OffsetCursorProvider::new(), &store);
+///
+/// let request = PageRequest::<StoredCursor>::new(Some(1), None, None);
+/// let offsets: PageRequest<OffsetCursor> = provider.resolve_page_request(&request).unwrap();
+/// // ... query with `offsets` ...
+/// let notes = vec![Some(Note { text: "Tomorrow comes".to_string() })];
+///
+/// let conn = NoteRelayConnection::new(Some(&notes), Some(3), provider, Some(request));
+/// let token = conn.page_info.end_cursor.unwrap();
+/// assert_eq!(store.get(&token.token), Some(OffsetCursor::new(0)));
+/// # use juniper_relay_helpers::CursorStore;
+/// ```
+///
+/// The tokens in the request are looked up once for the whole connection, and the `PageInfo` reuses
+/// the tokens of the first and last edges, so a page of `n` items stores `n` states.
+pub struct StoredCursorProvider<P, S>
+where
+    S: CursorStore,
+    S::CursorState: Cursor,
+{
+    inner: P,
+    store: S,
+    resolved: Mutex<Option<ResolvedRequest<S::CursorState>>>,
+    issued: Mutex<Option<IssuedCursors<S::CursorState>>>,
+}
+
+/// A page request as it came in, and with its tokens resolved to the states they stand for.
+type ResolvedRequest<CursorT> = (PageRequest<StoredCursor>, PageRequest<CursorT>);
+
+/// The tokens last handed out for a page's edges, along with the states stored under them.
+type IssuedCursors<CursorT> = (Vec<StoredCursor>, Vec<CursorT>);
+
+/// Locks one of the provider's caches. They're only ever replaced whole, so a panic elsewhere
+/// can't leave them inconsistent.
+fn lock_cache<T>(cache: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    cache
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+impl<P, S> StoredCursorProvider<P, S>
+where
+    S: CursorStore,
+    S::CursorState: Cursor,
+{
+    pub fn new(inner: P, store: S) -> Self {
+        StoredCursorProvider {
+            inner,
+            store,
+            resolved: Mutex::new(None),
+            issued: Mutex::new(None),
+        }
+    }
+
+    /// Looks up the `after` and `before` tokens of a request, for querying with. Returns
+    /// `CursorError::UnknownToken` if either is unknown or has expired.
+    pub fn resolve_page_request(
+        &self,
+        page_request: &PageRequest<StoredCursor>,
+    ) -> Result<PageRequest<S::CursorState>, CursorError> {
+        let resolve = |cursor: &Option<StoredCursor>| {
+            cursor
+                .as_ref()
+                .map(|cursor| {
+                    self.store
+                        .get(&cursor.token)
+                        .ok_or(CursorError::UnknownToken)
+                })
+                .transpose()
+        };
+
+        Ok(PageRequest {
+            first: page_request.first,
+            after: resolve(&page_request.after)?,
+            before: resolve(&page_request.before)?,
+            last: page_request.last,
+        })
+    }
+
+    fn store_cursor(&self, cursor: S::CursorState) -> StoredCursor {
+        StoredCursor::new(self.store.put(cursor))
+    }
+
+    /// The metadata for the inner provider. Tokens that can't be resolved are dropped, so
+    /// resolvers should check them with `resolve_page_request` first.
+    ///
+    /// The connection asks for this several times with the same page request, so the last
+    /// resolution is kept rather than going back to the store each time.
+    fn resolved_metadata(
+        &self,
+        metadata: &PaginationMetadata<StoredCursor>,
+    ) -> PaginationMetadata<S::CursorState> {
+        PaginationMetadata {
+            total_count: metadata.total_count,
+            page_request: metadata
+                .page_request
+                .as_ref()
+                .map(|pr| self.resolved_request(pr)),
+            more_available: metadata.more_available,
+        }
+    }
+
+    fn resolved_request(
+        &self,
+        page_request: &PageRequest<StoredCursor>,
+    ) -> PageRequest<S::CursorState> {
+        let mut resolved = lock_cache(&self.resolved);
+        if let Some((request, states)) = resolved.as_ref()
+            && request == page_request
+        {
+            return states.clone();
+        }
+
+        let resolve = |cursor: &Option<StoredCursor>| {
+            cursor
+                .as_ref()
+                .and_then(|cursor| self.store.get(&cursor.token))
+        };
+        let states = PageRequest {
+            first: page_request.first,
+            after: resolve(&page_request.after),
+            before: resolve(&page_request.before),
+            last: page_request.last,
+        };
+        *resolved = Some((page_request.clone(), states.clone()));
+        states
+    }
+}
+
+impl<ItemT, P, S> CursorProvider<ItemT> for StoredCursorProvider<P, S>
+where
+    P: CursorProvider<ItemT>,
+    S: CursorStore<CursorState = P::CursorType>,
+{
+    type CursorType = StoredCursor;
+
+    fn get_cursor_for_item(
+        &self,
+        metadata: &PaginationMetadata<StoredCursor>,
+        item_idx: i32,
+        item: Option<&ItemT>,
+    ) -> StoredCursor {
+        self.store_cursor(self.inner.get_cursor_for_item(
+            &self.resolved_metadata(metadata),
+            item_idx,
+            item,
+        ))
+    }
+
+    fn get_cursors_for_items(
+        &self,
+        metadata: &PaginationMetadata<StoredCursor>,
+        items: &[Option<ItemT>],
+    ) -> Vec<StoredCursor> {
+        let cursors = self
+            .inner
+            .get_cursors_for_items(&self.resolved_metadata(metadata), items);
+        let tokens: Vec<StoredCursor> = cursors
+            .iter()
+            .map(|cursor| self.store_cursor(cursor.clone()))
+            .collect();
+
+        // Kept for `get_page_info_for_cursors`, so the inner provider gets its own cursors back.
+        *lock_cache(&self.issued) = Some((tokens.clone(), cursors));
+        tokens
+    }
+
+    fn total_count(&self, metadata: &PaginationMetadata<StoredCursor>) -> Option<i32> {
+        self.inner.total_count(&self.resolved_metadata(metadata))
+    }
+
+    fn get_page_info<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<StoredCursor>,
+        items: Option<&[Option<ItemT>]>,
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<StoredCursor>,
+    {
+        let inner: PageInfoParts<P::CursorType> = self
+            .inner
+            .get_page_info(&self.resolved_metadata(metadata), items);
+
+        PageInfoType::new(
+            inner.has_prev_page,
+            inner.has_next_page,
+            inner.start_cursor.map(|cursor| self.store_cursor(cursor)),
+            inner.end_cursor.map(|cursor| self.store_cursor(cursor)),
+        )
+    }
+
+    fn get_page_info_for_cursors<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<StoredCursor>,
+        items: Option<&[Option<ItemT>]>,
+        cursors: &[StoredCursor],
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<StoredCursor>,
+    {
+        // The states behind the edge tokens, as long as they're the tokens handed out last.
+        let issued = lock_cache(&self.issued)
+            .take()
+            .filter(|(tokens, _)| tokens.as_slice() == cursors)
+            .map(|(_, states)| states)
+            .unwrap_or_default();
+        let inner: PageInfoParts<P::CursorType> =
+            self.inner
+                .get_page_info_for_cursors(&self.resolved_metadata(metadata), items, &issued);
+
+        // The start and end cursors are those of the first and last edges, which already have
+        // tokens, so there's no need to store them again.
+        let reuse = |cursor: Option<P::CursorType>, edge: Option<&StoredCursor>| {
+            cursor.map(|cursor| match edge {
+                Some(edge) => edge.clone(),
+                None => self.store_cursor(cursor),
+            })
+        };
+        PageInfoType::new(
+            inner.has_prev_page,
+            inner.has_next_page,
+            reuse(inner.start_cursor, cursors.first()),
+            reuse(inner.end_cursor, cursors.last()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Cursor, CursorError, CursorStore, FnCursorProvider, InMemoryCursorStore, OffsetCursor,
+        OffsetCursorProvider, PageRequest, RelayConnection, StoredCursor, StoredCursorProvider,
+        StringCursor,
+    };
+    use juniper::GraphQLObject;
+    use std::cell::Cell;
+    use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+    use std::time::Duration;
+
+    #[derive(Debug, Clone, GraphQLObject, RelayConnection)]
+    #[relay(cursor = StoredCursor)]
+    pub struct Entry {
+        text: String,
+    }
+
+    fn entries(count: usize) -> Vec<Option<Entry>> {
+        (0..count)
+            .map(|i| {
+                Some(Entry {
+                    text: format!("Entry {i}"),
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_store_mint_and_resolve() {
+        let store = InMemoryCursorStore::new(Duration::from_secs(60));
+        let first = store.put(OffsetCursor::new(4));
+        let second = store.put(OffsetCursor::new(4));
+
+        assert_ne!(first, second);
+        assert_eq!(first.len(), 32);
+        assert_eq!(store.get(&first), Some(OffsetCursor::new(4)));
+        assert_eq!(store.get(&second), Some(OffsetCursor::new(4)));
+        assert_eq!(store.get("not-a-token"), None);
+        assert_eq!(store.len(), 2);
+    }
+
+    #[test]
+    fn test_store_expiry() {
+        let store = InMemoryCursorStore::new(Duration::ZERO);
+        let token = store.put(OffsetCursor::new(1));
+        store.put(OffsetCursor::new(2));
+
+        assert_eq!(store.get(&token), None);
+        assert_eq!(store.len(), 1);
+        store.purge_expired();
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_token_is_the_encoded_cursor() {
+        let cursor = StoredCursor::new("0a1b2c".to_string());
+        assert_eq!(cursor.to_encoded_string(), "0a1b2c");
+        assert_eq!(StoredCursor::from_encoded_string("0a1b2c"), Ok(cursor));
+        assert_eq!(
+            StoredCursor::from_encoded_string(""),
+            Err(CursorError::InvalidCursor)
+        );
+        assert_eq!(
+            StoredCursor::from_encoded_string("offset||1"),
+            Err(CursorError::InvalidCursor)
+        );
+    }
+
+    #[test]
+    fn test_provider_round_trip() {
+        let store = InMemoryCursorStore::new(Duration::from_secs(60));
+        let provider = || StoredCursorProvider::new(OffsetCursorProvider::new(), &store);
+
+        let items = entries(2);
+        let first_page = EntryRelayConnection::new(
+            Some(&items),
+            Some(4),
+            provider(),
+            Some(PageRequest::new(Some(2), None, None)),
+        );
+        assert!(first_page.page_info.has_next_page);
+        let end = first_page.page_info.end_cursor.unwrap();
+        assert_eq!(store.get(&end.token), Some(OffsetCursor::new(1)));
+
+        // The next request comes back with the token, which resolves to the offset to query from.
+        let request = PageRequest::new(Some(2), Some(end), None);
+        let resolved = provider().resolve_page_request(&request).unwrap();
+        assert_eq!(resolved.after, Some(OffsetCursor::new(1)));
+
+        let second_page =
+            EntryRelayConnection::new(Some(&items), Some(4), provider(), Some(request));
+        assert!(second_page.page_info.has_previous_page);
+        let cursors: Vec<Option<OffsetCursor>> = second_page
+            .edges
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .map(|edge| store.get(&edge.cursor.unwrap().token))
+            .collect();
+        assert_eq!(
+            cursors,
+            vec![Some(OffsetCursor::new(2)), Some(OffsetCursor::new(3))]
+        );
+    }
+
+    #[test]
+    fn test_page_info_reuses_edge_tokens() {
+        let store = InMemoryCursorStore::new(Duration::from_secs(60));
+        let provider = StoredCursorProvider::new(OffsetCursorProvider::new(), &store);
+
+        let items = entries(3);
+        let conn = EntryRelayConnection::new(
+            Some(&items),
+            Some(10),
+            provider,
+            Some(PageRequest::new(Some(3), None, None)),
+        );
+        assert_eq!(store.len(), 3);
+
+        let edges: Vec<StoredCursor> = conn
+            .edges
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .map(|edge| edge.cursor.unwrap())
+            .collect();
+        assert_eq!(conn.page_info.start_cursor.as_ref(), edges.first());
+        assert_eq!(conn.page_info.end_cursor.as_ref(), edges.last());
+    }
+
+    /// Counts the lookups made against the store it wraps.
+    struct CountingStore {
+        store: InMemoryCursorStore<OffsetCursor>,
+        gets: AtomicUsize,
+    }
+
+    impl CursorStore for CountingStore {
+        type CursorState = OffsetCursor;
+
+        fn put(&self, state: OffsetCursor) -> String {
+            self.store.put(state)
+        }
+
+        fn get(&self, token: &str) -> Option<OffsetCursor> {
+            self.gets.fetch_add(1, AtomicOrdering::Relaxed);
+            self.store.get(token)
+        }
+    }
+
+    #[test]
+    fn test_request_resolved_once_per_connection() {
+        let store = CountingStore {
+            store: InMemoryCursorStore::new(Duration::from_secs(60)),
+            gets: AtomicUsize::new(0),
+        };
+        let after = StoredCursor::new(store.put(OffsetCursor::new(1)));
+
+        let items = entries(5);
+        let conn = EntryRelayConnection::new(
+            Some(&items),
+            Some(10),
+            StoredCursorProvider::new(OffsetCursorProvider::new(), &store),
+            Some(PageRequest::new(Some(5), Some(after), None)),
+        );
+        assert_eq!(conn.len(), 5);
+        assert_eq!(store.gets.load(AtomicOrdering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_inner_provider_gets_its_cursors_back() {
+        let store = InMemoryCursorStore::new(Duration::from_secs(60));
+        let calls = Cell::new(0);
+        let inner = FnCursorProvider::new(|_, idx, _: &Entry| {
+            calls.set(calls.get() + 1);
+            StringCursor::new(idx.to_string())
+        });
+
+        let items = entries(3);
+        let conn = EntryRelayConnection::new(
+            Some(&items),
+            None,
+            StoredCursorProvider::new(inner, &store),
+            None,
+        );
+        assert_eq!(calls.get(), 3);
+        let end = conn.page_info.end_cursor.unwrap();
+        assert_eq!(
+            store.get(&end.token),
+            Some(StringCursor::new("2".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_store_purges_as_it_grows() {
+        let store = InMemoryCursorStore::new(Duration::ZERO);
+        for offset in 0..5000 {
+            store.put(OffsetCursor::new(offset));
+        }
+        // Everything has expired, so each purge leaves only what's been put since.
+        assert!(store.len() <= 1024);
+    }
+
+    #[test]
+    fn test_provider_rejects_expired_tokens() {
+        let store = InMemoryCursorStore::new(Duration::ZERO);
+        let provider = StoredCursorProvider::new(OffsetCursorProvider::new(), &store);
+        let token = store.put(OffsetCursor::new(1));
+
+        let request = PageRequest::new(Some(2), Some(StoredCursor::new(token)), None);
+        assert_eq!(
+            provider.resolve_page_request(&request).unwrap_err(),
+            CursorError::UnknownToken
+        );
+    }
+}
@@ -0,0 +1,484 @@
+use crate::cursor_provider::{built_cursor, full_page_info};
+use crate::{
+    CURSOR_SEGMENT_DELIMITER, Cursor, CursorProvider, PageInfoFactory, PageRequest,
+    PaginationMetadata, RelayConnection, RelayEdge, RelayPageInfo, StringCursor,
+};
+
+/// A single step in a `CursorStabilityTester` run.
+#[derive(Debug, Clone)]
+pub enum StabilityStep<ItemT> {
+    /// Fetch the next page of `first` items, continuing from the end cursor of the previous page.
+    FetchPage { first: i32 },
+
+    /// Simulates a concurrent write by inserting an item into the dataset at the given index.
+    Insert { index: usize, item: ItemT },
+}
+
+/// Outcome of a `CursorStabilityTester` run.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct StabilityReport<ItemT> {
+    /// Items that were returned on more than one page.
+    pub duplicated: Vec<ItemT>,
+
+    /// Items from the original dataset that were never returned on any page.
+    pub skipped: Vec<ItemT>,
+
+    /// The items returned by each page, in the order they were fetched.
+    pub pages: Vec<Vec<ItemT>>,
+}
+
+impl<ItemT> StabilityReport<ItemT> {
+    /// True if no item was duplicated or skipped across the pages.
+    pub fn is_stable(&self) -> bool {
+        self.duplicated.is_empty() && self.skipped.is_empty()
+    }
+}
+
+/// Test utility that pages through a dataset with a given `CursorProvider`, simulating inserts
+/// between page fetches, and reports whether any node was skipped or duplicated.
+///
+/// Useful for seeing how offset and keyed cursors behave under concurrent writes before
+/// committing to one:
+///
+/// ```
+/// use juniper_relay_helpers::{CursorStabilityTester, OffsetCursorProvider};
+///
+/// let report = CursorStabilityTester::new(vec!["a", "b", "c", "d"])
+///     .fetch_page(2)
+///     .insert(0, "z")
+///     .fetch_page(2)
+///     .fetch_page(2)
+///     .run(&OffsetCursorProvider::new());
+///
+/// // "b" is returned again on the second page as the insert shifted the offsets:
+/// assert!(!report.is_stable());
+/// assert_eq!(report.duplicated, vec!["b"]);
+/// ```
+///
+/// Each page is resolved by finding the previous page's end cursor within the current dataset and
+/// starting from the item after it. Items in `skipped` are only meaningful if the steps page all
+/// the way through the dataset.
+#[derive(Debug, Clone)]
+pub struct CursorStabilityTester<ItemT> {
+    dataset: Vec<ItemT>,
+    steps: Vec<StabilityStep<ItemT>>,
+}
+
+impl<ItemT> CursorStabilityTester<ItemT>
+where
+    ItemT: Clone + PartialEq,
+{
+    /// Builds a tester over the initial dataset.
+    pub fn new(dataset: Vec<ItemT>) -> Self {
+        CursorStabilityTester {
+            dataset,
+            steps: vec![],
+        }
+    }
+
+    /// Adds a page fetch of `first` items to the run.
+    pub fn fetch_page(mut self, first: i32) -> Self {
+        self.steps.push(StabilityStep::FetchPage { first });
+        self
+    }
+
+    /// Adds an insert of `item` at `index` to the run. Indexes past the end append the item.
+    pub fn insert(mut self, index: usize, item: ItemT) -> Self {
+        self.steps.push(StabilityStep::Insert { index, item });
+        self
+    }
+
+    /// Runs the steps against the provider and reports on any duplicated or skipped items.
+    pub fn run<ProviderT>(&self, provider: &ProviderT) -> StabilityReport<ItemT>
+    where
+        ProviderT: CursorProvider<ItemT>,
+        ProviderT::CursorType: PartialEq,
+    {
+        let mut dataset = self.dataset.clone();
+        let mut after: Option<ProviderT::CursorType> = None;
+        let mut pages: Vec<Vec<ItemT>> = vec![];
+
+        for step in &self.steps {
+            match step {
+                StabilityStep::Insert { index, item } => {
+                    dataset.insert((*index).min(dataset.len()), item.clone());
+                }
+                StabilityStep::FetchPage { first } => {
+                    let start = match &after {
+                        Some(after_cursor) => {
+                            // Cursors are located against the dataset as it is now, the same way a
+                            // resolver would look them up against the store.
+                            let unpaged = PaginationMetadata {
+                                total_count: Some(dataset.len() as i32),
+                                page_request: None,
+                                more_available: None,
+                            };
+                            dataset
+                                .iter()
+                                .enumerate()
+                                .position(|(idx, item)| {
+                                    provider
+                                        .get_cursor_for_item(&unpaged, idx as i32, Some(item))
+                                        .eq(after_cursor)
+                                })
+                                .map(|idx| idx + 1)
+                                .unwrap_or(dataset.len())
+                        }
+                        None => 0,
+                    };
+
+                    let page: Vec<ItemT> = dataset
+                        .iter()
+                        .skip(start)
+                        .take((*first).max(0) as usize)
+                        .cloned()
+                        .collect();
+
+                    if let Some(last) = page.last() {
+                        let metadata = PaginationMetadata {
+                            total_count: Some(dataset.len() as i32),
+                            page_request: Some(PageRequest::new(Some(*first), after.clone(), None)),
+                            more_available: None,
+                        };
+                        after = Some(provider.get_cursor_for_item(
+                            &metadata,
+                            page.len() as i32 - 1,
+                            Some(last),
+                        ));
+                    }
+
+                    pages.push(page);
+                }
+            }
+        }
+
+        let mut seen: Vec<&ItemT> = vec![];
+        let mut duplicated: Vec<ItemT> = vec![];
+        for item in pages.iter().flatten() {
+            if seen.contains(&item) {
+                if !duplicated.contains(item) {
+                    duplicated.push(item.clone());
+                }
+            } else {
+                seen.push(item);
+            }
+        }
+
+        let skipped = self
+            .dataset
+            .iter()
+            .filter(|item| !seen.contains(item))
+            .cloned()
+            .collect();
+
+        StabilityReport {
+            duplicated,
+            skipped,
+            pages,
+        }
+    }
+}
+
+/// Cursor provider for tests that numbers the items sequentially, so the expected cursors are easy
+/// to write out by hand: `"0"`, `"1"`, `"2"` and so on. An `after` cursor from an earlier page
+/// carries the numbering on, so the second page of two starts at `"2"`.
+///
+/// ```
+/// use juniper::GraphQLObject;
+/// use juniper_relay_helpers::{
+///     assert_connection_page, MockCursorProvider, PageRequest, RelayConnection, StringCursor,
+/// };
+///
+/// #[derive(Clone, GraphQLObject, RelayConnection)]
+/// pub struct Lumina {
+///     pub name: String,
+/// }
+///
+/// let luminas = vec![
+///     Some(Lumina { name: "Energy Tint".to_string() }),
+///     Some(Lumina { name: "Revive Tint".to_string() }),
+/// ];
+/// let conn = LuminaRelayConnection::new(
+///     Some(&luminas),
+///     None,
+///     MockCursorProvider,
+///     Some(PageRequest::new(Some(2), Some(StringCursor::new("1".to_string())), None)),
+/// );
+///
+/// assert_connection_page(&conn, &["2", "3"], true, true);
+/// ```
+///
+/// PageInfo follows the same rules as the `KeysetCursorProvider`: there's a previous page if an
+/// `after` was given, and a next page if `PaginationMetadata::more_available` says so or, failing
+/// that, the page is full. Requires the `test-util` feature.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MockCursorProvider;
+
+impl MockCursorProvider {
+    /// Number of the first item in the page: the one after the `after` cursor, or 0.
+    fn page_start(metadata: &PaginationMetadata<StringCursor>) -> i32 {
+        metadata
+            .page_request
+            .as_ref()
+            .and_then(|pr| pr.after.as_ref())
+            .and_then(|after| after.value.parse::<i32>().ok())
+            .map_or(0, |after| after.saturating_add(1))
+    }
+}
+
+impl<ItemT> CursorProvider<ItemT> for MockCursorProvider {
+    type CursorType = StringCursor;
+
+    fn get_cursor_for_item(
+        &self,
+        metadata: &PaginationMetadata<StringCursor>,
+        item_idx: i32,
+        _item: Option<&ItemT>,
+    ) -> StringCursor {
+        StringCursor::new(
+            Self::page_start(metadata)
+                .saturating_add(item_idx)
+                .to_string(),
+        )
+    }
+
+    fn get_page_info<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<StringCursor>,
+        items: Option<&[Option<ItemT>]>,
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<StringCursor>,
+    {
+        full_page_info(metadata, items, |idx, item: &Option<ItemT>| {
+            self.get_cursor_for_item(metadata, idx, item.as_ref())
+        })
+    }
+
+    fn get_page_info_for_cursors<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<StringCursor>,
+        items: Option<&[Option<ItemT>]>,
+        cursors: &[StringCursor],
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<StringCursor>,
+    {
+        full_page_info(metadata, items, |idx, item: &Option<ItemT>| {
+            built_cursor(cursors, idx, || {
+                self.get_cursor_for_item(metadata, idx, item.as_ref())
+            })
+        })
+    }
+}
+
+/// Asserts that a connection's edges have the `expected_cursors`, in order, and that its PageInfo
+/// has the matching start and end cursors and the given `has_next_page` and `has_previous_page`.
+///
+/// The expected cursors are compared by their raw string form without the type tag (for a
+/// `StringCursor`, its value; for an `OffsetCursor`, `"10"` or `"10||first=5"`), so they're quick
+/// to write out. Panics with the mismatching part on failure. Requires the `test-util` feature.
+#[track_caller]
+pub fn assert_connection_page<ConnectionT>(
+    connection: &ConnectionT,
+    expected_cursors: &[&str],
+    has_next_page: bool,
+    has_previous_page: bool,
+) where
+    ConnectionT: RelayConnection,
+    ConnectionT::EdgeType: RelayEdge<CursorType = ConnectionT::CursorType>,
+{
+    let cursor_string = |cursor: Option<&ConnectionT::CursorType>| {
+        cursor.map(|cursor| untagged_raw_string(&cursor.to_raw_string()))
+    };
+
+    let edge_cursors: Vec<Option<String>> = connection
+        .edges()
+        .unwrap_or_default()
+        .iter()
+        .map(|edge| cursor_string(edge.as_ref().and_then(|edge| edge.cursor())))
+        .collect();
+    let expected: Vec<Option<String>> = expected_cursors
+        .iter()
+        .map(|cursor| Some(cursor.to_string()))
+        .collect();
+    assert_eq!(edge_cursors, expected, "edge cursors");
+
+    let page_info = connection.page_info();
+    assert_eq!(
+        cursor_string(page_info.start_cursor()),
+        expected.first().cloned().flatten(),
+        "start cursor"
+    );
+    assert_eq!(
+        cursor_string(page_info.end_cursor()),
+        expected.last().cloned().flatten(),
+        "end cursor"
+    );
+    assert_eq!(page_info.has_next_page(), has_next_page, "has_next_page");
+    assert_eq!(
+        page_info.has_previous_page(),
+        has_previous_page,
+        "has_previous_page"
+    );
+}
+
+/// The raw string of a cursor with the leading type tag segment removed.
+fn untagged_raw_string(raw: &str) -> String {
+    match raw.split_once(CURSOR_SEGMENT_DELIMITER) {
+        Some((_, rest)) => rest.to_string(),
+        None => raw.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CursorByKey, CursorStabilityTester, KeyedCursorProvider, MockCursorProvider, OffsetCursor,
+        OffsetCursorProvider, PageRequest, RelayConnection, StringCursor, assert_connection_page,
+    };
+    use juniper::GraphQLObject;
+
+    #[derive(Debug, Clone, Eq, PartialEq)]
+    struct Row {
+        id: String,
+    }
+
+    impl CursorByKey for Row {
+        fn cursor_key(&self) -> String {
+            self.id.clone()
+        }
+    }
+
+    fn row(id: &str) -> Row {
+        Row { id: id.to_string() }
+    }
+
+    fn tester() -> CursorStabilityTester<Row> {
+        CursorStabilityTester::new(vec![
+            row("a"),
+            row("b"),
+            row("c"),
+            row("d"),
+            row("e"),
+            row("f"),
+        ])
+        .fetch_page(2)
+        .insert(0, row("new"))
+        .fetch_page(2)
+        .fetch_page(2)
+        .fetch_page(2)
+    }
+
+    #[test]
+    fn test_offset_cursors_are_unstable_under_inserts() {
+        let report = tester().run(&OffsetCursorProvider::new());
+
+        assert!(!report.is_stable());
+        assert_eq!(report.duplicated, vec![row("b")]);
+        assert!(report.skipped.is_empty());
+        assert_eq!(report.pages[1], vec![row("b"), row("c")]);
+    }
+
+    #[test]
+    fn test_keyed_cursors_are_stable_under_inserts() {
+        let report = tester().run(&KeyedCursorProvider);
+
+        assert!(report.is_stable());
+        assert_eq!(report.pages[1], vec![row("c"), row("d")]);
+        assert_eq!(report.pages[3], vec![]);
+    }
+
+    #[test]
+    fn test_unfetched_items_are_skipped() {
+        let report = CursorStabilityTester::new(vec![row("a"), row("b"), row("c")])
+            .fetch_page(2)
+            .run(&KeyedCursorProvider);
+
+        assert!(!report.is_stable());
+        assert_eq!(report.skipped, vec![row("c")]);
+    }
+
+    #[derive(Debug, Clone, GraphQLObject, RelayConnection)]
+    pub struct Picto {
+        name: String,
+    }
+
+    #[derive(Debug, Clone, GraphQLObject, RelayConnection)]
+    #[relay(cursor = OffsetCursor)]
+    pub struct Lumina {
+        name: String,
+    }
+
+    fn pictos(count: usize) -> Vec<Option<Picto>> {
+        (0..count)
+            .map(|i| {
+                Some(Picto {
+                    name: format!("Picto {i}"),
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_mock_cursor_provider_numbers_pages() {
+        let first_page = PictoRelayConnection::new(
+            Some(&pictos(2)),
+            None,
+            MockCursorProvider,
+            Some(PageRequest::new(Some(2), None, None)),
+        );
+        assert_connection_page(&first_page, &["0", "1"], true, false);
+
+        let second_page = PictoRelayConnection::new(
+            Some(&pictos(1)),
+            None,
+            MockCursorProvider,
+            Some(PageRequest::new(
+                Some(2),
+                first_page.page_info.end_cursor.clone(),
+                None,
+            )),
+        );
+        assert_connection_page(&second_page, &["2"], false, true);
+
+        let empty = PictoRelayConnection::new(Some(&[]), None, MockCursorProvider, None);
+        assert_connection_page(&empty, &[], false, false);
+    }
+
+    #[test]
+    fn test_mock_cursor_provider_ignores_foreign_after() {
+        let conn = PictoRelayConnection::new(
+            Some(&pictos(1)),
+            None,
+            MockCursorProvider,
+            Some(PageRequest::new(
+                None,
+                Some(StringCursor::new("not-a-number".to_string())),
+                None,
+            )),
+        );
+        assert_connection_page(&conn, &["0"], false, true);
+    }
+
+    #[test]
+    fn test_assert_connection_page_strips_cursor_tag() {
+        let luminas = vec![Some(Lumina {
+            name: "Energy Tint".to_string(),
+        })];
+        let conn = LuminaRelayConnection::new(
+            Some(&luminas),
+            Some(3),
+            OffsetCursorProvider::new(),
+            Some(PageRequest::new(Some(1), Some(OffsetCursor::new(0)), None)),
+        );
+        assert_connection_page(&conn, &["1"], true, true);
+    }
+
+    #[test]
+    #[should_panic(expected = "edge cursors")]
+    fn test_assert_connection_page_mismatch() {
+        let conn = PictoRelayConnection::new(Some(&pictos(2)), None, MockCursorProvider, None);
+        assert_connection_page(&conn, &["0", "2"], false, false);
+    }
+}
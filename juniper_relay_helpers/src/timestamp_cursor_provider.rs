@@ -0,0 +1,190 @@
+use crate::cursor_provider::{built_cursor, full_page_info};
+use crate::{CursorProvider, PageInfoFactory, PaginationMetadata, TimestampCursor};
+use chrono::{DateTime, Utc};
+
+/// Implemented by items paged with the `TimestampCursorProvider`.
+pub trait CursorByTimestamp {
+    /// The timestamp the feed is ordered by, usually when the item was created.
+    fn created_at(&self) -> DateTime<Utc>;
+
+    /// The item's id, breaking ties between items with the same timestamp.
+    fn cursor_id(&self) -> String;
+}
+
+/// Cursor provider for time-ordered feeds, giving each edge a `TimestampCursor` of the item's
+/// `created_at` and id. Requires the `chrono` feature.
+///
+/// ```
+/// use chrono::{DateTime, TimeZone, Utc};
+/// use juniper::GraphQLObject;
+/// use juniper_relay_helpers::{
+///     CursorByTimestamp, RelayConnection, TimestampCursor, TimestampCursorProvider,
+/// };
+///
+/// #[derive(Clone, GraphQLObject, RelayConnection)]
+/// #[relay(cursor = TimestampCursor)]
+/// pub struct Post {
+///     pub id: String,
+///     #[graphql(skip)]
+///     pub posted_at: DateTime<Utc>,
+/// }
+///
+/// impl CursorByTimestamp for Post {
+///     fn created_at(&self) -> DateTime<Utc> {
+///         self.posted_at
+///     }
+///
+///     fn cursor_id(&self) -> String {
+///         self.id.clone()
+///     }
+/// }
+///
+/// let posts = vec![Some(Post {
+///     id: "p1".to_string(),
+///     posted_at: Utc.with_ymd_and_hms(1905, 3, 14, 9, 30, 0).unwrap(),
+/// })];
+/// let conn = PostRelayConnection::new(Some(&posts), None, TimestampCursorProvider, None);
+/// assert_eq!(conn.page_info.end_cursor.unwrap().id, "p1");
+/// ```
+///
+/// The resolver turns the `after` cursor back into `WHERE (created_at, id) > (?, ?)`. As with the
+/// `KeysetCursorProvider`, `has_next_page` comes from `PaginationMetadata::more_available` if it's
+/// set, and otherwise a full page is taken to mean more may follow. If an `after` is provided, it's
+/// assumed there is a previous page.
+pub struct TimestampCursorProvider;
+
+impl<ItemT> CursorProvider<ItemT> for TimestampCursorProvider
+where
+    ItemT: CursorByTimestamp,
+{
+    type CursorType = TimestampCursor;
+
+    fn get_cursor_for_item(
+        &self,
+        _metadata: &PaginationMetadata<TimestampCursor>,
+        _item_idx: i32,
+        item: Option<&ItemT>,
+    ) -> TimestampCursor {
+        item.map(|item| TimestampCursor::new(item.created_at(), item.cursor_id()))
+            .unwrap_or_default()
+    }
+
+    fn get_page_info<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<TimestampCursor>,
+        items: Option<&[Option<ItemT>]>,
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<TimestampCursor>,
+    {
+        full_page_info(metadata, items, |idx, item: &Option<ItemT>| {
+            self.get_cursor_for_item(metadata, idx, item.as_ref())
+        })
+    }
+
+    fn get_page_info_for_cursors<PageInfoType>(
+        &self,
+        metadata: &PaginationMetadata<TimestampCursor>,
+        items: Option<&[Option<ItemT>]>,
+        cursors: &[TimestampCursor],
+    ) -> PageInfoType
+    where
+        PageInfoType: PageInfoFactory<TimestampCursor>,
+    {
+        full_page_info(metadata, items, |idx, item: &Option<ItemT>| {
+            built_cursor(cursors, idx, || {
+                self.get_cursor_for_item(metadata, idx, item.as_ref())
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CursorByTimestamp, PageRequest, PaginationMetadata, RelayConnection, TimestampCursor,
+        TimestampCursorProvider,
+    };
+    use chrono::{DateTime, TimeZone, Utc};
+    use juniper::GraphQLObject;
+
+    #[derive(Debug, Clone, GraphQLObject, RelayConnection)]
+    #[relay(cursor = TimestampCursor)]
+    pub struct Letter {
+        id: i32,
+        #[graphql(skip)]
+        sent_at: DateTime<Utc>,
+    }
+
+    impl CursorByTimestamp for Letter {
+        fn created_at(&self) -> DateTime<Utc> {
+            self.sent_at
+        }
+
+        fn cursor_id(&self) -> String {
+            self.id.to_string()
+        }
+    }
+
+    fn letters(sent: &[(i32, i64)]) -> Vec<Option<Letter>> {
+        sent.iter()
+            .map(|&(id, secs)| {
+                Some(Letter {
+                    id,
+                    sent_at: Utc.timestamp_opt(secs, 0).unwrap(),
+                })
+            })
+            .collect()
+    }
+
+    fn cursor(secs: i64, id: &str) -> TimestampCursor {
+        TimestampCursor::new(Utc.timestamp_opt(secs, 0).unwrap(), id.to_string())
+    }
+
+    #[test]
+    fn test_cursors_from_items() {
+        let items = letters(&[(7, 100), (3, 100), (1, 200)]);
+        let conn = LetterRelayConnection::new(
+            Some(&items),
+            None,
+            TimestampCursorProvider,
+            Some(PageRequest::new(Some(3), Some(cursor(50, "9")), None)),
+        );
+
+        let cursors: Vec<Option<TimestampCursor>> = conn
+            .edges
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .map(|edge| edge.cursor)
+            .collect();
+        assert_eq!(
+            cursors,
+            vec![
+                Some(cursor(100, "7")),
+                Some(cursor(100, "3")),
+                Some(cursor(200, "1"))
+            ]
+        );
+        assert!(conn.page_info.has_previous_page);
+        assert!(conn.page_info.has_next_page);
+        assert_eq!(conn.page_info.start_cursor, Some(cursor(100, "7")));
+        assert_eq!(conn.page_info.end_cursor, Some(cursor(200, "1")));
+    }
+
+    #[test]
+    fn test_more_available() {
+        let items = letters(&[(1, 100), (2, 200)]);
+        let conn = LetterRelayConnection::from_metadata(
+            Some(&items),
+            PaginationMetadata {
+                total_count: None,
+                page_request: Some(PageRequest::new(Some(2), None, None)),
+                more_available: Some(false),
+            },
+            TimestampCursorProvider,
+        );
+        assert!(!conn.page_info.has_previous_page);
+        assert!(!conn.page_info.has_next_page);
+    }
+}
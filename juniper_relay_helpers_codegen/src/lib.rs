@@ -3,102 +3,613 @@ use proc_macro2::{Ident, Span};
 use quote::quote;
 use syn::{Data, DeriveInput, parse_macro_input};
 
+/// Options parsed from the `#[relay(...)]` attribute on a `RelayConnection` node.
+#[derive(Default)]
+struct RelayAttributes {
+    /// Custom Juniper context for the generated types: `context = MyContext`
+    context: Option<syn::Path>,
+
+    /// Custom Juniper `ScalarValue` for the generated types: `scalar = MyScalarValue`
+    scalar: Option<syn::Path>,
+
+    /// Cursor type to use for the connection: `cursor = OffsetCursor`
+    cursor: Option<syn::Path>,
+
+    /// Adds a `pageSize` field to the connection: `expose_page_size`
+    expose_page_size: bool,
+
+    /// Derives serde `Serialize` and `Deserialize` for the PageInfo: `serde`
+    serde: bool,
+
+    /// Generates a `FooPageArgs` input object for the pagination arguments: `page_args`
+    page_args: bool,
+
+    /// Suffix for the Rust connection and PageInfo type names: `rust_suffix = "Conn"`
+    rust_suffix: Option<syn::LitStr>,
+
+    /// Suffix for the GraphQL connection and PageInfo type names: `gql_suffix = "List"`
+    gql_suffix: Option<syn::LitStr>,
+
+    /// Suffix for the Rust edge type name: `edge_rust_suffix = "ListEdge"`
+    edge_rust_suffix: Option<syn::LitStr>,
+
+    /// Suffix for the GraphQL edge type name: `edge_gql_suffix = "ListEdge"`
+    edge_gql_suffix: Option<syn::LitStr>,
+
+    /// Adds a flattened `nodes` field alongside `edges`: `with_nodes`
+    with_nodes: bool,
+
+    /// Adds an `errors` field and a `new_partial` constructor: `partial_errors`
+    partial_errors: bool,
+
+    /// Holds each node in an `Arc`, so one allocation can be shared between connections: `rc_node`
+    rc_node: bool,
+
+    /// Makes the edge `cursor` field non-null: `non_null_cursor`
+    non_null_cursor: bool,
+
+    /// Visibility of the generated types, if not the node's own: `visibility = "pub(crate)"`
+    visibility: Option<syn::Visibility>,
+
+    /// Leaves the `count` field off the connection: `no_count`
+    no_count: bool,
+
+    /// GraphQL name for the `count` field: `count_name = "totalCount"`
+    count_name: Option<syn::LitStr>,
+
+    /// Extra fields on the connection, repeatable: `extra_field(name = "total_unread", ty = "i32")`
+    extra_fields: Vec<ExtraField>,
+
+    /// Interfaces for the generated types: `interface(connection = ConnectionValue, edge = EdgeValue)`
+    interface: InterfaceAttributes,
+}
+
+/// GraphQL interfaces the generated types implement, set with
+/// `interface(connection = ConnectionValue, edge = EdgeValue, page_info = PageInfoValue)`.
+#[derive(Default)]
+struct InterfaceAttributes {
+    connection: Option<syn::Path>,
+    edge: Option<syn::Path>,
+    page_info: Option<syn::Path>,
+}
+
+impl InterfaceAttributes {
+    fn parse(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        meta.parse_nested_meta(|meta| {
+            let slot = if meta.path.is_ident("connection") {
+                &mut self.connection
+            } else if meta.path.is_ident("edge") {
+                &mut self.edge
+            } else if meta.path.is_ident("page_info") {
+                &mut self.page_info
+            } else {
+                return Err(meta.error("expected connection, edge or page_info"));
+            };
+            *slot = Some(meta.value()?.parse()?);
+            Ok(())
+        })
+    }
+
+    /// The `impl = ...` to add to a generated type's `#[graphql(...)]`, if it has an interface.
+    fn impl_clause(interface: &Option<syn::Path>) -> proc_macro2::TokenStream {
+        match interface {
+            Some(path) => quote! { , impl = #path },
+            None => quote! {},
+        }
+    }
+}
+
+/// A field added to the connection with `extra_field(...)`.
+struct ExtraField {
+    name: Ident,
+    ty: syn::Type,
+}
+
+impl ExtraField {
+    /// Field names the generated connection already uses.
+    const RESERVED: [&'static str; 6] = [
+        "count",
+        "edges",
+        "errors",
+        "nodes",
+        "page_info",
+        "page_size",
+    ];
+
+    fn parse(meta: syn::meta::ParseNestedMeta) -> syn::Result<Self> {
+        let mut name: Option<syn::LitStr> = None;
+        let mut ty: Option<syn::LitStr> = None;
+
+        meta.parse_nested_meta(|field_meta| {
+            if field_meta.path.is_ident("name") {
+                name = Some(field_meta.value()?.parse()?);
+            } else if field_meta.path.is_ident("ty") {
+                ty = Some(field_meta.value()?.parse()?);
+            } else {
+                return Err(field_meta.error("unsupported extra_field attribute"));
+            }
+            Ok(())
+        })?;
+
+        let name = name.ok_or_else(|| meta.error("extra_field requires a `name`"))?;
+        let ty = ty.ok_or_else(|| meta.error("extra_field requires a `ty`"))?;
+        if Self::RESERVED.contains(&name.value().as_str()) {
+            return Err(syn::Error::new_spanned(
+                &name,
+                "extra_field name clashes with a generated connection field",
+            ));
+        }
+
+        Ok(ExtraField {
+            name: name.parse()?,
+            ty: ty.parse()?,
+        })
+    }
+}
+
+impl RelayAttributes {
+    fn from_attrs(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut relay_attrs = RelayAttributes::default();
+
+        for attr in attrs.iter().filter(|a| a.path().is_ident("relay")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("context") {
+                    relay_attrs.context = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("scalar") {
+                    relay_attrs.scalar = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("cursor") {
+                    relay_attrs.cursor = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("expose_page_size") {
+                    relay_attrs.expose_page_size = true;
+                } else if meta.path.is_ident("serde") {
+                    relay_attrs.serde = true;
+                } else if meta.path.is_ident("page_args") {
+                    relay_attrs.page_args = true;
+                } else if meta.path.is_ident("rust_suffix") {
+                    relay_attrs.rust_suffix = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("gql_suffix") {
+                    relay_attrs.gql_suffix = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("edge_rust_suffix") {
+                    relay_attrs.edge_rust_suffix = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("edge_gql_suffix") {
+                    relay_attrs.edge_gql_suffix = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("with_nodes") {
+                    relay_attrs.with_nodes = true;
+                } else if meta.path.is_ident("partial_errors") {
+                    relay_attrs.partial_errors = true;
+                } else if meta.path.is_ident("rc_node") {
+                    relay_attrs.rc_node = true;
+                } else if meta.path.is_ident("non_null_cursor") {
+                    relay_attrs.non_null_cursor = true;
+                } else if meta.path.is_ident("visibility") {
+                    let vis: syn::LitStr = meta.value()?.parse()?;
+                    relay_attrs.visibility = Some(vis.parse()?);
+                } else if meta.path.is_ident("no_count") {
+                    relay_attrs.no_count = true;
+                } else if meta.path.is_ident("count_name") {
+                    relay_attrs.count_name = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("extra_field") {
+                    relay_attrs.extra_fields.push(ExtraField::parse(meta)?);
+                } else if meta.path.is_ident("interface") {
+                    relay_attrs.interface.parse(meta)?;
+                } else {
+                    return Err(meta.error("unsupported relay attribute"));
+                }
+                Ok(())
+            })?;
+        }
+
+        if let (true, Some(count_name)) = (relay_attrs.no_count, &relay_attrs.count_name) {
+            return Err(syn::Error::new_spanned(
+                count_name,
+                "count_name has no effect with no_count, as there's no count field to rename",
+            ));
+        }
+
+        Ok(relay_attrs)
+    }
+}
+
+/// The `///` doc comment on an item, with the leading space of each line removed, if it has one.
+fn doc_comment(attrs: &[syn::Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|a| a.path().is_ident("doc"))
+        .filter_map(|a| match &a.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(line),
+                        ..
+                    }),
+                ..
+            }) => Some(line.value()),
+            _ => None,
+        })
+        .map(|line| line.strip_prefix(' ').unwrap_or(&line).to_string())
+        .collect();
+
+    let doc = lines.join("\n").trim().to_string();
+    (!doc.is_empty()).then_some(doc)
+}
+
+/// Description for a generated type, followed by the node's doc comment if it has one.
+fn describe_type(kind: &str, node: &Ident, node_doc: &Option<String>) -> String {
+    match node_doc {
+        Some(doc) => format!("{kind} type for {node}.\n\n{doc}"),
+        None => format!("{kind} type for {node}."),
+    }
+}
+
 /// Macro that will generate Connection and Edge structs for you to use when returning lists.
 #[proc_macro_derive(RelayConnection, attributes(relay))]
 pub fn macro_relay_connection_node(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
-    let relay_attrs: Option<syn::punctuated::Punctuated<syn::MetaNameValue, syn::Token![,]>> =
-        input
-            .attrs
-            .iter()
-            .find(|a| a.path().is_ident("relay"))
-            .and_then(|a| {
-                a.parse_args_with(
-            syn::punctuated::Punctuated::<syn::MetaNameValue, syn::Token![,]>::parse_terminated
-        ).ok()
-            });
-
-    let context_attr = relay_attrs
-        .as_ref()
-        .and_then(|attrs| attrs.iter().find(|mnv| mnv.path.is_ident("context")))
-        .and_then(|mnv| {
-            if let syn::Expr::Path(p) = &mnv.value {
-                Some(p.path.clone())
-            } else {
-                None
-            }
-        });
+    let relay_attrs = match RelayAttributes::from_attrs(&input.attrs) {
+        Ok(attrs) => attrs,
+        Err(err) => return err.to_compile_error().into(),
+    };
 
-    let context_clause = if let Some(ref ctx_path) = context_attr {
+    let context_clause = if let Some(ref ctx_path) = relay_attrs.context {
         quote! { , context = #ctx_path }
     } else {
         quote! {}
     };
 
-    let cursor_attr = relay_attrs
-        .and_then(|attrs| attrs.into_iter().find(|mnv| mnv.path.is_ident("cursor")))
-        .and_then(|mnv| {
-            if let syn::Expr::Path(p) = mnv.value {
-                Some(p.path)
-            } else {
-                None
-            }
-        });
+    // Without a `scalar` the generated types are generic over the `ScalarValue`, as Juniper's derives
+    // are, but the node check needs a concrete one.
+    let (scalar_clause, scalar_type) = match &relay_attrs.scalar {
+        Some(scalar) => (quote! { , scalar = #scalar }, quote! { #scalar }),
+        None => (quote! {}, quote! { juniper::DefaultScalarValue }),
+    };
 
-    let cursor_type = if let Some(cursor_path) = &cursor_attr {
+    let cursor_type = if let Some(cursor_path) = &relay_attrs.cursor {
         quote! { #cursor_path }
     } else {
         quote! { juniper_relay_helpers::StringCursor }
     };
 
+    let connection_impl_clause =
+        InterfaceAttributes::impl_clause(&relay_attrs.interface.connection);
+    let edge_impl_clause = InterfaceAttributes::impl_clause(&relay_attrs.interface.edge);
+    let page_info_impl_clause = InterfaceAttributes::impl_clause(&relay_attrs.interface.page_info);
+
+    let rust_suffix = relay_attrs
+        .rust_suffix
+        .as_ref()
+        .map_or("RelayConnection".to_string(), |suffix| suffix.value());
+    let gql_suffix = relay_attrs
+        .gql_suffix
+        .as_ref()
+        .map_or("Connection".to_string(), |suffix| suffix.value());
+    let edge_rust_suffix = relay_attrs
+        .edge_rust_suffix
+        .as_ref()
+        .map_or("RelayEdge".to_string(), |suffix| suffix.value());
+    let edge_gql_suffix = relay_attrs
+        .edge_gql_suffix
+        .as_ref()
+        .map_or("Edge".to_string(), |suffix| suffix.value());
+
+    // The connection owns its nodes, and the generated impls can't carry a borrow through their
+    // `Cow` and async constructors, so borrowing nodes are turned away with a pointer to the lifetime.
+    if let Some(lifetime) = input.generics.lifetimes().next() {
+        return syn::Error::new_spanned(
+            lifetime,
+            "RelayConnection can't be derived for nodes with lifetime parameters, as the connection \
+             owns its nodes; use owned fields, or `&'static` references, instead",
+        )
+        .to_compile_error()
+        .into();
+    }
+
     let out = match input.data {
         Data::Struct(_s) => {
-            let connection_gql_name = format!("{}Connection", input.ident);
-            let connection_gql_desc = format!("Connection type for {}.", input.ident);
-            let connection_name = Ident::new(
-                &format!("{}RelayConnection", input.ident),
-                Span::mixed_site(),
-            );
+            let connection_gql_name = format!("{}{}", input.ident, gql_suffix);
+            let node_doc = doc_comment(&input.attrs);
+            let connection_gql_desc = describe_type("Connection", &input.ident, &node_doc);
+            let connection_name =
+                match syn::parse_str::<Ident>(&format!("{}{}", input.ident, rust_suffix)) {
+                    Ok(ident) => Ident::new(&ident.to_string(), Span::mixed_site()),
+                    Err(_) => {
+                        return syn::Error::new_spanned(
+                            relay_attrs.rust_suffix,
+                            "rust_suffix must form a valid identifier",
+                        )
+                        .to_compile_error()
+                        .into();
+                    }
+                };
 
-            let edge_gql_name = format!("{}Edge", input.ident);
-            let edge_gql_desc = format!("Edge type for {}.", input.ident);
-            let edge_name = Ident::new(&format!("{}RelayEdge", input.ident), Span::mixed_site());
-            let edge_trait_name = Ident::new(
-                &format!("{}RelayEdgeTrait", input.ident),
-                Span::mixed_site(),
-            );
+            let edge_gql_name = format!("{}{}", input.ident, edge_gql_suffix);
+            let edge_gql_desc = describe_type("Edge", &input.ident, &node_doc);
+            let edge_name =
+                match syn::parse_str::<Ident>(&format!("{}{}", input.ident, edge_rust_suffix)) {
+                    Ok(ident) => Ident::new(&ident.to_string(), Span::mixed_site()),
+                    Err(_) => {
+                        return syn::Error::new_spanned(
+                            relay_attrs.edge_rust_suffix,
+                            "edge_rust_suffix must form a valid identifier",
+                        )
+                        .to_compile_error()
+                        .into();
+                    }
+                };
 
-            let page_info_gql_name = format!("{}ConnectionPageInfo", input.ident);
+            let page_info_gql_name = format!("{}PageInfo", connection_gql_name);
             let page_info_gql_desc = format!("PageInfo type for {}.", input.ident);
-            let page_info_name = Ident::new(
-                &format!("{}RelayConnectionPageInfo", input.ident),
-                Span::mixed_site(),
+            let page_info_name =
+                Ident::new(&format!("{}PageInfo", connection_name), Span::mixed_site());
+
+            let page_args_gql_name = format!("{}PageArgs", input.ident);
+            let page_args_gql_desc = format!(
+                "Pagination arguments for {}. Page forwards with first and after, or backwards with last and before, but not both.",
+                input.ident
             );
+            let page_args_name =
+                Ident::new(&format!("{}RelayPageArgs", input.ident), Span::mixed_site());
 
+            // The generated types are as visible as the node, unless told otherwise.
+            let vis = relay_attrs.visibility.as_ref().unwrap_or(&input.vis);
             let struct_name = input.ident;
+            let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+            let struct_type = quote! { #struct_name #ty_generics };
+            // With `rc_node` the connection is built from, and its edges hold, `Arc<Node>`s.
+            let node_type = if relay_attrs.rc_node {
+                quote! { ::std::sync::Arc<#struct_type> }
+            } else {
+                struct_type.clone()
+            };
+            // With `non_null_cursor` every edge holds a cursor, making it `cursor: Foo!` in the schema.
+            // An edge can't be built from parts without one.
+            let (edge_cursor_type, wrap_cursor, cursor_ref, cursor_from_option, cursor_into_option) =
+                if relay_attrs.non_null_cursor {
+                    (
+                        quote! { #cursor_type },
+                        quote! { cursor },
+                        quote! { Some(&self.cursor) },
+                        quote! { cursor? },
+                        quote! { Some(self.cursor) },
+                    )
+                } else {
+                    (
+                        quote! { Option<#cursor_type> },
+                        quote! { Some(cursor) },
+                        quote! { self.cursor.as_ref() },
+                        quote! { cursor },
+                        quote! { self.cursor },
+                    )
+                };
+            let connection_type = quote! { #connection_name #ty_generics };
+            let edge_type = quote! { #edge_name #ty_generics };
+
+            let page_args = if relay_attrs.page_args {
+                quote! {
+                    #[derive(juniper::GraphQLInputObject, Clone, Debug)]
+                    #[graphql(name = #page_args_gql_name, description = #page_args_gql_desc)]
+                    #vis struct #page_args_name {
+                        #[graphql(description = "The number of items to return, paging forwards")]
+                        pub first: Option<i32>,
+
+                        #[graphql(description = "Return items after this cursor, paging forwards")]
+                        pub after: Option<#cursor_type>,
+
+                        #[graphql(description = "The number of items to return, paging backwards")]
+                        pub last: Option<i32>,
+
+                        #[graphql(description = "Return items before this cursor, paging backwards")]
+                        pub before: Option<#cursor_type>,
+                    }
+
+                    impl #page_args_name {
+                        /// Builds the `PageRequest`, rejecting arguments that mix forward and backward paging.
+                        pub fn into_page_request(
+                            self
+                        ) -> Result<juniper_relay_helpers::PageRequest<#cursor_type>, juniper_relay_helpers::PageRequestError> {
+                            juniper_relay_helpers::PageRequest::from_args(self.first, self.after, self.last, self.before)
+                        }
+                    }
+                }
+            } else {
+                quote! {}
+            };
+
+            // Goes through the library's re-export so the user's crate doesn't need serde itself.
+            let page_info_serde = if relay_attrs.serde {
+                quote! {
+                    #[derive(
+                        juniper_relay_helpers::__private::serde::Serialize,
+                        juniper_relay_helpers::__private::serde::Deserialize
+                    )]
+                    #[serde(crate = "juniper_relay_helpers::__private::serde")]
+                }
+            } else {
+                quote! {}
+            };
+
+            let (page_size_field, page_size_init) = if relay_attrs.expose_page_size {
+                (
+                    quote! {
+                        #[graphql(description = "The page size that was applied when building this connection")]
+                        pub page_size: Option<i32>,
+                    },
+                    quote! {
+                        page_size: metadata.page_request.as_ref().and_then(|pr| pr.first.or(pr.last)),
+                    },
+                )
+            } else {
+                (quote! {}, quote! {})
+            };
+
+            let (count_field, count_init, count_value) = if relay_attrs.no_count {
+                (quote! {}, quote! {}, quote! { None })
+            } else {
+                let count_name = relay_attrs
+                    .count_name
+                    .as_ref()
+                    .map_or("count".to_string(), |name| name.value());
+                (
+                    quote! {
+                        #[graphql(name = #count_name)]
+                        pub count: Option<i32>,
+                    },
+                    quote! { count: metadata.known_total_count(), },
+                    quote! { self.count },
+                )
+            };
+
+            // `nodes` comes first in the constructors, so it's built before the nodes move into the edges.
+            let (nodes_field, nodes_init, nodes_init_owned, nodes_init_edges) = if relay_attrs
+                .with_nodes
+            {
+                (
+                    quote! {
+                        #[graphql(description = "The nodes in the connection, without the edges around them")]
+                        pub nodes: Vec<#node_type>,
+                    },
+                    quote! { nodes: nodes.unwrap_or_default().iter().flatten().cloned().collect(), },
+                    quote! { nodes: nodes.iter().flatten().cloned().collect(), },
+                    quote! { nodes: edges.iter().filter_map(|edge| edge.node.clone()).collect(), },
+                )
+            } else {
+                (quote! {}, quote! {}, quote! {}, quote! {})
+            };
+
+            let (errors_field, errors_init, new_partial) = if relay_attrs.partial_errors {
+                (
+                    quote! {
+                        #[graphql(description = "Describes any nodes that failed to load and are missing from the edges")]
+                        pub errors: Vec<String>,
+                    },
+                    quote! { errors: Vec::new(), },
+                    quote! {
+                        impl #impl_generics #connection_type #where_clause {
+                            /// As `RelayConnection::new`, but also reports the nodes that failed to load in `errors`.
+                            pub fn new_partial<ProviderT>(
+                                nodes: Option<&[Option<#node_type>]>,
+                                errors: Vec<String>,
+                                total_items: Option<i32>,
+                                cursor_provider: ProviderT,
+                                page_request: Option<juniper_relay_helpers::PageRequest<#cursor_type>>,
+                            ) -> Self
+                            where
+                                ProviderT: juniper_relay_helpers::CursorProvider<#node_type, CursorType = #cursor_type>
+                            {
+                                let mut connection = <Self as juniper_relay_helpers::RelayConnection>::new(
+                                    nodes,
+                                    total_items,
+                                    cursor_provider,
+                                    page_request,
+                                );
+                                connection.errors = errors;
+                                connection
+                            }
+                        }
+                    },
+                )
+            } else {
+                (quote! {}, quote! {}, quote! {})
+            };
+
+            // `nodes` mirrors the edges, so it's reordered and merged along with them.
+            let (reverse_nodes, merge_nodes) = if relay_attrs.with_nodes {
+                (
+                    quote! { self.nodes.reverse(); },
+                    quote! { self.nodes.extend(other.nodes); },
+                )
+            } else {
+                (quote! {}, quote! {})
+            };
+            let merge_count = if relay_attrs.no_count {
+                quote! {}
+            } else {
+                quote! {
+                    self.count = self
+                        .count
+                        .zip(other.count)
+                        .map(|(count, other_count)| count.saturating_add(other_count));
+                }
+            };
+            let merge_errors = if relay_attrs.partial_errors {
+                quote! { self.errors.extend(other.errors); }
+            } else {
+                quote! {}
+            };
+
+            // The provider's own total, if it has one, wins over the one passed in.
+            let provider_total = quote! {
+                let metadata = juniper_relay_helpers::PaginationMetadata::<#cursor_type> {
+                    total_count: cursor_provider.total_count(&metadata).or(metadata.total_count),
+                    ..metadata
+                };
+            };
+
+            // Extra fields are left for the resolver to fill in after building the connection.
+            let extra_field_names: Vec<&Ident> =
+                relay_attrs.extra_fields.iter().map(|f| &f.name).collect();
+            let extra_field_types: Vec<&syn::Type> =
+                relay_attrs.extra_fields.iter().map(|f| &f.ty).collect();
+            let extra_fields = quote! { #(pub #extra_field_names: #extra_field_types,)* };
+            let extra_fields_init =
+                quote! { #(#extra_field_names: ::std::default::Default::default(),)* };
+
+            // Builds the connection from `nodes: Vec<Option<Node>>`, moving the nodes into the edges.
+            let from_owned_nodes = quote! {
+                let cursors = cursor_provider.get_cursors_for_items(&metadata, &nodes);
+                let page_info = cursor_provider.get_page_info_for_cursors(&metadata, Some(&nodes), &cursors);
+
+                Self {
+                    #count_init
+                    #nodes_init_owned
+                    edges: Some(nodes.into_iter().zip(cursors).map(|(node, cursor)| {
+                        Some(<#edge_type as juniper_relay_helpers::RelayEdge>::new(node, cursor))
+                    }).collect()),
+                    page_info,
+                    #page_size_init
+                    #extra_fields_init
+                    #errors_init
+                }
+            };
 
             quote! {
+                const _: () = {
+                    #[allow(dead_code)]
+                    fn assert_relay_node #impl_generics () #where_clause {
+                        juniper_relay_helpers::__private::assert_relay_node::<#scalar_type, #struct_type>();
+                    }
+                };
+
+                #page_args
+
                 #[derive(juniper::GraphQLObject, Clone)]
                 #[graphql(
                     name = #connection_gql_name,
                     description = #connection_gql_desc
                     #context_clause
+                    #scalar_clause
+                    #connection_impl_clause
                 )]
-                pub struct #connection_name {
-                    pub count: Option<i32>,
-                    pub edges: Option<Vec<Option<#edge_name>>>,
+                #vis struct #connection_name #impl_generics #where_clause {
+                    #count_field
+                    pub edges: Option<Vec<Option<#edge_type>>>,
+                    #nodes_field
+                    #errors_field
                     pub page_info: #page_info_name,
+                    #page_size_field
+                    #extra_fields
                 }
 
-                use juniper_relay_helpers::RelayEdge as #edge_trait_name;
-                impl juniper_relay_helpers::RelayConnection for #connection_name {
-                    type EdgeType = #edge_name;
-                    type NodeType = #struct_name;
+                #new_partial
+
+                impl #impl_generics juniper_relay_helpers::RelayConnection for #connection_type #where_clause {
+                    type EdgeType = #edge_type;
+                    type NodeType = #node_type;
                     type CursorType = #cursor_type;
+                    type PageInfoType = #page_info_name;
 
                     fn new<ProviderT>(
-                        nodes: Option<&[Option<#struct_name>]>,
+                        nodes: Option<&[Option<#node_type>]>,
                         total_items: Option<i32>,
                         cursor_provider: ProviderT,
                         page_request: Option<juniper_relay_helpers::PageRequest<#cursor_type>>,
@@ -108,20 +619,225 @@ pub fn macro_relay_connection_node(input: TokenStream) -> TokenStream {
                     {
                         let metadata = juniper_relay_helpers::PaginationMetadata::<#cursor_type> {
                             total_count: total_items,
-                            page_request
+                            page_request,
+                            more_available: None,
                         };
+                        Self::from_metadata(nodes, metadata, cursor_provider)
+                    }
+
+                    fn try_new<ProviderT>(
+                        nodes: Option<&[Option<#node_type>]>,
+                        total_items: Option<i32>,
+                        cursor_provider: ProviderT,
+                        page_request: Option<juniper_relay_helpers::PageRequest<#cursor_type>>,
+                    ) -> Result<Self, juniper_relay_helpers::CursorError>
+                    where
+                        ProviderT: juniper_relay_helpers::CursorProvider<Self::NodeType, CursorType = #cursor_type>
+                    {
+                        let metadata = juniper_relay_helpers::PaginationMetadata::<#cursor_type> {
+                            total_count: total_items,
+                            page_request,
+                            more_available: None,
+                        };
+                        #provider_total
+                        let edges = nodes.map(|n| n.iter().enumerate().map(|(idx, node)| {
+                            let cursor = cursor_provider.try_get_cursor_for_item(&metadata, idx as i32, node.as_ref())?;
+                            Ok(Some(<#edge_type as juniper_relay_helpers::RelayEdge>::new(node.clone(), cursor)))
+                        }).collect::<Result<Vec<_>, juniper_relay_helpers::CursorError>>()).transpose()?;
+
+                        Ok(Self {
+                            #count_init
+                            #nodes_init
+                            edges,
+                            page_info: cursor_provider.try_get_page_info(&metadata, nodes)?,
+                            #page_size_init
+                            #extra_fields_init
+                            #errors_init
+                        })
+                    }
+
+                    fn from_iter<IterT, ProviderT>(
+                        nodes: IterT,
+                        total_items: Option<i32>,
+                        cursor_provider: ProviderT,
+                        page_request: Option<juniper_relay_helpers::PageRequest<#cursor_type>>,
+                    ) -> Self
+                    where
+                        IterT: IntoIterator<Item = #node_type>,
+                        ProviderT: juniper_relay_helpers::CursorProvider<Self::NodeType, CursorType = #cursor_type>
+                    {
+                        let metadata = juniper_relay_helpers::PaginationMetadata::<#cursor_type> {
+                            total_count: total_items,
+                            page_request,
+                            more_available: None,
+                        };
+                        #provider_total
+                        let nodes: Vec<Option<#node_type>> = nodes.into_iter().map(Some).collect();
+                        #from_owned_nodes
+                    }
+
+                    fn from_cow<'cow, NodesT, ProviderT>(
+                        nodes: NodesT,
+                        total_items: Option<i32>,
+                        cursor_provider: ProviderT,
+                        page_request: Option<juniper_relay_helpers::PageRequest<#cursor_type>>,
+                    ) -> Self
+                    where
+                        #node_type: 'cow,
+                        NodesT: Into<::std::borrow::Cow<'cow, [Option<#node_type>]>>,
+                        ProviderT: juniper_relay_helpers::CursorProvider<Self::NodeType, CursorType = #cursor_type>
+                    {
+                        match nodes.into() {
+                            ::std::borrow::Cow::Borrowed(nodes) => {
+                                <Self as juniper_relay_helpers::RelayConnection>::new(Some(nodes), total_items, cursor_provider, page_request)
+                            }
+                            ::std::borrow::Cow::Owned(nodes) => {
+                                let metadata = juniper_relay_helpers::PaginationMetadata::<#cursor_type> {
+                                    total_count: total_items,
+                                    page_request,
+                                    more_available: None,
+                                };
+                                #provider_total
+                                #from_owned_nodes
+                            }
+                        }
+                    }
+
+                    fn from_metadata<ProviderT>(
+                        nodes: Option<&[Option<#node_type>]>,
+                        metadata: juniper_relay_helpers::PaginationMetadata<#cursor_type>,
+                        cursor_provider: ProviderT,
+                    ) -> Self
+                    where
+                        ProviderT: juniper_relay_helpers::CursorProvider<Self::NodeType, CursorType = #cursor_type>
+                    {
+                        #provider_total
+                        let cursors = nodes
+                            .map(|n| cursor_provider.get_cursors_for_items(&metadata, n))
+                            .unwrap_or_default();
+                        // Built from the edge cursors, so the provider isn't asked for the boundary cursors again.
+                        let page_info = cursor_provider.get_page_info_for_cursors(&metadata, nodes, &cursors);
                         Self {
-                            count: total_items,
-                            edges: nodes.map(|n| n.iter().enumerate().map(|(idx, node)| {
-                                Some(
-                                    #edge_name::new(
-                                        node.clone(),
-                                        cursor_provider.get_cursor_for_item(&metadata, idx as i32, node.as_ref())
-                                    )
-                                )
-                            }).collect()),
-                            page_info: cursor_provider.get_page_info(&metadata, nodes),
+                            #count_init
+                            #nodes_init
+                            edges: nodes.map(|n| {
+                                let mut edges = Vec::with_capacity(n.len());
+                                edges.extend(n.iter().cloned().zip(cursors).map(|(node, cursor)| {
+                                    Some(<#edge_type as juniper_relay_helpers::RelayEdge>::new(node, cursor))
+                                }));
+                                edges
+                            }),
+                            page_info,
+                            #page_size_init
+                            #extra_fields_init
+                            #errors_init
+                        }
+                    }
+
+                    async fn new_async<ProviderT>(
+                        nodes: Option<&[Option<#node_type>]>,
+                        total_items: Option<i32>,
+                        cursor_provider: ProviderT,
+                        page_request: Option<juniper_relay_helpers::PageRequest<#cursor_type>>,
+                    ) -> Self
+                    where
+                        Self::NodeType: Send + Sync,
+                        Self::CursorType: Send + Sync,
+                        ProviderT: juniper_relay_helpers::AsyncCursorProvider<Self::NodeType, CursorType = #cursor_type> + Send + Sync
+                    {
+                        let metadata = juniper_relay_helpers::PaginationMetadata::<#cursor_type> {
+                            total_count: total_items,
+                            page_request,
+                            more_available: None,
+                        };
+                        let mut edges = None;
+                        if let Some(n) = nodes {
+                            let mut built = Vec::with_capacity(n.len());
+                            for (idx, node) in n.iter().enumerate() {
+                                let cursor = cursor_provider.get_cursor_for_item(&metadata, idx as i32, node.as_ref()).await;
+                                built.push(Some(<#edge_type as juniper_relay_helpers::RelayEdge>::new(node.clone(), cursor)));
+                            }
+                            edges = Some(built);
+                        }
+                        let page_info = cursor_provider.get_page_info(&metadata, nodes).await;
+
+                        Self {
+                            #count_init
+                            #nodes_init
+                            edges,
+                            page_info,
+                            #page_size_init
+                            #extra_fields_init
+                            #errors_init
+                        }
+                    }
+
+                    fn from_edges(
+                        edges: Vec<#edge_type>,
+                        total_items: Option<i32>,
+                        page_info: #page_info_name,
+                    ) -> Self {
+                        #[allow(unused_variables)]
+                        let metadata = juniper_relay_helpers::PaginationMetadata::<#cursor_type> {
+                            total_count: total_items,
+                            page_request: None,
+                            more_available: None,
+                        };
+                        Self {
+                            #count_init
+                            #nodes_init_edges
+                            edges: Some(edges.into_iter().map(Some).collect()),
+                            page_info,
+                            #page_size_init
+                            #extra_fields_init
+                            #errors_init
+                        }
+                    }
+
+                    fn into_parts(self) -> (Option<Vec<Option<#edge_type>>>, Option<i32>, #page_info_name) {
+                        (self.edges, #count_value, self.page_info)
+                    }
+
+                    fn edges(&self) -> Option<&[Option<#edge_type>]> {
+                        self.edges.as_deref()
+                    }
+
+                    fn page_info(&self) -> &#page_info_name {
+                        &self.page_info
+                    }
+
+                    fn merge(mut self, other: Self) -> Self {
+                        let edges: Vec<Option<#edge_type>> = self
+                            .edges
+                            .take()
+                            .into_iter()
+                            .chain(other.edges)
+                            .flatten()
+                            .collect();
+                        let boundary_cursor = |edge: Option<&#edge_type>| {
+                            edge.and_then(juniper_relay_helpers::RelayEdge::cursor).cloned()
+                        };
+                        let page_info = &mut self.page_info;
+                        page_info.has_previous_page |= other.page_info.has_previous_page;
+                        page_info.has_next_page |= other.page_info.has_next_page;
+                        page_info.start_cursor = boundary_cursor(edges.iter().flatten().next());
+                        page_info.end_cursor = boundary_cursor(edges.iter().flatten().next_back());
+                        self.edges = Some(edges);
+                        #merge_count
+                        #merge_nodes
+                        #merge_errors
+                        self
+                    }
+
+                    fn reverse(mut self) -> Self {
+                        if let Some(edges) = self.edges.as_mut() {
+                            edges.reverse();
                         }
+                        #reverse_nodes
+                        let page_info = &mut self.page_info;
+                        ::std::mem::swap(&mut page_info.has_next_page, &mut page_info.has_previous_page);
+                        ::std::mem::swap(&mut page_info.start_cursor, &mut page_info.end_cursor);
+                        self
                     }
                 }
 
@@ -130,31 +846,52 @@ pub fn macro_relay_connection_node(input: TokenStream) -> TokenStream {
                     name = #edge_gql_name,
                     description = #edge_gql_desc
                     #context_clause
+                    #scalar_clause
+                    #edge_impl_clause
                 )]
-                pub struct #edge_name {
-                    pub node: Option<#struct_name>,
-                    pub cursor: Option<#cursor_type>,
+                #vis struct #edge_name #impl_generics #where_clause {
+                    pub node: Option<#node_type>,
+                    pub cursor: #edge_cursor_type,
                 }
 
-                impl juniper_relay_helpers::RelayEdge for #edge_name {
-                    type NodeType = #struct_name;
+                impl #impl_generics juniper_relay_helpers::RelayEdge for #edge_type #where_clause {
+                    type NodeType = #node_type;
                     type CursorType = #cursor_type;
 
                     fn new(node: Option<Self::NodeType>, cursor: #cursor_type) -> Self {
                         Self {
                             node,
-                            cursor: Some(cursor),
+                            cursor: #wrap_cursor,
                         }
                     }
+
+                    fn node(&self) -> Option<&#node_type> {
+                        self.node.as_ref()
+                    }
+
+                    fn cursor(&self) -> Option<&#cursor_type> {
+                        #cursor_ref
+                    }
+
+                    fn try_from_parts(node: Option<Self::NodeType>, cursor: Option<#cursor_type>) -> Option<Self> {
+                        Some(Self { node, cursor: #cursor_from_option })
+                    }
+
+                    fn into_parts(self) -> (Option<Self::NodeType>, Option<#cursor_type>) {
+                        (self.node, #cursor_into_option)
+                    }
                 }
 
                 #[derive(juniper::GraphQLObject, Clone)]
+                #page_info_serde
                 #[graphql(
                     name = #page_info_gql_name,
                     description = #page_info_gql_desc
                     #context_clause
+                    #scalar_clause
+                    #page_info_impl_clause
                 )]
-                pub struct #page_info_name {
+                #vis struct #page_info_name {
                     #[graphql(description = "Indicates whether there is a page following this current one")]
                     pub has_next_page: bool,
 
@@ -172,6 +909,43 @@ pub fn macro_relay_connection_node(input: TokenStream) -> TokenStream {
                     pub end_cursor: Option<#cursor_type>,
                 }
 
+                impl #page_info_name {
+                    /// Starts building the PageInfo a field at a time. Anything left unset is `false` or `None`.
+                    pub fn builder() -> juniper_relay_helpers::PageInfoBuilder<#cursor_type> {
+                        juniper_relay_helpers::PageInfoBuilder::new()
+                    }
+
+                    /// The PageInfo for a page with no results: no pages either side, and no cursors.
+                    pub fn empty() -> Self {
+                        Self {
+                            has_next_page: false,
+                            has_previous_page: false,
+                            start_cursor: None,
+                            end_cursor: None,
+                        }
+                    }
+                }
+
+                impl juniper_relay_helpers::RelayPageInfo for #page_info_name {
+                    type CursorType = #cursor_type;
+
+                    fn has_next_page(&self) -> bool {
+                        self.has_next_page
+                    }
+
+                    fn has_previous_page(&self) -> bool {
+                        self.has_previous_page
+                    }
+
+                    fn start_cursor(&self) -> Option<&#cursor_type> {
+                        self.start_cursor.as_ref()
+                    }
+
+                    fn end_cursor(&self) -> Option<&#cursor_type> {
+                        self.end_cursor.as_ref()
+                    }
+                }
+
                 impl juniper_relay_helpers::PageInfoFactory<#cursor_type> for #page_info_name {
                     fn new(has_previous_page: bool, has_next_page: bool, start_cursor: Option<#cursor_type>, end_cursor: Option<#cursor_type>) -> Self {
                         Self {
@@ -210,26 +984,129 @@ pub fn macro_relay_connection_node(input: TokenStream) -> TokenStream {
 /// `EntityType` can now be used in `RelayIdentifier(123, EntityType::Character)` without
 /// any additional code.
 ///
-#[proc_macro_derive(IdentifierTypeDiscriminator)]
+/// Each variant is written as its lowercased name by default. Use `#[discriminator("...")]` on a
+/// variant to pick the value yourself, so the variant can be renamed without changing existing IDs:
+///
+/// ```nocompile
+/// #[derive(IdentifierTypeDiscriminator)]
+/// enum EntityType {
+///     #[discriminator("char")]
+///     PlayableCharacter,
+///     Weapon,
+/// }
+/// ```
+///
+/// Decoding matches the value exactly. Add `#[relay(case_insensitive)]` to the enum to accept any
+/// casing, such as IDs written by older code that used the variant names as-is:
+///
+/// ```nocompile
+/// #[derive(IdentifierTypeDiscriminator)]
+/// #[relay(case_insensitive)]
+/// enum EntityType {
+///     Character,
+///     Weapon,
+/// }
+/// ```
+///
+/// Unknown values are rejected with `IdentifierError::UnknownDiscriminator`, holding the value.
+/// Two variants with the same value (ignoring case with `case_insensitive`) are a compile error.
+///
+#[proc_macro_derive(IdentifierTypeDiscriminator, attributes(discriminator, relay))]
 pub fn macro_type_discriminator(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
+    let mut case_insensitive = false;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("relay")) {
+        if let Err(err) = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("case_insensitive") {
+                case_insensitive = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported relay attribute"))
+            }
+        }) {
+            return err.to_compile_error().into();
+        }
+    }
+
     let out = match input.data {
         Data::Enum(e) => {
             let enum_name = input.ident;
-            let enum_display_variants = e.variants.iter().map(|v| {
-                let v_string = v.ident.to_string().to_lowercase();
+
+            // Each variant's wire value: the `#[discriminator("...")]` if given, else the lowercased name.
+            let variant_values = match e
+                .variants
+                .iter()
+                .map(|v| {
+                    let value = match v.attrs.iter().find(|a| a.path().is_ident("discriminator")) {
+                        Some(attr) => {
+                            let value = attr.parse_args::<syn::LitStr>()?;
+                            // `::` separates the discriminator from the id in a RelayIdentifier.
+                            if value.value().is_empty() || value.value().contains("::") {
+                                return Err(syn::Error::new_spanned(
+                                    value,
+                                    "discriminator values must be non-empty and can't contain `::`",
+                                ));
+                            }
+                            value.value()
+                        }
+                        None => v.ident.to_string().to_lowercase(),
+                    };
+                    Ok((v.ident.clone(), value))
+                })
+                .collect::<syn::Result<Vec<_>>>()
+            {
+                Ok(values) => values,
+                Err(err) => return err.to_compile_error().into(),
+            };
+
+            // Two variants with the same value couldn't be told apart when decoding.
+            for (idx, (v, v_string)) in variant_values.iter().enumerate() {
+                let duplicate = variant_values[..idx].iter().any(|(_, earlier)| {
+                    earlier == v_string
+                        || (case_insensitive && earlier.eq_ignore_ascii_case(v_string))
+                });
+                if duplicate {
+                    let variant = e.variants.iter().find(|variant| &variant.ident == v);
+                    return syn::Error::new_spanned(
+                        variant,
+                        format!("duplicate discriminator value `{v_string}`"),
+                    )
+                    .to_compile_error()
+                    .into();
+                }
+            }
+
+            let enum_display_variants = variant_values.iter().map(|(v, v_string)| {
                 quote! {
-                    #enum_name::#v => { write!(f, #v_string) }
+                    #enum_name::#v => { f.write_str(#v_string) }
                 }
             });
-            let fromstr_display_variants = e.variants.iter().map(|v| {
-                let v_string = v.ident.to_string().to_lowercase();
-                let v = v.ident.clone();
+            let fromstr_body = if case_insensitive {
+                let checks = variant_values.iter().map(|(v, v_string)| {
+                    quote! {
+                        if s.eq_ignore_ascii_case(#v_string) {
+                            return Ok(#enum_name::#v);
+                        }
+                    }
+                });
                 quote! {
-                    #v_string => Ok(#enum_name::#v)
+                    #(#checks)*
+                    Err(juniper_relay_helpers::IdentifierError::UnknownDiscriminator(s.to_string()))
                 }
-            });
+            } else {
+                let arms = variant_values.iter().map(|(v, v_string)| {
+                    quote! {
+                        #v_string => Ok(#enum_name::#v)
+                    }
+                });
+                quote! {
+                    match s {
+                        #(#arms),*,
+                        _ => Err(juniper_relay_helpers::IdentifierError::UnknownDiscriminator(s.to_string()))
+                    }
+                }
+            };
 
             quote! {
                 impl std::fmt::Display for #enum_name {
@@ -241,12 +1118,9 @@ pub fn macro_type_discriminator(input: TokenStream) -> TokenStream {
                 }
 
                 impl std::str::FromStr for #enum_name {
-                    type Err = &'static str;
+                    type Err = juniper_relay_helpers::IdentifierError;
                     fn from_str(s: &str) -> Result<Self, Self::Err> {
-                        match s {
-                            #(#fromstr_display_variants),*,
-                            &_ => Err("Invalid type delimiter")
-                        }
+                        #fromstr_body
                     }
                 }
             }
@@ -256,3 +1130,169 @@ pub fn macro_type_discriminator(input: TokenStream) -> TokenStream {
 
     out.into()
 }
+
+/// Options parsed from a `#[relay_resolver(...)]` attribute.
+#[derive(Default)]
+struct ResolverAttributes {
+    cursor: Option<syn::Type>,
+    provider: Option<syn::Expr>,
+}
+
+impl ResolverAttributes {
+    fn from_attr(attr: &syn::Attribute) -> syn::Result<Self> {
+        let mut resolver_attrs = ResolverAttributes::default();
+        if matches!(attr.meta, syn::Meta::Path(_)) {
+            return Ok(resolver_attrs);
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("cursor") {
+                resolver_attrs.cursor = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("provider") {
+                resolver_attrs.provider = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("unsupported relay_resolver attribute"));
+            }
+            Ok(())
+        })?;
+        Ok(resolver_attrs)
+    }
+}
+
+/// Splits `Conn`, `Result<Conn, E>` or `FieldResult<Conn>` into the connection type, and whether
+/// it's wrapped in a result.
+fn resolver_connection_type(output: &syn::ReturnType) -> syn::Result<(syn::Type, bool)> {
+    let syn::ReturnType::Type(_, ty) = output else {
+        return Err(syn::Error::new_spanned(
+            output,
+            "relay_resolver functions must return the connection type",
+        ));
+    };
+
+    if let syn::Type::Path(path) = ty.as_ref()
+        && let Some(segment) = path.path.segments.last()
+        && segment.ident.to_string().ends_with("Result")
+        && let syn::PathArguments::AngleBracketed(args) = &segment.arguments
+        && let Some(syn::GenericArgument::Type(connection)) = args.args.first()
+    {
+        return Ok((connection.clone(), true));
+    }
+    Ok((ty.as_ref().clone(), false))
+}
+
+/// Rewrites a `#[relay_resolver]` method to take `first` and `after`, and to build the connection
+/// from the `(nodes, total)` its body evaluates to.
+fn expand_relay_resolver(
+    method: &mut syn::ImplItemFn,
+    resolver_attrs: ResolverAttributes,
+) -> syn::Result<()> {
+    let (connection_type, is_result) = resolver_connection_type(&method.sig.output)?;
+    let cursor_type = match resolver_attrs.cursor {
+        Some(cursor) => quote! { #cursor },
+        None => quote! { <#connection_type as juniper_relay_helpers::RelayConnection>::CursorType },
+    };
+    let provider = match resolver_attrs.provider {
+        Some(provider) => quote! { #provider },
+        None => quote! { juniper_relay_helpers::OffsetCursorProvider::new() },
+    };
+
+    method
+        .sig
+        .inputs
+        .push(syn::parse_quote! { first: Option<i32> });
+    method
+        .sig
+        .inputs
+        .push(syn::parse_quote! { after: Option<#cursor_type> });
+
+    let body = &method.block;
+    let connection = quote! {
+        <#connection_type as juniper_relay_helpers::RelayConnection>::from_iter(
+            nodes,
+            total_items,
+            #provider,
+            Some(page_request),
+        )
+    };
+    let connection = if is_result {
+        quote! { Ok(#connection) }
+    } else {
+        connection
+    };
+
+    method.block = syn::parse_quote! {
+        {
+            let page_request = juniper_relay_helpers::PageRequest::<#cursor_type>::new(first, after, None);
+            let (nodes, total_items) = #body;
+            #connection
+        }
+    };
+    Ok(())
+}
+
+/// Wires up the pagination for the methods of a `graphql_object` impl marked `#[relay_resolver]`,
+/// so each only has to fetch its page of nodes.
+///
+/// A marked method gets `first` and `after` arguments added, and a `page_request` built from them
+/// for its body to fetch with. The body evaluates to `(nodes, total)`, where `nodes` is anything
+/// iterable over the node type and `total` is an `Option<i32>`, and the method returns the
+/// connection built from them. The return type is the connection, optionally in a `Result` or
+/// `FieldResult`, in which case `?` works in the body.
+///
+/// Goes above `#[graphql_object]`, so the arguments are in place before Juniper reads them:
+///
+/// ```nocompile
+/// #[relay_resolvers]
+/// #[juniper::graphql_object(context = Context)]
+/// impl QueryRoot {
+///     #[relay_resolver]
+///     async fn locations(ctx: &Context) -> FieldResult<LocationRelayConnection> {
+///         let locations = ctx.db.locations(&page_request).await?;
+///         (locations, Some(ctx.db.count_locations().await?))
+///     }
+/// }
+/// ```
+///
+/// `#[relay_resolver(cursor = ..., provider = ...)]` sets the `after` argument's type (defaulting to
+/// the connection's cursor type) and the cursor provider (defaulting to the `OffsetCursorProvider`).
+///
+/// The body is inlined, so it must not `return` early, and it should borrow `page_request` rather
+/// than move it.
+#[proc_macro_attribute]
+pub fn relay_resolvers(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut item_impl = parse_macro_input!(item as syn::ItemImpl);
+
+    for impl_item in item_impl.items.iter_mut() {
+        let syn::ImplItem::Fn(method) = impl_item else {
+            continue;
+        };
+        let Some(pos) = method
+            .attrs
+            .iter()
+            .position(|a| a.path().is_ident("relay_resolver"))
+        else {
+            continue;
+        };
+
+        let attr = method.attrs.remove(pos);
+        if let Err(err) = ResolverAttributes::from_attr(&attr)
+            .and_then(|resolver_attrs| expand_relay_resolver(method, resolver_attrs))
+        {
+            return err.to_compile_error().into();
+        }
+    }
+
+    quote! { #item_impl }.into()
+}
+
+/// Marks a method for `#[relay_resolvers]` to wire up. Only valid inside an impl with that attribute,
+/// which removes it, so it doesn't need importing.
+#[proc_macro_attribute]
+pub fn relay_resolver(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let item = proc_macro2::TokenStream::from(item);
+    let err = syn::Error::new(
+        Span::call_site(),
+        "#[relay_resolver] needs #[relay_resolvers] on the impl block, above #[graphql_object]",
+    )
+    .to_compile_error();
+    quote! { #err #item }.into()
+}
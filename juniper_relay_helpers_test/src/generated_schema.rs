@@ -1,8 +1,13 @@
 #[cfg(test)]
 mod integration_tests {
     use googletest::prelude::*;
-    use juniper::{EmptyMutation, EmptySubscription, FieldResult, GraphQLObject, RootNode};
-    use juniper_relay_helpers::{RelayConnection, StringCursor};
+    use juniper::{EmptyMutation, EmptySubscription, FieldResult, GraphQLObject, ID, RootNode};
+    use juniper_relay_helpers::{
+        ConnectionArgs, Cursor, OffsetCursor, OffsetCursorProvider, PageRequest, RelayConnection,
+        StringCursor, relay_resolvers,
+    };
+    use std::marker::PhantomData;
+    use std::sync::Arc;
 
     // ---- Define the types ----
 
@@ -12,14 +17,133 @@ mod integration_tests {
     }
 
     #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq, RelayConnection)]
+    #[relay(expose_page_size)]
     pub struct Post {
         title: String,
     }
 
+    #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq, RelayConnection)]
+    #[relay(cursor = OffsetCursor, page_args)]
+    pub struct Tag {
+        name: String,
+    }
+
+    #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq, RelayConnection)]
+    #[relay(
+        cursor = OffsetCursor,
+        extra_field(name = "total_unread", ty = "i32"),
+        extra_field(name = "folder", ty = "Option<String>")
+    )]
+    pub struct Message {
+        subject: String,
+    }
+
+    #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq, RelayConnection)]
+    #[relay(cursor = OffsetCursor, with_nodes)]
+    pub struct Pictos {
+        name: String,
+    }
+
+    #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq, RelayConnection)]
+    #[relay(cursor = OffsetCursor, partial_errors)]
+    pub struct Journal {
+        author: String,
+    }
+
+    /// Which sensor a reading came from, only known on the Rust side.
+    #[derive(Debug, Clone, Eq, PartialEq)]
+    pub struct Barometer;
+
+    #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq, RelayConnection)]
+    #[relay(cursor = OffsetCursor)]
+    pub struct Reading<SensorT>
+    where
+        SensorT: Clone + Send + Sync + 'static,
+    {
+        value: i32,
+        #[graphql(skip)]
+        sensor: PhantomData<SensorT>,
+    }
+
+    #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq, RelayConnection)]
+    #[relay(cursor = OffsetCursor, count_name = "totalCount")]
+    pub struct Quest {
+        title: String,
+    }
+
+    #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq, RelayConnection)]
+    #[relay(cursor = OffsetCursor, no_count)]
+    pub struct Event {
+        title: String,
+    }
+
+    #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq, RelayConnection)]
+    #[relay(rust_suffix = "Conn", gql_suffix = "List")]
+    pub struct Comment {
+        body: String,
+    }
+
+    /// Comments on a different kind of thing, with the same name as the one above.
+    mod threads {
+        use juniper::GraphQLObject;
+        use juniper_relay_helpers::RelayConnection;
+
+        #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq, RelayConnection)]
+        #[graphql(name = "ThreadComment")]
+        #[relay(
+            gql_suffix = "Thread",
+            edge_gql_suffix = "ThreadEdge",
+            edge_rust_suffix = "ThreadEdge"
+        )]
+        pub struct Comment {
+            pub body: String,
+        }
+    }
+
+    #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq, RelayConnection)]
+    #[relay(cursor = OffsetCursor)]
+    pub struct Expedition {
+        number: i32,
+    }
+
+    #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq, RelayConnection)]
+    #[relay(cursor = OffsetCursor)]
+    pub struct Gestral {
+        #[graphql(name = "fightingStyle")]
+        style: String,
+    }
+
+    #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq, RelayConnection)]
+    #[relay(cursor = OffsetCursor, rc_node)]
+    pub struct Nevron {
+        name: String,
+    }
+
+    /// A colour drawn from a canvas.
+    ///
+    /// Painters spend it to paint.
+    #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq, RelayConnection)]
+    #[relay(cursor = OffsetCursor, non_null_cursor)]
+    pub struct Chroma {
+        hue: String,
+    }
+
+    /// A node with no fields of its own, only an `id` resolved from elsewhere.
+    #[derive(Debug, Clone, Eq, PartialEq, RelayConnection)]
+    pub struct Marker;
+
+    #[juniper::graphql_object]
+    impl Marker {
+        fn id() -> ID {
+            ID::new("marker")
+        }
+    }
+
     // ----- Build the query root ----
 
     struct QueryRoot;
 
+    #[relay_resolvers]
     #[juniper::graphql_object()]
     impl QueryRoot {
         fn get_users() -> FieldResult<UserRelayConnection> {
@@ -58,6 +182,237 @@ mod integration_tests {
                     has_previous_page: false,
                     has_next_page: false,
                 },
+                page_size: Some(10),
+            })
+        }
+
+        fn get_shared_args_tags(
+            args: ConnectionArgs<OffsetCursor>,
+        ) -> FieldResult<TagRelayConnection> {
+            let tags = vec![Some(Tag {
+                name: "Lumière".to_owned(),
+            })];
+            Ok(TagRelayConnection::new(
+                Some(&tags),
+                Some(1),
+                OffsetCursorProvider::new(),
+                Some(args.into_page_request()?),
+            ))
+        }
+
+        fn get_shared_args_users(
+            args: ConnectionArgs<StringCursor>,
+        ) -> FieldResult<UserRelayConnection> {
+            let page_request = args.into_page_request()?;
+            Ok(UserRelayConnection {
+                count: Some(0),
+                edges: Some(vec![]),
+                page_info: UserRelayConnectionPageInfo {
+                    start_cursor: None,
+                    end_cursor: None,
+                    has_previous_page: page_request.after.is_some(),
+                    has_next_page: false,
+                },
+            })
+        }
+
+        fn get_tags(args: Option<TagRelayPageArgs>) -> FieldResult<TagRelayConnection> {
+            let page_request = match args {
+                Some(args) => args.into_page_request()?,
+                None => PageRequest::new(Some(10), None, None),
+            };
+            let tags = vec![Some(Tag {
+                name: "Expedition 33".to_owned(),
+            })];
+            Ok(TagRelayConnection::new(
+                Some(&tags),
+                Some(1),
+                OffsetCursorProvider::new(),
+                Some(page_request),
+            ))
+        }
+
+        fn get_messages() -> FieldResult<MessageRelayConnection> {
+            let mut conn = MessageRelayConnection::new(
+                Some(&[Some(Message {
+                    subject: "Gommage".to_string(),
+                })]),
+                Some(1),
+                OffsetCursorProvider::new(),
+                None,
+            );
+            conn.total_unread = 3;
+            Ok(conn)
+        }
+
+        fn get_quests() -> FieldResult<QuestRelayConnection> {
+            Ok(QuestRelayConnection::new(
+                Some(&[Some(Quest {
+                    title: "Reach the Monolith".to_string(),
+                })]),
+                Some(33),
+                OffsetCursorProvider::new(),
+                None,
+            ))
+        }
+
+        fn get_events() -> FieldResult<EventRelayConnection> {
+            let events: Vec<Option<Event>> = ["Gommage", "Expedition Festival"]
+                .into_iter()
+                .map(|title| {
+                    Some(Event {
+                        title: title.to_string(),
+                    })
+                })
+                .collect();
+
+            Ok(EventRelayConnection::new(
+                Some(&events),
+                None,
+                OffsetCursorProvider::new(),
+                Some(PageRequest::new(Some(2), None, None)),
+            ))
+        }
+
+        fn get_comments() -> FieldResult<CommentConn> {
+            Ok(CommentConn {
+                count: Some(0),
+                edges: Some(vec![]),
+                page_info: CommentConnPageInfo {
+                    start_cursor: None,
+                    end_cursor: None,
+                    has_previous_page: false,
+                    has_next_page: false,
+                },
+            })
+        }
+
+        fn get_thread() -> FieldResult<threads::CommentRelayConnection> {
+            Ok(threads::CommentRelayConnection {
+                count: Some(1),
+                edges: Some(vec![Some(threads::CommentThreadEdge {
+                    node: Some(threads::Comment {
+                        body: "Tomorrow comes".to_string(),
+                    }),
+                    cursor: None,
+                })]),
+                page_info: threads::CommentRelayConnectionPageInfo {
+                    start_cursor: None,
+                    end_cursor: None,
+                    has_previous_page: false,
+                    has_next_page: false,
+                },
+            })
+        }
+
+        fn get_pictos() -> FieldResult<PictosRelayConnection> {
+            let pictos = vec![
+                Some(Pictos {
+                    name: "Dodger".to_string(),
+                }),
+                None,
+                Some(Pictos {
+                    name: "Augmented Attack".to_string(),
+                }),
+            ];
+
+            Ok(PictosRelayConnection::new(
+                Some(&pictos),
+                Some(3),
+                OffsetCursorProvider::new(),
+                None,
+            ))
+        }
+
+        fn get_journals() -> FieldResult<JournalRelayConnection> {
+            Ok(JournalRelayConnection::new_partial(
+                Some(&[Some(Journal {
+                    author: "Gustave".to_string(),
+                })]),
+                vec!["Journal 2 could not be loaded".to_string()],
+                Some(2),
+                OffsetCursorProvider::new(),
+                None,
+            ))
+        }
+
+        fn get_readings() -> FieldResult<ReadingRelayConnection<Barometer>> {
+            let readings: Vec<Option<Reading<Barometer>>> = [1013, 1009]
+                .into_iter()
+                .map(|value| {
+                    Some(Reading {
+                        value,
+                        sensor: PhantomData,
+                    })
+                })
+                .collect();
+
+            Ok(ReadingRelayConnection::new(
+                Some(&readings),
+                Some(2),
+                OffsetCursorProvider::new(),
+                None,
+            ))
+        }
+
+        #[relay_resolver]
+        fn get_expeditions() -> FieldResult<ExpeditionRelayConnection> {
+            let start = page_request
+                .after
+                .as_ref()
+                .map_or(0, |after| after.offset + 1);
+            let end = (start + page_request.first.unwrap_or(10)).min(70);
+            let expeditions = (start..end).map(|number| Expedition { number });
+            (expeditions, Some(70))
+        }
+
+        fn get_gestrals() -> FieldResult<GestralRelayConnection> {
+            Ok(GestralRelayConnection::new(
+                Some(&[Some(Gestral {
+                    style: "Beach volleyball".to_string(),
+                })]),
+                Some(1),
+                OffsetCursorProvider::new(),
+                None,
+            ))
+        }
+
+        fn get_nevrons() -> FieldResult<NevronRelayConnection> {
+            let nevron = Arc::new(Nevron {
+                name: "Lancelier".to_string(),
+            });
+            Ok(NevronRelayConnection::new(
+                Some(&[Some(Arc::clone(&nevron)), Some(nevron)]),
+                Some(2),
+                OffsetCursorProvider::new(),
+                None,
+            ))
+        }
+
+        fn get_chromas() -> FieldResult<ChromaRelayConnection> {
+            Ok(ChromaRelayConnection::new(
+                Some(&[Some(Chroma {
+                    hue: "Blue".to_string(),
+                })]),
+                Some(1),
+                OffsetCursorProvider::new(),
+                None,
+            ))
+        }
+
+        fn get_markers() -> FieldResult<MarkerRelayConnection> {
+            Ok(MarkerRelayConnection {
+                count: Some(1),
+                edges: Some(vec![Some(MarkerRelayEdge {
+                    node: Some(Marker),
+                    cursor: None,
+                })]),
+                page_info: MarkerRelayConnectionPageInfo {
+                    start_cursor: None,
+                    end_cursor: None,
+                    has_previous_page: false,
+                    has_next_page: false,
+                },
             })
         }
     }
@@ -69,6 +424,241 @@ mod integration_tests {
         Schema::new(QueryRoot, EmptyMutation::new(), EmptySubscription::new())
     }
 
+    // ---- Conformance schema: a single connection, compared against a checked-in SDL file ----
+
+    #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq, RelayConnection)]
+    pub struct Lumina {
+        name: String,
+    }
+
+    struct ConformanceQuery;
+
+    #[juniper::graphql_object()]
+    impl ConformanceQuery {
+        fn luminas() -> LuminaRelayConnection {
+            LuminaRelayConnection::from_edges(
+                vec![],
+                Some(0),
+                LuminaRelayConnectionPageInfo {
+                    has_next_page: false,
+                    has_previous_page: false,
+                    start_cursor: None,
+                    end_cursor: None,
+                },
+            )
+        }
+    }
+
+    type ConformanceSchema = RootNode<ConformanceQuery, EmptyMutation, EmptySubscription>;
+
+    /// The derived connection, edge and `PageInfo` types must match the Relay connection spec
+    /// exactly. If this fails, check the change against the spec before updating
+    /// `connection_sdl.graphql`: the field names and nullability are part of the public schema.
+    #[test]
+    fn connection_sdl_matches_golden_file() {
+        let schema = ConformanceSchema::new(
+            ConformanceQuery,
+            EmptyMutation::new(),
+            EmptySubscription::new(),
+        );
+        let expected = include_str!("connection_sdl.graphql");
+
+        assert_that!(schema.as_sdl().trim(), eq(expected.trim()));
+    }
+
+    // ---- Interface schema: connections sharing Connection, Edge and PageInfo interfaces ----
+
+    #[allow(dead_code)]
+    #[juniper::graphql_interface(for = [PetalRelayConnection, ManorRelayConnection])]
+    trait Connection {
+        fn count(&self) -> Option<i32>;
+        fn edges(&self) -> Option<Vec<Option<EdgeValue>>>;
+        fn page_info(&self) -> PageInfoValue;
+    }
+
+    #[allow(dead_code)]
+    #[juniper::graphql_interface(for = [PetalRelayEdge, ManorRelayEdge])]
+    trait Edge {
+        fn cursor(&self) -> Option<OffsetCursor>;
+    }
+
+    #[allow(dead_code)]
+    #[juniper::graphql_interface(for = [
+        PetalRelayConnectionPageInfo,
+        ManorRelayConnectionPageInfo,
+    ])]
+    trait PageInfo {
+        fn has_next_page(&self) -> bool;
+        fn end_cursor(&self) -> Option<OffsetCursor>;
+    }
+
+    #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq, RelayConnection)]
+    #[relay(
+        cursor = OffsetCursor,
+        interface(connection = ConnectionValue, edge = EdgeValue, page_info = PageInfoValue)
+    )]
+    pub struct Petal {
+        colour: String,
+    }
+
+    #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq, RelayConnection)]
+    #[relay(
+        cursor = OffsetCursor,
+        interface(connection = ConnectionValue, edge = EdgeValue, page_info = PageInfoValue)
+    )]
+    pub struct Manor {
+        owner: String,
+    }
+
+    struct InterfaceQuery;
+
+    #[juniper::graphql_object()]
+    impl InterfaceQuery {
+        fn petals() -> PetalRelayConnection {
+            let petals = [Some(Petal {
+                colour: "red".to_string(),
+            })];
+            PetalRelayConnection::new(Some(&petals), Some(1), OffsetCursorProvider::new(), None)
+        }
+
+        fn manors() -> ManorRelayConnection {
+            let manors = [Some(Manor {
+                owner: "Dessendre".to_string(),
+            })];
+            ManorRelayConnection::new(
+                Some(&manors),
+                Some(3),
+                OffsetCursorProvider::new(),
+                Some(PageRequest::new(Some(1), None, None)),
+            )
+        }
+    }
+
+    #[test]
+    fn connections_implement_interfaces() {
+        let schema = RootNode::new(
+            InterfaceQuery,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        let sdl = schema.as_sdl();
+        assert_that!(
+            sdl,
+            contains_substring("type PetalConnection implements Connection")
+        );
+        assert_that!(sdl, contains_substring("type ManorEdge implements Edge"));
+        assert_that!(
+            sdl,
+            contains_substring("type ManorConnectionPageInfo implements PageInfo")
+        );
+
+        // One fragment on the interfaces covers both connections.
+        let (res, errors) = juniper::execute_sync(
+            r#"
+            fragment Paging on Connection {
+                count
+                edges { cursor }
+                pageInfo { hasNextPage }
+            }
+            query { petals { ...Paging } manors { ...Paging } }
+            "#,
+            None,
+            &schema,
+            &juniper::Variables::new(),
+            &(),
+        )
+        .unwrap();
+
+        assert_that!(errors, is_empty());
+        let cursor = OffsetCursor::new(0).to_encoded_string();
+        assert_that!(
+            res.to_string(),
+            eq(&format!(
+                r#"{{"petals": {{"count": 1, "edges": [{{"cursor": "{cursor}"}}], "pageInfo": {{"hasNextPage": false}}}}, "manors": {{"count": 3, "edges": [{{"cursor": "{cursor}"}}], "pageInfo": {{"hasNextPage": true}}}}}}"#
+            ))
+        );
+    }
+
+    // ---- Custom scalar schema: a schema built on its own ScalarValue ----
+
+    #[derive(
+        Clone,
+        Debug,
+        derive_more::Display,
+        derive_more::From,
+        PartialEq,
+        juniper::ScalarValue,
+        serde::Serialize,
+        serde::Deserialize,
+        derive_more::TryInto,
+    )]
+    #[serde(untagged)]
+    pub enum LongScalarValue {
+        #[value(to_float, to_int)]
+        Int(i32),
+
+        Long(i64),
+
+        #[value(to_float)]
+        Float(f64),
+
+        #[value(as_str, to_string)]
+        String(String),
+
+        #[value(to_bool)]
+        Boolean(bool),
+    }
+
+    #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq, RelayConnection)]
+    #[graphql(scalar = LongScalarValue)]
+    #[relay(cursor = OffsetCursor, scalar = LongScalarValue)]
+    pub struct Ledger {
+        entry: String,
+    }
+
+    struct LedgerQuery;
+
+    #[juniper::graphql_object(scalar = LongScalarValue)]
+    impl LedgerQuery {
+        fn ledgers(first: Option<i32>, after: Option<OffsetCursor>) -> LedgerRelayConnection {
+            let ledgers = [Some(Ledger {
+                entry: "Paintress".to_string(),
+            })];
+            LedgerRelayConnection::new(
+                Some(&ledgers),
+                Some(1),
+                OffsetCursorProvider::new(),
+                Some(PageRequest::new(first, after, None)),
+            )
+        }
+    }
+
+    #[test]
+    fn custom_scalar_connections_resolve() {
+        let schema: RootNode<_, _, _, LongScalarValue> = RootNode::new_with_scalar_value(
+            LedgerQuery,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let (res, errors) = juniper::execute_sync(
+            "query { ledgers(first: 1) { count edges { node { entry } } pageInfo { hasNextPage } } }",
+            None,
+            &schema,
+            &juniper::Variables::new(),
+            &(),
+        )
+        .unwrap();
+
+        assert_that!(errors, is_empty());
+        assert_that!(
+            res.to_string(),
+            eq(
+                r#"{"ledgers": {"count": 1, "edges": [{"node": {"entry": "Paintress"}}], "pageInfo": {"hasNextPage": false}}}"#
+            )
+        );
+    }
+
     #[test]
     fn print_schema_for_debugging() {
         let schema_document = build_schema();
@@ -116,4 +706,506 @@ mod integration_tests {
             contains_substring("type PostConnectionPageInfo")
         );
     }
+
+    #[test]
+    fn page_size_generated_when_exposed() {
+        let schema_document = build_schema();
+        let schema_sdl = schema_document.as_sdl();
+
+        assert_that!(
+            schema_sdl,
+            contains_regex(r"type PostConnection \{[^}]*pageSize: Int")
+        );
+        assert_that!(
+            schema_sdl,
+            not(contains_regex(r"type UserConnection \{[^}]*pageSize"))
+        );
+    }
+
+    #[test]
+    fn minimal_nodes_generated() {
+        let schema_document = build_schema();
+        let schema_sdl = schema_document.as_sdl();
+
+        assert_that!(schema_sdl, contains_substring("type TagConnection"));
+        assert_that!(schema_sdl, contains_substring("type TagEdge"));
+        assert_that!(schema_sdl, contains_substring("type MarkerConnection"));
+        assert_that!(schema_sdl, contains_substring("type MarkerEdge"));
+    }
+
+    #[test]
+    fn minimal_nodes_resolve() {
+        let (res, errors) = juniper::execute_sync(
+            "{ getTags { count edges { node { name } } } getMarkers { edges { node { id } } } }",
+            None,
+            &build_schema(),
+            &juniper::Variables::new(),
+            &(),
+        )
+        .unwrap();
+
+        assert_that!(errors, is_empty());
+        assert_that!(
+            res.to_string(),
+            contains_substring(r#""node": {"name": "Expedition 33"}"#)
+        );
+        assert_that!(
+            res.to_string(),
+            contains_substring(r#""node": {"id": "marker"}"#)
+        );
+    }
+
+    #[test]
+    fn page_args_generated() {
+        let schema_document = build_schema();
+        let schema_sdl = schema_document.as_sdl();
+
+        assert_that!(
+            schema_sdl,
+            contains_regex(
+                r"input TagPageArgs \{[^}]*first: Int[^}]*after: OffsetCursor[^}]*last: Int[^}]*before: OffsetCursor"
+            )
+        );
+        assert_that!(schema_sdl, not(contains_substring("input UserPageArgs")));
+    }
+
+    #[test]
+    fn shared_connection_args_generated() {
+        let schema_document = build_schema();
+        let schema_sdl = schema_document.as_sdl();
+
+        assert_that!(
+            schema_sdl,
+            contains_regex(
+                r"input OffsetCursorConnectionArgs \{[^}]*first: Int[^}]*after: OffsetCursor[^}]*last: Int[^}]*before: OffsetCursor"
+            )
+        );
+        assert_that!(
+            schema_sdl,
+            contains_regex(
+                r"input StringCursorConnectionArgs \{[^}]*first: Int[^}]*after: StringCursor[^}]*last: Int[^}]*before: StringCursor"
+            )
+        );
+        assert_that!(
+            schema_sdl,
+            contains_substring("getSharedArgsTags(args: OffsetCursorConnectionArgs!)")
+        );
+    }
+
+    #[test]
+    fn shared_connection_args_resolve() {
+        let query = |q: &str| {
+            juniper::execute_sync(q, None, &build_schema(), &juniper::Variables::new(), &())
+                .unwrap()
+        };
+
+        let (result, errors) = query(
+            r#"{ getSharedArgsTags(args: { first: 1, after: "b2Zmc2V0fHwx" }) { edges { cursor } } }"#,
+        );
+        assert_that!(errors, is_empty());
+        assert_that!(
+            result.to_string(),
+            contains_substring(OffsetCursor::new(2).to_encoded_string())
+        );
+
+        let (_, errors) =
+            query(r#"{ getSharedArgsUsers(args: { last: 1, after: "c3RyaW5nfHxh" }) { count } }"#);
+        assert_that!(errors, len(eq(1)));
+    }
+
+    #[test]
+    fn page_args_reject_mixed_directions() {
+        let query = |args: &str| {
+            juniper::execute_sync(
+                &format!("{{ getTags(args: {args}) {{ count }} }}"),
+                None,
+                &build_schema(),
+                &juniper::Variables::new(),
+                &(),
+            )
+            .unwrap()
+        };
+
+        let (_, errors) = query("{ first: 1 }");
+        assert_that!(errors, is_empty());
+
+        let (_, errors) = query(r#"{ first: 1, before: "b2Zmc2V0fHwx" }"#);
+        assert_that!(errors, len(eq(1)));
+        assert_that!(
+            errors[0].error().message(),
+            eq("Forward (first/after) and backward (last/before) arguments can't be mixed")
+        );
+    }
+
+    #[test]
+    fn custom_suffixes_generated() {
+        let schema_document = build_schema();
+        let schema_sdl = schema_document.as_sdl();
+
+        assert_that!(schema_sdl, contains_substring("type CommentList {"));
+        assert_that!(schema_sdl, contains_substring("type CommentListPageInfo {"));
+        assert_that!(schema_sdl, contains_substring("type CommentEdge {"));
+        assert_that!(
+            schema_sdl,
+            not(contains_substring("type CommentConnection"))
+        );
+    }
+
+    #[test]
+    fn edge_suffixes_generated() {
+        let schema_document = build_schema();
+        let schema_sdl = schema_document.as_sdl();
+
+        // Both nodes are `Comment`, so without the edge suffixes both edges would be `CommentEdge`.
+        assert_that!(schema_sdl, contains_substring("type CommentThread {"));
+        assert_that!(
+            schema_sdl,
+            contains_substring("edges: [CommentThreadEdge]\n")
+        );
+        assert_that!(schema_sdl, contains_substring("type CommentThreadEdge {"));
+        assert_that!(schema_sdl, contains_substring("node: ThreadComment\n"));
+        assert_that!(schema_sdl, contains_substring("type CommentEdge {"));
+    }
+
+    #[test]
+    fn extra_fields_generated() {
+        let schema_document = build_schema();
+        let schema_sdl = schema_document.as_sdl();
+
+        assert_that!(
+            schema_sdl,
+            contains_regex(r"type MessageConnection \{[^}]*totalUnread: Int![^}]*folder: String\n")
+        );
+        assert_that!(
+            schema_sdl,
+            not(contains_regex(r"type UserConnection \{[^}]*totalUnread"))
+        );
+    }
+
+    #[test]
+    fn extra_fields_resolve() {
+        let (res, errors) = juniper::execute_sync(
+            "query { getMessages { count totalUnread folder } }",
+            None,
+            &build_schema(),
+            &juniper::Variables::new(),
+            &(),
+        )
+        .unwrap();
+
+        assert_that!(errors, is_empty());
+        assert_that!(
+            res.to_string(),
+            contains_substring(r#""count": 1, "totalUnread": 3, "folder": null"#)
+        );
+    }
+
+    #[test]
+    fn no_count_generated() {
+        let schema_document = build_schema();
+        let schema_sdl = schema_document.as_sdl();
+
+        assert_that!(
+            schema_sdl,
+            contains_regex(r"type EventConnection \{[^}]*pageInfo")
+        );
+        assert_that!(
+            schema_sdl,
+            not(contains_regex(r"type EventConnection \{[^}]*count"))
+        );
+        assert_that!(
+            schema_sdl,
+            contains_regex(r"type UserConnection \{[^}]*count: Int")
+        );
+    }
+
+    #[test]
+    fn no_count_resolves_without_total() {
+        let (res, errors) = juniper::execute_sync(
+            "query { getEvents { edges { node { title } } pageInfo { hasNextPage } } }",
+            None,
+            &build_schema(),
+            &juniper::Variables::new(),
+            &(),
+        )
+        .unwrap();
+
+        assert_that!(errors, is_empty());
+        // A full page with no total is taken to mean there may be more.
+        assert_that!(
+            res.to_string(),
+            contains_substring(r#""pageInfo": {"hasNextPage": true}"#)
+        );
+    }
+
+    #[test]
+    fn count_name_generated() {
+        let schema_document = build_schema();
+        let schema_sdl = schema_document.as_sdl();
+
+        assert_that!(
+            schema_sdl,
+            contains_regex(r"type QuestConnection \{[^}]*totalCount: Int")
+        );
+        assert_that!(
+            schema_sdl,
+            not(contains_regex(r"type QuestConnection \{[^}]*\bcount:"))
+        );
+
+        let (res, errors) = juniper::execute_sync(
+            "query { getQuests { totalCount } }",
+            None,
+            &schema_document,
+            &juniper::Variables::new(),
+            &(),
+        )
+        .unwrap();
+
+        assert_that!(errors, is_empty());
+        assert_that!(res.to_string(), contains_substring(r#""totalCount": 33"#));
+    }
+
+    #[test]
+    fn generic_nodes_resolve() {
+        let (res, errors) = juniper::execute_sync(
+            "query { getReadings { count edges { node { value } } } }",
+            None,
+            &build_schema(),
+            &juniper::Variables::new(),
+            &(),
+        )
+        .unwrap();
+
+        assert_that!(errors, is_empty());
+        assert_that!(
+            res.to_string(),
+            contains_substring(r#""node": {"value": 1009}"#)
+        );
+    }
+
+    #[test]
+    fn nodes_generated_when_requested() {
+        let schema_document = build_schema();
+        let schema_sdl = schema_document.as_sdl();
+
+        assert_that!(
+            schema_sdl,
+            contains_regex(r"type PictosConnection \{[^}]*nodes: \[Pictos!\]!")
+        );
+        assert_that!(
+            schema_sdl,
+            not(contains_regex(r"type UserConnection \{[^}]*nodes"))
+        );
+
+        let (res, errors) = juniper::execute_sync(
+            "query { getPictos { nodes { name } } }",
+            None,
+            &schema_document,
+            &juniper::Variables::new(),
+            &(),
+        )
+        .unwrap();
+
+        assert_that!(errors, is_empty());
+        assert_that!(
+            res.to_string(),
+            contains_substring(r#""nodes": [{"name": "Dodger"}, {"name": "Augmented Attack"}]"#)
+        );
+    }
+
+    #[test]
+    fn relay_resolver_wires_arguments() {
+        let schema_document = build_schema();
+        assert_that!(
+            schema_document.as_sdl(),
+            contains_substring(
+                "getExpeditions(first: Int, after: OffsetCursor): ExpeditionConnection!"
+            )
+        );
+
+        let query = format!(
+            r#"query {{ getExpeditions(first: 2, after: "{}") {{ count edges {{ node {{ number }} cursor }} pageInfo {{ hasNextPage }} }} }}"#,
+            OffsetCursor::new(32).to_encoded_string()
+        );
+        let (res, errors) = juniper::execute_sync(
+            &query,
+            None,
+            &schema_document,
+            &juniper::Variables::new(),
+            &(),
+        )
+        .unwrap();
+
+        assert_that!(errors, is_empty());
+        let res = res.to_string();
+        assert_that!(res, contains_substring(r#""count": 70"#));
+        assert_that!(res, contains_substring(r#""node": {"number": 33}"#));
+        assert_that!(res, contains_substring(r#""node": {"number": 34}"#));
+        assert_that!(res, not(contains_substring(r#""number": 35"#)));
+        assert_that!(
+            res,
+            contains_substring(format!(
+                r#""cursor": "{}""#,
+                OffsetCursor::new(34).to_encoded_string()
+            ))
+        );
+        assert_that!(res, contains_substring(r#""hasNextPage": true"#));
+    }
+
+    #[test]
+    fn renamed_node_fields_kept_in_edges() {
+        let schema_document = build_schema();
+
+        let (res, errors) = juniper::execute_sync(
+            r#"query {
+                edge: __type(name: "GestralEdge") { fields { name type { name } } }
+                node: __type(name: "Gestral") { fields { name } }
+            }"#,
+            None,
+            &schema_document,
+            &juniper::Variables::new(),
+            &(),
+        )
+        .unwrap();
+
+        assert_that!(errors, is_empty());
+        let res = res.to_string();
+        assert_that!(
+            res,
+            contains_substring(r#"{"name": "node", "type": {"name": "Gestral"}}"#)
+        );
+        assert_that!(
+            res,
+            contains_substring(r#""fields": [{"name": "fightingStyle"}]"#)
+        );
+
+        let (res, errors) = juniper::execute_sync(
+            "query { getGestrals { edges { node { fightingStyle } } } }",
+            None,
+            &schema_document,
+            &juniper::Variables::new(),
+            &(),
+        )
+        .unwrap();
+
+        assert_that!(errors, is_empty());
+        assert_that!(
+            res.to_string(),
+            contains_substring(r#""node": {"fightingStyle": "Beach volleyball"}"#)
+        );
+    }
+
+    #[test]
+    fn rc_nodes_resolve() {
+        let schema_document = build_schema();
+        assert_that!(
+            schema_document.as_sdl(),
+            contains_regex(r"type NevronEdge \{[^}]*node: Nevron\n")
+        );
+
+        let (res, errors) = juniper::execute_sync(
+            "query { getNevrons { edges { node { name } } } }",
+            None,
+            &schema_document,
+            &juniper::Variables::new(),
+            &(),
+        )
+        .unwrap();
+
+        assert_that!(errors, is_empty());
+        assert_that!(
+            res.to_string(),
+            contains_substring(
+                r#""edges": [{"node": {"name": "Lancelier"}}, {"node": {"name": "Lancelier"}}]"#
+            )
+        );
+    }
+
+    #[test]
+    fn node_doc_comments_in_descriptions() {
+        let (res, errors) = juniper::execute_sync(
+            r#"query {
+                connection: __type(name: "ChromaConnection") { description }
+                edge: __type(name: "ChromaEdge") { description }
+                undocumented: __type(name: "UserConnection") { description }
+            }"#,
+            None,
+            &build_schema(),
+            &juniper::Variables::new(),
+            &(),
+        )
+        .unwrap();
+
+        assert_that!(errors, is_empty());
+        let doc = "A colour drawn from a canvas.\n\nPainters spend it to paint.";
+        let connection = format!("Connection type for Chroma.\n\n{doc}");
+        let edge = format!("Edge type for Chroma.\n\n{doc}");
+        assert_that!(
+            res.to_string(),
+            eq(&format!(
+                r#"{{"connection": {{"description": "{connection}"}}, "edge": {{"description": "{edge}"}}, "undocumented": {{"description": "Connection type for User."}}}}"#
+            ))
+        );
+    }
+
+    #[test]
+    fn non_null_cursors_generated() {
+        let schema_document = build_schema();
+        let schema_sdl = schema_document.as_sdl();
+        assert_that!(
+            schema_sdl,
+            contains_regex(r"type ChromaEdge \{[^}]*cursor: OffsetCursor!\n")
+        );
+        // Without the option the cursor stays nullable.
+        assert_that!(
+            schema_sdl,
+            contains_regex(r"type NevronEdge \{[^}]*cursor: OffsetCursor\n")
+        );
+
+        let (res, errors) = juniper::execute_sync(
+            "query { getChromas { edges { cursor } } }",
+            None,
+            &schema_document,
+            &juniper::Variables::new(),
+            &(),
+        )
+        .unwrap();
+
+        assert_that!(errors, is_empty());
+        assert_that!(
+            res.to_string(),
+            contains_substring(format!(
+                r#""cursor": "{}""#,
+                OffsetCursor::new(0).to_encoded_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn partial_errors_resolve() {
+        let schema_document = build_schema();
+        assert_that!(
+            schema_document.as_sdl(),
+            contains_regex(r"type JournalConnection \{[^}]*errors: \[String!\]!")
+        );
+
+        let (res, errors) = juniper::execute_sync(
+            "query { getJournals { edges { node { author } } errors } }",
+            None,
+            &schema_document,
+            &juniper::Variables::new(),
+            &(),
+        )
+        .unwrap();
+
+        assert_that!(errors, is_empty());
+        assert_that!(
+            res.to_string(),
+            contains_substring(r#""node": {"author": "Gustave"}"#)
+        );
+        assert_that!(
+            res.to_string(),
+            contains_substring(r#""errors": ["Journal 2 could not be loaded"]"#)
+        );
+    }
 }
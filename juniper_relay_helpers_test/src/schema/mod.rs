@@ -48,11 +48,12 @@ impl QueryRoot {
                     })
                     .collect(),
             ),
-            page_info: CharacterRelayConnectionPageInfo {
-                has_next_page: false,
-                has_previous_page: false,
-                start_cursor: None,
-                end_cursor: None,
+            page_info: match ctx.characters.len() {
+                0 => CharacterRelayConnectionPageInfo::empty(),
+                len => CharacterRelayConnectionPageInfo::builder()
+                    .start_cursor(OffsetCursor::new(0))
+                    .end_cursor(OffsetCursor::new(len as i32 - 1))
+                    .build(),
             },
         })
     }
@@ -81,6 +82,7 @@ impl QueryRoot {
                 let pagination_metadata = PaginationMetadata {
                     total_count: Some(ctx.locations.len() as i32),
                     page_request: Some(sub_page),
+                    more_available: None,
                 };
                 let item_cursor = cp.get_cursor_for_item(&pagination_metadata, 0, Some(item));
                 item_cursor.eq(after_cursor)